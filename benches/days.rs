@@ -0,0 +1,289 @@
+use aoc2022::inputs::InputManager;
+use aoc2022::year2022::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::collections::BTreeSet;
+use std::fs::read_to_string;
+use std::io::{self, Read};
+
+/// The real input of the day when there is one, its example otherwise.
+fn input(day: u8, example: &str) -> (String, &'static str) {
+    match InputManager::default().find(day).map(read_to_string) {
+        Some(Ok(data)) => (data, "input"),
+        _ => (example.to_owned(), "example"),
+    }
+}
+
+/// Benchmarks the parse and solve phases of a day separately, in a `dayN/input` or
+/// `dayN/example` group depending on the data used.
+macro_rules! bench_day {
+    ($c:expr, $number:literal, $day:ident) => {{
+        let (data, source) = input($number, $day::EXAMPLE);
+        let mut group = $c.benchmark_group(format!("day{}/{}", $number, source));
+        group.sample_size(10);
+        group.bench_function("parse", |b| {
+            b.iter(|| $day::parse(black_box(&data)).unwrap())
+        });
+        let parsed = $day::parse(&data).unwrap();
+        group.bench_function("solve", |b| {
+            b.iter(|| $day::solve(black_box(&parsed)).unwrap())
+        });
+        group.finish();
+    }};
+}
+
+fn days(c: &mut Criterion) {
+    bench_day!(c, 1, day01);
+    bench_day!(c, 2, day02);
+    bench_day!(c, 3, day03);
+    bench_day!(c, 4, day04);
+    bench_day!(c, 5, day05);
+    bench_day!(c, 6, day06);
+    bench_day!(c, 7, day07);
+    bench_day!(c, 8, day08);
+    bench_day!(c, 9, day09);
+    bench_day!(c, 10, day10);
+    bench_day!(c, 11, day11);
+    bench_day!(c, 12, day12);
+    bench_day!(c, 13, day13);
+    bench_day!(c, 14, day14);
+    bench_day!(c, 15, day15);
+    bench_day!(c, 16, day16);
+    bench_day!(c, 17, day17);
+    bench_day!(c, 18, day18);
+    bench_day!(c, 19, day19);
+    bench_day!(c, 20, day20);
+    bench_day!(c, 21, day21);
+    bench_day!(c, 22, day22);
+    bench_day!(c, 23, day23);
+    bench_day!(c, 24, day24);
+    bench_day!(c, 25, day25);
+}
+
+/// The rounds of the monkeys as they were played before the items were kept in queues: the
+/// items of each monkey are cloned at every turn. Kept to compare with [`monkey_rounds`].
+fn cloning_rounds(
+    monkeys: &[day11::Monkey],
+    n: u16,
+    strategy: &impl day11::WorryStrategy,
+) -> Vec<u64> {
+    let mut items = monkeys
+        .iter()
+        .map(|m| m.items().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let mut nb_item_inspections = vec![0u64; monkeys.len()];
+    for _ in 0..n {
+        let mut round_items: Vec<Vec<u64>> = vec![vec![]; items.len()];
+        for (mk, (monkey, monkey_items)) in monkeys.iter().zip(&items).enumerate() {
+            let mut items_to_inspect = monkey_items.clone();
+            items_to_inspect.extend(&round_items[mk]);
+            round_items[mk] = vec![];
+            nb_item_inspections[mk] += items_to_inspect.len() as u64;
+            for item in items_to_inspect {
+                let worry = strategy.inspect(monkey.operation(), item).unwrap();
+                let (if_true, if_false) = monkey.targets();
+                let throw_to = if worry.is_multiple_of(monkey.divisor()) {
+                    if_true
+                } else {
+                    if_false
+                };
+                round_items[usize::from(throw_to)].push(worry);
+            }
+        }
+        items = round_items;
+    }
+    nb_item_inspections
+}
+
+/// The simulation of the monkeys over the 10000 rounds of part 2, which dominates day 11, against
+/// the cloning rounds it replaced.
+fn monkey_rounds(c: &mut Criterion) {
+    let (data, source) = input(11, day11::EXAMPLE);
+    let monkeys = day11::parse(&data).unwrap();
+    let strategy = day11::ModuloDivisors::new(&monkeys).unwrap();
+    let mut group = c.benchmark_group(format!("day11/{source}"));
+    group.bench_function("10000 rounds", |b| {
+        b.iter(|| {
+            day11::compute_score(black_box(&monkeys), day11::Business::default(), &strategy)
+                .unwrap()
+        })
+    });
+    group.bench_function("10000 cloning rounds", |b| {
+        b.iter(|| cloning_rounds(black_box(&monkeys), day11::WORRYING_ROUNDS, &strategy))
+    });
+    group.finish();
+}
+
+/// Next pseudo-random number below `bound`, good enough to make up inputs.
+fn random(seed: &mut u32, bound: usize) -> usize {
+    *seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+    (*seed >> 16) as usize % bound
+}
+
+/// A square forest of pseudo-random trees, much larger than the puzzle one.
+fn large_forest(side: usize) -> String {
+    let mut seed = 42u32;
+    (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| char::from(b'0' + random(&mut seed, 10) as u8))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The best scenic score of day 8 as it was found before the monotonic stacks: the row and the
+/// column of each tree are rescanned in the four directions. Kept to compare with
+/// [`large_forests`].
+fn rescanning_best_spot((data, (nb_rows, nb_columns)): &day08::Forest) -> u32 {
+    let (nb_rows, nb_columns) = (*nb_rows, *nb_columns);
+    // trees seen from `c` along `line`, up to the first one at least as tall
+    let viewing_distance = |c: u8, line: &mut dyn Iterator<Item = usize>| {
+        let mut count = 0u32;
+        for ix in line {
+            count += 1;
+            if data[ix] >= c {
+                break;
+            }
+        }
+        count
+    };
+    data.iter()
+        .enumerate()
+        .map(|(i, &c)| {
+            let (row, column) = (i / nb_columns, i % nb_columns);
+            let row_start = row * nb_columns;
+            viewing_distance(c, &mut (column..i).step_by(nb_columns).rev())
+                * viewing_distance(
+                    c,
+                    &mut (i + nb_columns..nb_rows * nb_columns).step_by(nb_columns),
+                )
+                * viewing_distance(c, &mut (row_start..i).rev())
+                * viewing_distance(c, &mut (i + 1..row_start + nb_columns))
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Day 8 on a forest large enough to tell how the solver scales, against the rescans of the
+/// scenic scores it replaced: these leave out the visibility of the trees, which the solver
+/// times too.
+fn large_forests(c: &mut Criterion) {
+    let forest = day08::parse(&large_forest(500)).unwrap();
+    let mut group = c.benchmark_group("day8/large");
+    group.sample_size(10);
+    group.bench_function("500x500 solve", |b| {
+        b.iter(|| day08::solve(black_box(&forest)).unwrap())
+    });
+    group.bench_function("500x500 rescanning scenic scores", |b| {
+        b.iter(|| rescanning_best_spot(black_box(&forest)))
+    });
+    group.finish();
+}
+
+/// Groups of three pseudo-random rucksacks, each with a single misplaced item and a single
+/// badge per group, as in the puzzle.
+fn large_rucksacks(groups: usize) -> String {
+    const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    const FILLERS: usize = 10;
+    let mut seed = 42u32;
+    let mut rucksacks = Vec::with_capacity(groups * 3);
+    for _ in 0..groups {
+        let mut items = ITEMS.to_vec();
+        for i in (1..items.len()).rev() {
+            items.swap(i, random(&mut seed, i + 1));
+        }
+        // the badge, then the misplaced item and the items of each half of each rucksack: the
+        // pools of items do not overlap, so nothing else is shared
+        let (badge, rest) = items.split_first().unwrap();
+        let pool = (rest.len() - 3) / 6;
+        for (k, &misplaced) in rest[..3].iter().enumerate() {
+            let mut fill = |half: usize, n: usize| {
+                let start = 3 + (2 * k + half) * pool;
+                (0..n)
+                    .map(|_| char::from(rest[start + random(&mut seed, pool)]))
+                    .collect::<String>()
+            };
+            let first = fill(0, FILLERS);
+            let second = fill(1, FILLERS + 1);
+            rucksacks.push(format!(
+                "{}{}{first}{}{second}",
+                char::from(misplaced),
+                char::from(*badge),
+                char::from(misplaced)
+            ));
+        }
+    }
+    rucksacks.join("\n")
+}
+
+/// The priorities of day 3 as they were computed before the items became bitmasks: each
+/// rucksack is split into sets of items, which are intersected. Kept to compare with
+/// [`large_rucksack_lists`].
+fn btree_set_priorities(rucksacks: &[String]) -> (u32, u32) {
+    let misplaced = rucksacks
+        .iter()
+        .map(|s| {
+            let (part1, part2) = s.split_at(s.len() / 2);
+            let part1 = part1.chars().collect::<BTreeSet<char>>();
+            let part2 = part2.chars().collect::<BTreeSet<char>>();
+            part1.intersection(&part2).next().map_or(0, day03::priority)
+        })
+        .sum();
+    let badges = rucksacks
+        .chunks_exact(3)
+        .map(|group| {
+            let sets = group
+                .iter()
+                .map(|s| s.chars().collect::<BTreeSet<char>>())
+                .collect::<Vec<_>>();
+            let common = sets[0].intersection(&sets[1]).collect::<BTreeSet<_>>();
+            sets[2]
+                .iter()
+                .find(|c| common.contains(c))
+                .map_or(0, day03::priority)
+        })
+        .sum();
+    (misplaced, badges)
+}
+
+/// Day 3 on enough rucksacks to tell how the item sets perform, against the sets of items it
+/// intersected before.
+fn large_rucksack_lists(c: &mut Criterion) {
+    let rucksacks = day03::parse(&large_rucksacks(10_000)).unwrap();
+    let mut group = c.benchmark_group("day3/large");
+    group.sample_size(10);
+    group.bench_function("30000 rucksacks solve", |b| {
+        b.iter(|| day03::solve(black_box(&rucksacks)).unwrap())
+    });
+    group.bench_function("30000 rucksacks solve, BTreeSet", |b| {
+        b.iter(|| btree_set_priorities(black_box(&rucksacks)))
+    });
+    group.finish();
+}
+
+/// Day 6 on a signal of 10 MB whose markers come last, read as a stream.
+fn long_signals(c: &mut Criterion) {
+    const NOISE: u64 = 10_000_000;
+    c.benchmark_group("day6/large")
+        .sample_size(10)
+        .bench_function("10 MB stream", |b| {
+            b.iter(|| {
+                let signal = io::repeat(b'a').take(NOISE).chain(&b"abcdefghijklmnop"[..]);
+                day06::fix_device(black_box(signal)).unwrap()
+            })
+        });
+}
+
+criterion_group!(
+    benches,
+    days,
+    monkey_rounds,
+    large_forests,
+    large_rucksack_lists,
+    long_signals
+);
+criterion_main!(benches);
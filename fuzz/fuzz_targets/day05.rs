@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc2022::year2022::day05;
+use libfuzzer_sys::fuzz_target;
+
+// any stacks specification and moves must be parsed or rejected, never panic
+fuzz_target!(|data: &str| {
+    let _ = day05::parse(data);
+});
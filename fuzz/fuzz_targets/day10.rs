@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc2022::year2022::day10;
+use libfuzzer_sys::fuzz_target;
+
+// any program must be parsed or rejected, never panic
+fuzz_target!(|data: &str| {
+    let _ = day10::parse(data);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc2022::year2022::day11;
+use libfuzzer_sys::fuzz_target;
+
+// any monkey notes must be parsed or rejected, never panic
+fuzz_target!(|data: &str| {
+    let _ = day11::parse(data);
+});
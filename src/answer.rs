@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The answer to a part of a puzzle.
+///
+/// It is written as a number, a string or an array of strings in JSON and TOML, so answers files
+/// can give the expected answers as they are printed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    /// A drawing, one row after the other, like the CRT screen of day 10.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// The drawing made of the lines of `text`.
+    pub fn grid(text: &str) -> Self {
+        Answer::Grid(text.lines().map(str::to_owned).collect())
+    }
+
+    /// Whether the answer takes more than a line to print.
+    pub fn is_multiline(&self) -> bool {
+        match self {
+            Answer::Number(_) => false,
+            Answer::Text(text) => text.contains('\n'),
+            Answer::Grid(rows) => rows.len() > 1,
+        }
+    }
+
+    /// Whether both answers print the same, but for trailing whitespaces: a number may be
+    /// expected as text, and a drawing with a trailing newline.
+    pub fn matches(&self, other: &Answer) -> bool {
+        self.to_string().trim_end() == other.to_string().trim_end()
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+/// A part of a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = u8;
+
+    fn try_from(number: u8) -> Result<Self, u8> {
+        match number {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(number),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+macro_rules! number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n.into())
+                }
+            }
+        )*
+    };
+}
+
+number!(u8, u16, u32, i8, i16, i32, i64);
+
+macro_rules! large_number {
+    ($($t:ty),*) => {
+        $(
+            // too large numbers are kept as text rather than truncated
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    i64::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Number)
+                }
+            }
+        )*
+    };
+}
+
+large_number!(u64, usize, i128, u128);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn conversions() {
+        assert_that!(Answer::from(42u16)).is_equal_to(Answer::Number(42));
+        assert_that!(Answer::from(-3i64)).is_equal_to(Answer::Number(-3));
+        assert_that!(Answer::from(30893109657usize)).is_equal_to(Answer::Number(30893109657));
+        assert_that!(Answer::from(u64::MAX)).is_equal_to(Answer::Text(u64::MAX.to_string()));
+        assert_that!(Answer::from("MCD")).is_equal_to(Answer::Text("MCD".to_owned()));
+    }
+
+    #[test]
+    fn printed() {
+        assert_that!(Answer::Number(24000).to_string().as_str()).is_equal_to("24000");
+        assert_that!(Answer::grid("##..\n..##").to_string().as_str()).is_equal_to("##..\n..##");
+        assert_that!(Answer::grid("##..\n..##").is_multiline()).is_true();
+        assert_that!(Answer::from("MCD").is_multiline()).is_false();
+    }
+
+    #[test]
+    fn matching() {
+        assert_that!(Answer::Number(24000).matches(&Answer::from("24000"))).is_true();
+        assert_that!(Answer::from("##..\n..##\n").matches(&Answer::grid("##..\n..##"))).is_true();
+        assert_that!(Answer::Number(24000).matches(&Answer::Number(45000))).is_false();
+    }
+
+    #[test]
+    fn parts() {
+        assert_that!(Part::try_from(2))
+            .is_ok()
+            .is_equal_to(Part::Two);
+        assert_that!(Part::try_from(3)).is_err().is_equal_to(3);
+        assert_that!(Part::One.to_string().as_str()).is_equal_to("1");
+    }
+
+    #[test]
+    fn serialized() {
+        let answers = [
+            Answer::Number(24000),
+            Answer::from("MCD"),
+            Answer::grid("##..\n..##"),
+        ];
+
+        let json = serde_json::to_string(&answers).unwrap();
+
+        assert_that!(json.as_str()).is_equal_to(r###"[24000,"MCD",["##..","..##"]]"###);
+        assert_that!(serde_json::from_str::<Vec<Answer>>(&json))
+            .is_ok()
+            .is_equal_to(answers.to_vec());
+    }
+}
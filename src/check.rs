@@ -0,0 +1,250 @@
+use crate::answer::Answer;
+use crate::color::{paint, Color};
+use crate::runner::Report;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs::read_to_string;
+use std::path::Path;
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+struct ExpectedAnswers {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+/// Known-correct answers, by day, as written in an `answers.toml` file:
+///
+/// ```toml
+/// [1]
+/// part1 = 24000
+/// part2 = 45000
+///
+/// [5]
+/// part2 = "MCD"
+///
+/// [10]
+/// part2 = ["#..#", ".##."]
+/// ```
+#[derive(Deserialize, Debug, Default)]
+#[serde(try_from = "BTreeMap<String, ExpectedAnswers>")]
+pub struct Answers(BTreeMap<u8, ExpectedAnswers>);
+
+impl TryFrom<BTreeMap<String, ExpectedAnswers>> for Answers {
+    type Error = String;
+
+    // TOML table names are strings, so the days are parsed here
+    fn try_from(answers: BTreeMap<String, ExpectedAnswers>) -> Result<Self, Self::Error> {
+        answers
+            .into_iter()
+            .map(|(day, expected)| match day.parse() {
+                Ok(day) => Ok((day, expected)),
+                Err(_) => Err(format!("invalid day {day:?}")),
+            })
+            .collect::<Result<_, _>>()
+            .map(Answers)
+    }
+}
+
+impl Answers {
+    pub fn parse(data: &str) -> Result<Self> {
+        Ok(toml::from_str(data)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let data =
+            read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+        Self::parse(&data).with_context(|| format!("invalid answers in {}", path.display()))
+    }
+
+    /// Whether an answer is expected for any part of `day`.
+    pub fn has_day(&self, day: u8) -> bool {
+        self.0
+            .get(&day)
+            .is_some_and(|answers| answers.part1.is_some() || answers.part2.is_some())
+    }
+
+    fn expected(&self, day: u8, part: u8) -> Option<&Answer> {
+        let answers = self.0.get(&day)?;
+        match part {
+            1 => answers.part1.as_ref(),
+            _ => answers.part2.as_ref(),
+        }
+    }
+}
+
+/// A part whose answer differs from the expected one, `actual` being `None` when the day failed
+/// or did not report this part.
+#[derive(Debug, PartialEq)]
+pub struct Mismatch {
+    pub day: u8,
+    pub part: u8,
+    pub expected: Answer,
+    pub actual: Option<Answer>,
+}
+
+impl Mismatch {
+    /// Describes the mismatch, with the expected answer in green and the wrong one in red when
+    /// `colored`.
+    pub fn describe(&self, colored: bool) -> String {
+        let actual = self
+            .actual
+            .as_ref()
+            .map_or_else(|| "nothing".to_owned(), Answer::to_string);
+        let multiline =
+            self.expected.is_multiline() || self.actual.as_ref().is_some_and(Answer::is_multiline);
+        let sep = if multiline { "\n" } else { " " };
+        format!(
+            "Day {} part {}: expected{sep}{}{sep}but got{sep}{}",
+            self.day,
+            self.part,
+            paint(&self.expected.to_string(), Color::Green, colored),
+            paint(&actual, Color::Red, colored)
+        )
+    }
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.describe(false))
+    }
+}
+
+/// Compares the answers of the reports to the expected ones. Parts without an expected answer
+/// are not checked.
+pub fn check(reports: &[Report], expected: &Answers) -> Vec<Mismatch> {
+    reports
+        .iter()
+        .flat_map(|report| [1, 2].map(|part| (report, part)))
+        .filter_map(|(report, part)| {
+            let expected = expected.expected(report.day, part)?;
+            let actual = report
+                .outcome
+                .as_ref()
+                .ok()
+                .and_then(|(answers, _)| answers.get(part));
+            match actual {
+                Some(answer) if expected.matches(answer) => None,
+                _ => Some(Mismatch {
+                    day: report.day,
+                    part,
+                    expected: expected.clone(),
+                    actual: actual.cloned(),
+                }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::runner;
+    use spectral::prelude::*;
+
+    const ANSWERS: &str = r##"
+[1]
+part1 = 24000
+part2 = 45000
+
+[5]
+part2 = "MCD"
+
+[8]
+part1 = ["#.", ".#"]
+
+[10]
+part2 = '''
+##..
+..##
+'''
+"##;
+
+    fn report(day: u8, answers: runner::Answers) -> Report {
+        Report {
+            day,
+            diagnosis: None,
+            outcome: Ok((answers, Default::default())),
+        }
+    }
+
+    #[test]
+    fn all_good() {
+        let reports = [
+            report(1, runner::Answers::from((24000, 45000))),
+            report(5, runner::Answers::part2("MCD")),
+            report(8, runner::Answers::part1(Answer::grid("#.\n.#"))),
+            report(
+                10,
+                runner::Answers::from((13140, Answer::grid("##..\n..##"))),
+            ),
+            report(12, runner::Answers::from((31, 29))),
+        ];
+
+        let mismatches = check(&reports, &Answers::parse(ANSWERS).unwrap());
+
+        assert_that!(mismatches).is_empty();
+    }
+
+    #[test]
+    fn wrong_and_failed() {
+        let reports = [
+            report(1, runner::Answers::from((24000, 41000))),
+            Report {
+                day: 5,
+                diagnosis: None,
+                outcome: Err("no input".to_owned()),
+            },
+        ];
+
+        let mismatches = check(&reports, &Answers::parse(ANSWERS).unwrap());
+
+        assert_that!(mismatches).is_equal_to(vec![
+            Mismatch {
+                day: 1,
+                part: 2,
+                expected: Answer::Number(45000),
+                actual: Some(Answer::Number(41000)),
+            },
+            Mismatch {
+                day: 5,
+                part: 2,
+                expected: Answer::from("MCD"),
+                actual: None,
+            },
+        ]);
+    }
+
+    #[test]
+    fn colored_mismatch() {
+        let mismatch = Mismatch {
+            day: 1,
+            part: 2,
+            expected: Answer::Number(45000),
+            actual: Some(Answer::Number(41000)),
+        };
+
+        assert_that!(mismatch.to_string().as_str())
+            .is_equal_to("Day 1 part 2: expected 45000 but got 41000");
+        assert_that!(mismatch.describe(true).as_str()).is_equal_to(
+            "Day 1 part 2: expected \x1b[32m45000\x1b[0m but got \x1b[31m41000\x1b[0m",
+        );
+    }
+
+    #[test]
+    fn days_with_answers() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_that!(answers.has_day(5)).is_true();
+        assert_that!(answers.has_day(7)).is_false();
+    }
+
+    #[test]
+    fn invalid_file() {
+        assert_that!(Answers::parse("[1]\npart3 = 12")).is_err();
+        assert_that!(Answers::parse("[day1]\npart1 = 12")).is_err();
+    }
+}
@@ -0,0 +1,114 @@
+//! Colors of the terminal output, as ANSI escape codes.
+
+use std::ffi::OsStr;
+use std::io::IsTerminal;
+
+/// When to color the output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// When printing to a terminal, unless `NO_COLOR` is set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to color what is printed to `stream`.
+    pub fn enabled(self, stream: &impl IsTerminal) -> bool {
+        let no_color = std::env::var_os("NO_COLOR");
+        self.enabled_with(no_color.as_deref(), stream.is_terminal())
+    }
+
+    // see https://no-color.org: an empty NO_COLOR does not count
+    fn enabled_with(self, no_color: Option<&OsStr>, terminal: bool) -> bool {
+        match self {
+            ColorChoice::Auto => terminal && no_color.is_none_or(OsStr::is_empty),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Bold,
+    Dim,
+}
+
+impl Color {
+    fn code(self) -> &'static str {
+        match self {
+            Color::Red => "31",
+            Color::Green => "32",
+            Color::Yellow => "33",
+            Color::Bold => "1",
+            Color::Dim => "2",
+        }
+    }
+}
+
+/// `text` in `color`, or left as it is when not `enabled`.
+pub fn paint(text: &str, color: Color, enabled: bool) -> String {
+    if enabled && !text.is_empty() {
+        format!("\x1b[{}m{text}\x1b[0m", color.code())
+    } else {
+        text.to_owned()
+    }
+}
+
+/// `text` with the runs of `pixel` in `color`, like the lit pixels of a screen.
+pub fn tint(text: &str, pixel: char, color: Color, enabled: bool) -> String {
+    if !enabled {
+        return text.to_owned();
+    }
+    let mut tinted = String::with_capacity(text.len());
+    let mut run = String::new();
+    for c in text.chars() {
+        if c == pixel {
+            run.push(c);
+        } else {
+            tinted.push_str(&paint(&run, color, true));
+            run.clear();
+            tinted.push(c);
+        }
+    }
+    tinted.push_str(&paint(&run, color, true));
+    tinted
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn choice() {
+        let no_color = Some(OsStr::new("1"));
+
+        assert_that!(ColorChoice::Auto.enabled_with(None, true)).is_true();
+        assert_that!(ColorChoice::Auto.enabled_with(None, false)).is_false();
+        assert_that!(ColorChoice::Auto.enabled_with(no_color, true)).is_false();
+        assert_that!(ColorChoice::Auto.enabled_with(Some(OsStr::new("")), true)).is_true();
+        assert_that!(ColorChoice::Always.enabled_with(no_color, false)).is_true();
+        assert_that!(ColorChoice::Never.enabled_with(None, true)).is_false();
+    }
+
+    #[test]
+    fn painted() {
+        assert_that!(paint("failed", Color::Red, true).as_str())
+            .is_equal_to("\x1b[31mfailed\x1b[0m");
+        assert_that!(paint("failed", Color::Red, false).as_str()).is_equal_to("failed");
+    }
+
+    #[test]
+    fn tinted() {
+        assert_that!(tint("##.#\n..##", '#', Color::Green, true).as_str())
+            .is_equal_to("\x1b[32m##\x1b[0m.\x1b[32m#\x1b[0m\n..\x1b[32m##\x1b[0m");
+        assert_that!(tint("##.#", '#', Color::Green, false).as_str()).is_equal_to("##.#");
+    }
+}
@@ -0,0 +1,103 @@
+use crate::output::Format;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs::read_to_string;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Where the configuration is read from when no other file is given.
+pub const DEFAULT_PATH: &str = "aoc.toml";
+
+/// Defaults for the command line, as written in an `aoc.toml` file; the options given on the
+/// command line override them:
+///
+/// ```toml
+/// year = 2022
+/// days = [1, 5, 12]
+/// input_dir = "inputs"
+/// session_file = "session.txt"
+/// format = "csv"
+/// ```
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub year: Option<u16>,
+    /// Days to solve, all of them when missing or empty.
+    #[serde(default)]
+    pub days: Vec<u8>,
+    /// Directory to look for the inputs in first.
+    pub input_dir: Option<PathBuf>,
+    /// File holding the value of the `session` cookie, used when `AOC_SESSION` is not set.
+    pub session_file: Option<PathBuf>,
+    pub format: Option<Format>,
+}
+
+impl Config {
+    pub fn parse(data: &str) -> Result<Self> {
+        Ok(toml::from_str(data)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let data =
+            read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+        Self::parse(&data).with_context(|| format!("invalid configuration in {}", path.display()))
+    }
+
+    /// Loads the configuration from `path` if given, or else from `aoc.toml` if there is one.
+    pub fn find(path: Option<&Path>) -> Result<Self> {
+        match path {
+            Some(path) => Self::load(path),
+            None => match read_to_string(DEFAULT_PATH) {
+                Ok(data) => Self::parse(&data)
+                    .with_context(|| format!("invalid configuration in {DEFAULT_PATH}")),
+                Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+                Err(e) => Err(e).with_context(|| format!("cannot read {DEFAULT_PATH}")),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn full() {
+        let config = Config::parse(
+            r#"
+year = 2022
+days = [1, 5]
+input_dir = "inputs"
+session_file = "session.txt"
+format = "json"
+"#,
+        );
+
+        assert_that!(config).is_ok().is_equal_to(Config {
+            year: Some(2022),
+            days: vec![1, 5],
+            input_dir: Some(PathBuf::from("inputs")),
+            session_file: Some(PathBuf::from("session.txt")),
+            format: Some(Format::Json),
+        });
+    }
+
+    #[test]
+    fn empty() {
+        assert_that!(Config::parse(""))
+            .is_ok()
+            .is_equal_to(Config::default());
+    }
+
+    #[test]
+    fn unknown_key() {
+        assert_that!(Config::parse("dayz = [1]")).is_err();
+    }
+
+    #[test]
+    fn unknown_format() {
+        assert_that!(Config::parse(r#"format = "xml""#)).is_err();
+    }
+}
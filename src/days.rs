@@ -0,0 +1,79 @@
+use crate::answer::{Answer, Part};
+use crate::error::{AocError, Result};
+use crate::runner::Day;
+use crate::year2022;
+
+/// Every year with solved puzzles, with its days in order.
+pub const YEARS: [(u16, &[Day]); 1] = [(year2022::YEAR, &year2022::DAYS)];
+
+/// The year run when none is given.
+pub const DEFAULT_YEAR: u16 = year2022::YEAR;
+
+/// The days of the given year, if any of them has been solved.
+pub fn year(year: u16) -> Option<&'static [Day]> {
+    YEARS
+        .iter()
+        .find(|(number, _)| *number == year)
+        .map(|(_, days)| *days)
+}
+
+/// The day of the given year and number, if it has been solved.
+pub fn day(year: u16, number: u8) -> Option<&'static Day> {
+    self::year(year)?.iter().find(|day| day.number == number)
+}
+
+/// Solves a part of a day of the default year on `input`.
+pub fn solve(day: u8, part: Part, input: &str) -> Result<Answer> {
+    let solver = self::day(DEFAULT_YEAR, day).ok_or(AocError::UnknownDay(day))?;
+    let (answers, _) = (solver.solver)(input)?;
+    answers
+        .get(part.number())
+        .cloned()
+        .ok_or(AocError::NoAnswer { day, part })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn lookup() {
+        assert_that!(day(2022, 7).map(|day| day.number))
+            .is_some()
+            .is_equal_to(7);
+        assert_that!(day(2022, 26).map(|day| day.number)).is_none();
+        assert_that!(day(2015, 7).map(|day| day.number)).is_none();
+    }
+
+    #[test]
+    fn solved_parts() {
+        let example = year2022::day01::EXAMPLE;
+
+        assert_that!(solve(1, Part::Two, example))
+            .is_ok()
+            .is_equal_to(Answer::Number(45000));
+        assert_that!(matches!(
+            solve(26, Part::One, example),
+            Err(AocError::UnknownDay(26))
+        ))
+        .is_true();
+        assert_that!(matches!(
+            solve(25, Part::Two, year2022::day25::EXAMPLE),
+            Err(AocError::NoAnswer {
+                day: 25,
+                part: Part::Two
+            })
+        ))
+        .is_true();
+    }
+
+    #[test]
+    fn registered_in_order() {
+        for (_, days) in YEARS {
+            assert_that!(days.iter().map(|day| day.number).collect::<Vec<_>>())
+                .is_equal_to((1..=25).collect::<Vec<_>>());
+        }
+    }
+}
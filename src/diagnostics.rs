@@ -0,0 +1,395 @@
+use crate::error::AocError;
+use miette::{
+    Diagnostic, GraphicalReportHandler, GraphicalTheme, LabeledSpan, NamedSource, SourceCode,
+    SourceSpan,
+};
+use nom::character::complete::multispace0;
+use nom::sequence::terminated;
+use nom::{AsChar, InputLength, InputTakeAtPosition, Offset, Parser};
+use nom_supreme::error::{ErrorTree, GenericErrorTree};
+use nom_supreme::final_parser::{final_parser, ByteOffset};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::iter;
+
+/// A puzzle input the parser of a day choked on, pointing at where it went wrong and at what
+/// the parser was reading there.
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("bad input")]
+pub struct BadInput {
+    src: String,
+    bad_bit: SourceSpan,
+    kind: String,
+    /// Where the enclosing parsers started, innermost first, with what they were parsing.
+    contexts: Vec<(SourceSpan, String)>,
+}
+
+/// Span of the character at `offset` in `src`, empty at the end of it.
+fn char_span(src: &str, offset: usize) -> SourceSpan {
+    let length = src[offset..].chars().next().map_or(0, char::len_utf8);
+    SourceSpan::new(offset.into(), length.into())
+}
+
+impl BadInput {
+    fn new(src: &str, error: ErrorTree<ByteOffset>) -> Self {
+        let trace = deepest(error);
+        // highlight the offending character, if any
+        Self {
+            src: src.to_owned(),
+            bad_bit: char_span(src, trace.offset),
+            kind: trace.kind,
+            contexts: trace
+                .contexts
+                .into_iter()
+                .map(|(offset, context)| (char_span(src, offset), context))
+                .collect(),
+        }
+    }
+
+    /// Points at `fragment`, a part of `src` that could not be parsed.
+    pub fn at(src: &str, fragment: &str, kind: impl Display) -> Self {
+        Self {
+            src: src.to_owned(),
+            bad_bit: SourceSpan::new(src.offset(fragment).into(), fragment.len().into()),
+            kind: kind.to_string(),
+            contexts: vec![],
+        }
+    }
+
+    /// Points at `line`, the line numbered `number` from 1 of an input read line by line.
+    pub fn on_line(number: usize, line: &str, kind: impl Display) -> Self {
+        // the earlier lines are left blank, for the line to keep its number
+        let src = "\n".repeat(number.saturating_sub(1)) + line;
+        Self {
+            bad_bit: SourceSpan::new((src.len() - line.len()).into(), line.len().into()),
+            src,
+            kind: kind.to_string(),
+            contexts: vec![],
+        }
+    }
+
+    /// Byte offset of the error in the input.
+    pub fn offset(&self) -> usize {
+        self.bad_bit.offset()
+    }
+
+    /// Line and column of the error in the input, both counting from 1.
+    pub fn position(&self) -> (usize, usize) {
+        let before = &self.src[..self.offset()];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+        )
+    }
+
+    /// What went wrong and where, on a line.
+    fn summary(&self) -> String {
+        let (line, column) = self.position();
+        let mut summary = format!("{self} at line {line}, column {column}: {}", self.kind);
+        for (_, context) in &self.contexts {
+            summary.push_str(&format!(", {context}"));
+        }
+        summary
+    }
+}
+
+impl Diagnostic for BadInput {
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&self.src)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let error = LabeledSpan::new_with_span(Some(self.kind.clone()), self.bad_bit);
+        let contexts = self
+            .contexts
+            .iter()
+            .map(|(span, context)| LabeledSpan::new_with_span(Some(context.clone()), *span));
+        Some(Box::new(iter::once(error).chain(contexts)))
+    }
+}
+
+/// A parse error, with the contexts it was found in.
+struct Trace {
+    offset: usize,
+    kind: String,
+    contexts: Vec<(usize, String)>,
+}
+
+/// The error that went the farthest in the input: it is most likely the actual problem among
+/// the alternatives the parser tried. Alternatives failing at the same place are all reported.
+fn deepest(error: ErrorTree<ByteOffset>) -> Trace {
+    match error {
+        GenericErrorTree::Base { location, kind } => Trace {
+            offset: location.0,
+            kind: kind.to_string(),
+            contexts: vec![],
+        },
+        GenericErrorTree::Stack { base, contexts } => {
+            let mut trace = deepest(*base);
+            trace.contexts.extend(
+                contexts
+                    .into_iter()
+                    .map(|(location, context)| (location.0, context.to_string())),
+            );
+            trace
+        }
+        GenericErrorTree::Alt(alternatives) => {
+            let traces = alternatives.into_iter().map(deepest).collect::<Vec<_>>();
+            let offset = traces.iter().map(|t| t.offset).max().unwrap_or_default();
+            let mut farthest = traces.into_iter().filter(|t| t.offset == offset);
+            let Some(mut trace) = farthest.next() else {
+                return Trace {
+                    offset,
+                    kind: String::new(),
+                    contexts: vec![],
+                };
+            };
+            for other in farthest {
+                if !trace.kind.split(" or ").any(|kind| kind == other.kind) {
+                    trace.kind = format!("{} or {}", trace.kind, other.kind);
+                }
+            }
+            trace
+        }
+    }
+}
+
+/// Runs `parser` on the whole input (but trailing whitespaces), reporting failures as
+/// [`BadInput`].
+pub fn parse_all<'a, I, O, P>(data: &'a str, parser: P) -> Result<O, BadInput>
+where
+    I: From<&'a str> + Clone + InputLength + InputTakeAtPosition + Offset,
+    <I as InputTakeAtPosition>::Item: AsChar + Clone,
+    P: Parser<I, O, ErrorTree<I>>,
+{
+    final_parser(terminated(parser, multispace0))(I::from(data))
+        .map_err(|e: ErrorTree<ByteOffset>| BadInput::new(data, e))
+}
+
+/// A [`BadInput`] read from a named file, rendered with the name of the file.
+#[derive(Debug)]
+struct Named<'a> {
+    bad_input: &'a BadInput,
+    source: NamedSource,
+}
+
+impl Display for Named<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.bad_input)
+    }
+}
+
+impl Error for Named<'_> {}
+
+impl Diagnostic for Named<'_> {
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&self.source)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        self.bad_input.labels()
+    }
+}
+
+fn render(diagnostic: &dyn Diagnostic, colored: bool) -> Option<String> {
+    let handler = if colored {
+        GraphicalReportHandler::new()
+    } else {
+        GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor())
+    };
+    let mut report = String::new();
+    handler.render_report(&mut report, diagnostic).ok()?;
+    Some(report)
+}
+
+/// A parse error, with the name of the file it was read from if any, to show as a graphical
+/// report pointing at the problem in the input.
+#[derive(Clone, Debug)]
+pub struct Diagnosis {
+    bad_input: BadInput,
+    name: Option<String>,
+}
+
+impl Diagnosis {
+    /// The diagnosis of `error` if it is a parse error, `name` being the file the input was read
+    /// from.
+    pub fn of(error: &AocError, name: Option<&str>) -> Option<Self> {
+        match error {
+            AocError::Parse(bad_input) => Some(Self {
+                bad_input: bad_input.clone(),
+                name: name.map(str::to_owned),
+            }),
+            _ => None,
+        }
+    }
+
+    /// The graphical report, titled with the name of the file, made for terminals and only
+    /// `colored` when asked to.
+    pub fn render(&self, colored: bool) -> Option<String> {
+        match &self.name {
+            Some(name) => render(
+                &Named {
+                    bad_input: &self.bad_input,
+                    source: NamedSource::new(name, self.bad_input.src.clone()),
+                },
+                colored,
+            ),
+            None => render(&self.bad_input, colored),
+        }
+    }
+}
+
+/// Describes an error and its causes on a line, with the line and column of the problem in the
+/// input for a parse error.
+pub fn describe(error: &AocError) -> String {
+    match error {
+        AocError::Parse(bad_input) => bad_input.summary(),
+        _ => causes(error),
+    }
+}
+
+/// Describes an error like [`describe`], prefixed with the file `name` the input was read from.
+pub fn describe_input(error: &AocError, name: &str) -> String {
+    format!("{name}: {}", describe(error))
+}
+
+fn causes(error: &AocError) -> String {
+    let mut description = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        description.push_str(&format!(": {cause}"));
+        source = cause.source();
+    }
+    description
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use nom::branch::alt;
+    use nom::bytes::complete::tag;
+    use nom::character::complete::{line_ending, u32};
+    use nom::combinator::{cut, value};
+    use nom::multi::separated_list1;
+    use nom::sequence::separated_pair;
+    use nom::IResult;
+    use nom_supreme::tag::complete::tag as supreme_tag;
+    use nom_supreme::ParserExt;
+    use spectral::prelude::*;
+
+    fn numbers(i: &str) -> IResult<&str, Vec<u32>, ErrorTree<&str>> {
+        separated_list1(tag(","), u32)(i)
+    }
+
+    #[test]
+    fn whole_input() {
+        assert_that!(parse_all("1,2,3\n", numbers).map_err(|e| e.offset()))
+            .is_ok()
+            .is_equal_to(vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn error_location() {
+        let error = parse_all("1,2,x", numbers).unwrap_err();
+
+        assert_that!(error.offset()).is_equal_to(3);
+    }
+
+    #[test]
+    fn leftovers() {
+        let error = parse_all("1,2\n;3", numbers).unwrap_err();
+
+        assert_that!(error.offset()).is_equal_to(4);
+    }
+
+    fn pairs(i: &str) -> IResult<&str, Vec<(u32, u32)>, ErrorTree<&str>> {
+        let pair = separated_pair(u32, tag(","), alt((u32, value(0, supreme_tag("-")))));
+        separated_list1(line_ending, cut(pair.context("pair")))(i)
+    }
+
+    #[test]
+    fn error_contexts() {
+        let error = parse_all("1,2\n3,x", pairs).unwrap_err();
+
+        assert_that!(error.offset()).is_equal_to(6);
+        let labels = error
+            .labels()
+            .unwrap()
+            .map(|label| (label.offset(), label.label().unwrap_or_default().to_owned()))
+            .collect::<Vec<_>>();
+        assert_that!(labels).is_equal_to(vec![
+            (6, r#"expected an ascii digit or expected "-""#.to_owned()),
+            (4, r#"in section "pair""#.to_owned()),
+        ]);
+    }
+
+    #[test]
+    fn fragment_location() {
+        let src = "1,2\n3,x\n";
+        let line = src.lines().nth(1).unwrap();
+
+        assert_that!(BadInput::at(src, line, "not a number").offset()).is_equal_to(4);
+    }
+
+    #[test]
+    fn line_location() {
+        assert_that!(BadInput::on_line(3, "3,x", "not a number").position()).is_equal_to((3, 1));
+    }
+
+    #[test]
+    fn plain_description() {
+        let error = AocError::from(parse_all("1,2\n3,x", numbers).unwrap_err());
+
+        assert_that!(describe(&error))
+            .is_equal_to("bad input at line 2, column 1: expected eof".to_owned());
+    }
+
+    #[test]
+    fn graphical_report() {
+        let error = AocError::from(parse_all("1,2,x", numbers).unwrap_err());
+
+        let report = Diagnosis::of(&error, None).and_then(|d| d.render(false));
+        assert_that!(report).is_some().contains("bad input");
+        assert_that!(report).is_some().contains("1,2,x");
+        let error = AocError::from(std::io::Error::new(std::io::ErrorKind::NotFound, "no file"));
+        assert_that!(Diagnosis::of(&error, None)).is_none();
+    }
+
+    #[test]
+    fn uncolored_report() {
+        let error = AocError::from(parse_all("1,2,x", numbers).unwrap_err());
+        let diagnosis = Diagnosis::of(&error, None).unwrap();
+
+        assert_that!(diagnosis.render(false))
+            .is_some()
+            .matches(|r| !r.contains('\u{1b}'));
+        assert_that!(diagnosis.render(true))
+            .is_some()
+            .contains("\u{1b}");
+    }
+
+    #[test]
+    fn named_input() {
+        let error = AocError::from(parse_all("1,2\n3,x", numbers).unwrap_err());
+
+        let report = Diagnosis::of(&error, Some("day_1_input.txt")).and_then(|d| d.render(false));
+        assert_that!(report).is_some().contains("day_1_input.txt");
+        assert_that!(report).is_some().contains("3,x");
+        assert_that!(describe_input(&error, "day_1_input.txt"))
+            .starts_with("day_1_input.txt: bad input at line 2");
+
+        let error = AocError::from(std::io::Error::new(std::io::ErrorKind::NotFound, "no file"));
+        assert_that!(describe_input(&error, "day_1_input.txt"))
+            .is_equal_to("day_1_input.txt: cannot read the puzzle input: no file".to_owned());
+    }
+
+    #[test]
+    fn causes() {
+        let error = AocError::from(std::io::Error::new(std::io::ErrorKind::NotFound, "no file"));
+
+        assert_that!(describe(&error))
+            .is_equal_to("cannot read the puzzle input: no file".to_owned());
+    }
+}
@@ -0,0 +1,40 @@
+use crate::answer::Part;
+use crate::diagnostics::BadInput;
+use std::error::Error;
+use std::io;
+
+/// Everything that can go wrong when solving a puzzle.
+#[derive(thiserror::Error, Debug)]
+pub enum AocError {
+    /// The puzzle input could not be read.
+    #[error("cannot read the puzzle input")]
+    Io(#[from] io::Error),
+
+    /// The puzzle input is malformed, at a known place.
+    #[error(transparent)]
+    Parse(#[from] BadInput),
+
+    /// No solver is registered for the day.
+    #[error("no solver for day {0}")]
+    UnknownDay(u8),
+
+    /// The day does not report an answer to the part.
+    #[error("day {day} has no answer to part {part}")]
+    NoAnswer { day: u8, part: Part },
+
+    /// The puzzle input is well-formed, but the solver could not make sense of it (no path
+    /// through the map, no marker in the signal...).
+    #[error(transparent)]
+    Logic(Box<dyn Error + Send + Sync>),
+}
+
+/// An error specific to the puzzle of a day, reported as [`AocError::Logic`].
+pub trait PuzzleError: Error + Send + Sync + 'static {}
+
+impl<E: PuzzleError> From<E> for AocError {
+    fn from(error: E) -> Self {
+        AocError::Logic(Box::new(error))
+    }
+}
+
+pub type Result<T, E = AocError> = std::result::Result<T, E>;
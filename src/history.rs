@@ -0,0 +1,187 @@
+use crate::runner::Report;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs::{read_to_string, write};
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::Duration;
+
+/// Where the timings are kept when no other file is given.
+pub const DEFAULT_PATH: &str = ".aoc-history.json";
+
+/// Timings of the last run of a day, in milliseconds.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+struct Record {
+    parse_ms: f64,
+    solve_ms: f64,
+}
+
+/// The timings of the last run of each day, by year, as kept in a JSON file.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct History(BTreeMap<u16, BTreeMap<u8, Record>>);
+
+impl History {
+    /// Loads the history from `path`, empty if the file does not exist yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        match read_to_string(path) {
+            Ok(data) => serde_json::from_str(&data)
+                .with_context(|| format!("invalid history in {}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("cannot read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let data = serde_json::to_string_pretty(self)?;
+        write(path, data).with_context(|| format!("cannot write {}", path.display()))
+    }
+
+    /// Records the timings of the days solved in `reports`, returning the ones whose solve time
+    /// grew by more than `threshold` percent since the last run. Failed days are left as they
+    /// were; a previous time that is zero or not a valid duration is not compared against.
+    pub fn update(&mut self, year: u16, reports: &[Report], threshold: f64) -> Vec<Regression> {
+        let days = self.0.entry(year).or_default();
+        let mut regressions = Vec::new();
+        for report in reports {
+            let Ok((_, timings)) = &report.outcome else {
+                continue;
+            };
+            let record = Record {
+                parse_ms: timings.parse.as_secs_f64() * 1000.0,
+                solve_ms: timings.solve.as_secs_f64() * 1000.0,
+            };
+            // a corrupted previous time is treated as absent, and a zero one cannot be grown from
+            let before = days
+                .insert(report.day, record)
+                .and_then(|before| Duration::try_from_secs_f64(before.solve_ms / 1000.0).ok())
+                .filter(|before| !before.is_zero());
+            if let Some(before) = before {
+                if timings.solve.as_secs_f64() > before.as_secs_f64() * (1.0 + threshold / 100.0) {
+                    regressions.push(Regression {
+                        day: report.day,
+                        before,
+                        after: timings.solve,
+                    });
+                }
+            }
+        }
+        regressions
+    }
+}
+
+/// A day whose solve time grew from `before` to `after` since the last run.
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let growth = (self.after.as_secs_f64() / self.before.as_secs_f64() - 1.0) * 100.0;
+        write!(
+            f,
+            "Day {}: solved in {:.2?} instead of {:.2?} ({growth:+.0}%)",
+            self.day, self.after, self.before
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::runner::{Answers, Timings};
+    use spectral::prelude::*;
+
+    fn report(day: u8, solve: u64) -> Report {
+        Report {
+            day,
+            diagnosis: None,
+            outcome: Ok((
+                Answers::default(),
+                Timings {
+                    parse: Duration::from_millis(1),
+                    solve: Duration::from_millis(solve),
+                    peak_memory: None,
+                },
+            )),
+        }
+    }
+
+    #[test]
+    fn first_run() {
+        let mut history = History::default();
+
+        let regressions = history.update(2022, &[report(1, 10), report(2, 20)], 10.0);
+
+        assert_that!(regressions).is_empty();
+        assert_that!(history.0[&2022][&2].solve_ms).is_equal_to(20.0);
+    }
+
+    #[test]
+    fn regressions_above_threshold() {
+        let mut history = History::default();
+        history.update(2022, &[report(1, 10), report(2, 20), report(3, 30)], 10.0);
+
+        let failed = Report {
+            day: 3,
+            diagnosis: None,
+            outcome: Err("no input".to_owned()),
+        };
+        let regressions = history.update(2022, &[report(1, 15), report(2, 21), failed], 10.0);
+
+        assert_that!(regressions).is_equal_to(vec![Regression {
+            day: 1,
+            before: Duration::from_millis(10),
+            after: Duration::from_millis(15),
+        }]);
+        assert_that!(regressions[0].to_string().as_str())
+            .is_equal_to("Day 1: solved in 15.00ms instead of 10.00ms (+50%)");
+        assert_that!(history.0[&2022][&3].solve_ms).is_equal_to(30.0);
+    }
+
+    #[test]
+    fn unusable_previous_time() {
+        let mut history = History::default();
+        let days = history.0.entry(2022).or_default();
+        for (day, solve_ms) in [(1, 0.0), (2, -5.0), (3, f64::MAX)] {
+            let parse_ms = 1.0;
+            days.insert(day, Record { parse_ms, solve_ms });
+        }
+
+        let regressions =
+            history.update(2022, &[report(1, 10), report(2, 10), report(3, 10)], 10.0);
+
+        assert_that!(regressions).is_empty();
+        assert_that!(history.0[&2022][&2].solve_ms).is_equal_to(10.0);
+    }
+
+    #[test]
+    fn years_apart() {
+        let mut history = History::default();
+        history.update(2021, &[report(1, 10)], 10.0);
+
+        let regressions = history.update(2022, &[report(1, 100)], 10.0);
+
+        assert_that!(regressions).is_empty();
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path =
+            std::env::temp_dir().join(format!("aoc2022-{}-history.json", std::process::id()));
+        let mut history = History::default();
+        history.update(2022, &[report(1, 10)], 10.0);
+
+        history.save(&path).unwrap();
+
+        assert_that!(History::load(&path))
+            .is_ok()
+            .is_equal_to(history);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,352 @@
+use crate::days;
+use crate::runner::Input;
+use anyhow::{bail, Context, Result};
+use std::env;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Environment variable holding the value of the `session` cookie of adventofcode.com.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Environment variable naming a directory to look for inputs in.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Directory of the inputs kept along with the sources.
+const DATA_DIR: &str = "data";
+
+const USER_AGENT: &str = concat!(
+    "github.com/cdelmas/aoc-2022 ",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION")
+);
+
+/// Where the input of a day is within an input directory.
+fn file_name(year: u16, day: u8) -> PathBuf {
+    PathBuf::from(year.to_string()).join(format!("day_{day}_input.txt"))
+}
+
+/// Directory of the cached inputs, following the XDG base directory specification.
+fn cache_dir() -> Option<PathBuf> {
+    let cache = match env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".cache"),
+    };
+    Some(cache.join(env!("CARGO_PKG_NAME")))
+}
+
+pub fn input_url(year: u16, day: u8) -> String {
+    format!("https://adventofcode.com/{year}/day/{day}/input")
+}
+
+/// Downloads the puzzle input of a day, as the user identified by the session cookie.
+pub fn download(year: u16, day: u8, session: &str) -> Result<String> {
+    let url = input_url(year, day);
+    let response = ureq::get(&url)
+        .set("Cookie", &format!("session={session}"))
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .with_context(|| format!("cannot download {url}"))?;
+    let data = response.into_string()?;
+    if data.is_empty() {
+        bail!("empty input downloaded from {url}");
+    }
+    Ok(data)
+}
+
+/// The input of a day that is in none of the input directories, with no session to download it.
+#[derive(thiserror::Error, Debug, PartialEq)]
+#[error(
+    "no input for day {day}: expected {} (also looked in {}); pass another file with --input, \
+     or set {SESSION_VAR} or a session_file in aoc.toml to download it from {}",
+    expected.display(),
+    searched.iter().map(|dir| dir.display().to_string()).collect::<Vec<_>>().join(", "),
+    input_url(*year, *day)
+)]
+pub struct MissingInput {
+    pub year: u16,
+    pub day: u8,
+    /// Where the input would be downloaded to.
+    pub expected: PathBuf,
+    /// The other directories the input was looked for in.
+    pub searched: Vec<PathBuf>,
+}
+
+/// Reads the file at `path`, or when it does not exist, stores there what `fetch` returns.
+fn read_or_fetch(path: &Path, fetch: impl FnOnce() -> Result<String>) -> Result<String> {
+    match read_to_string(path) {
+        Ok(data) => Ok(data),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            let data = fetch()?;
+            if let Some(dir) = path.parent() {
+                create_dir_all(dir)?;
+            }
+            write(path, &data).with_context(|| format!("cannot store {}", path.display()))?;
+            Ok(data)
+        }
+        Err(e) => Err(e).with_context(|| format!("cannot read {}", path.display())),
+    }
+}
+
+/// Finds the puzzle inputs of a year in a list of directories, the first one receiving the
+/// downloaded inputs. The inputs are in a subdirectory per year, like `data/2022/day_1_input.txt`.
+#[derive(Clone, Debug)]
+pub struct InputManager {
+    year: u16,
+    dirs: Vec<PathBuf>,
+    session_file: Option<PathBuf>,
+    file: Option<PathBuf>,
+}
+
+impl Default for InputManager {
+    fn default() -> Self {
+        Self::new(days::DEFAULT_YEAR, None)
+    }
+}
+
+impl InputManager {
+    /// Looks for the inputs of `year` in `dir` if given, then in the directory named by
+    /// `AOC_INPUT_DIR`, in the cache directory, and last in `data`.
+    pub fn new(year: u16, dir: Option<PathBuf>) -> Self {
+        let dirs = dir
+            .into_iter()
+            .chain(env::var_os(INPUT_DIR_VAR).map(PathBuf::from))
+            .chain(cache_dir())
+            .chain([PathBuf::from(DATA_DIR)])
+            .collect();
+        Self {
+            year,
+            dirs,
+            session_file: None,
+            file: None,
+        }
+    }
+
+    /// Reads the input of any day from `path` instead, when given.
+    pub fn with_file(self, path: Option<PathBuf>) -> Self {
+        Self { file: path, ..self }
+    }
+
+    /// Reads the session from `path` when it is not set in `AOC_SESSION`.
+    pub fn with_session_file(self, path: Option<PathBuf>) -> Self {
+        Self {
+            session_file: path,
+            ..self
+        }
+    }
+
+    fn session(&self) -> Option<String> {
+        env::var(SESSION_VAR)
+            .ok()
+            .or_else(|| read_to_string(self.session_file.as_ref()?).ok())
+            .map(|session| session.trim().to_owned())
+    }
+
+    /// Where the input of a day is, if it is anywhere.
+    pub fn find(&self, day: u8) -> Option<PathBuf> {
+        if let Some(file) = &self.file {
+            return file.is_file().then(|| file.clone());
+        }
+        self.dirs
+            .iter()
+            .map(|dir| dir.join(file_name(self.year, day)))
+            .find(|path| path.is_file())
+    }
+
+    fn missing_input(&self, day: u8) -> MissingInput {
+        MissingInput {
+            year: self.year,
+            day,
+            expected: self.dirs[0].join(file_name(self.year, day)),
+            searched: self.dirs[1..].to_vec(),
+        }
+    }
+
+    /// Why the input of a day cannot be read, when it is nowhere to be found and cannot be
+    /// downloaded. An input file given with [`InputManager::with_file`] is never missing: not
+    /// finding it is an error.
+    pub fn missing(&self, day: u8) -> Option<MissingInput> {
+        if self.file.is_some() || self.find(day).is_some() || self.session().is_some() {
+            return None;
+        }
+        Some(self.missing_input(day))
+    }
+
+    /// Reads the puzzle input of a day, downloading it first to the first directory if it is
+    /// missing and a session is set in `AOC_SESSION` or in the session file.
+    pub fn read(&self, day: u8) -> Result<String> {
+        Ok(self.load(day)?.data)
+    }
+
+    /// Reads the puzzle input of a day like [`InputManager::read`], along with the file it was
+    /// read from.
+    pub fn load(&self, day: u8) -> Result<Input> {
+        if let Some(file) = &self.file {
+            let data =
+                read_to_string(file).with_context(|| format!("cannot read {}", file.display()))?;
+            return Ok(Input {
+                data,
+                path: Some(file.clone()),
+                example: false,
+            });
+        }
+        let path = self
+            .find(day)
+            .unwrap_or_else(|| self.dirs[0].join(file_name(self.year, day)));
+        let data = read_or_fetch(&path, || match self.session() {
+            Some(session) => download(self.year, day, &session),
+            None => Err(self.missing_input(day).into()),
+        })?;
+        Ok(Input {
+            data,
+            path: Some(path),
+            example: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+    use std::fs::remove_dir_all;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc2022-{}-{name}", std::process::id()));
+        let _ = remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn existing_input_is_not_fetched() {
+        let dir = scratch_dir("existing");
+        create_dir_all(&dir).unwrap();
+        let path = dir.join("day_1_input.txt");
+        write(&path, "1000").unwrap();
+
+        let data = read_or_fetch(&path, || bail!("should not be fetched"));
+
+        assert_that!(data).is_ok().is_equal_to("1000".to_owned());
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_input_is_fetched_and_stored() {
+        let dir = scratch_dir("missing");
+        let path = dir.join("data").join("day_1_input.txt");
+
+        let data = read_or_fetch(&path, || Ok("1000".to_owned()));
+
+        assert_that!(data).is_ok().is_equal_to("1000".to_owned());
+        assert_that!(read_to_string(&path).ok()).is_equal_to(Some("1000".to_owned()));
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn failed_fetch_stores_nothing() {
+        let dir = scratch_dir("failed");
+        let path = dir.join("day_1_input.txt");
+
+        let data = read_or_fetch(&path, || bail!("no session"));
+
+        assert_that!(data).is_err();
+        assert_that!(path.exists()).is_false();
+    }
+
+    fn manager(dirs: &[&Path]) -> InputManager {
+        InputManager {
+            year: 2022,
+            dirs: dirs.iter().map(|dir| dir.to_path_buf()).collect(),
+            session_file: None,
+            file: None,
+        }
+    }
+
+    #[test]
+    fn first_directory_wins() {
+        let dir = scratch_dir("precedence");
+        let (first, second) = (dir.join("first"), dir.join("second"));
+        create_dir_all(first.join("2022")).unwrap();
+        create_dir_all(second.join("2022")).unwrap();
+        write(second.join("2022/day_1_input.txt"), "second").unwrap();
+        write(second.join("2022/day_2_input.txt"), "second").unwrap();
+        write(first.join("2022/day_2_input.txt"), "first").unwrap();
+        write(first.join("day_3_input.txt"), "another year").unwrap();
+
+        let inputs = manager(&[&first, &second]);
+
+        assert_that!(inputs.find(1)).is_equal_to(Some(second.join("2022/day_1_input.txt")));
+        assert_that!(inputs.read(2))
+            .is_ok()
+            .is_equal_to("first".to_owned());
+        assert_that!(inputs.find(3)).is_none();
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn file_overrides_directories() {
+        let dir = scratch_dir("file");
+        create_dir_all(dir.join("2022")).unwrap();
+        write(dir.join("2022/day_7_input.txt"), "mine").unwrap();
+        let friend = dir.join("friend_input.txt");
+        write(&friend, "friend").unwrap();
+
+        let inputs = manager(&[&dir]).with_file(Some(friend.clone()));
+
+        assert_that!(inputs.find(7)).is_equal_to(Some(friend));
+        assert_that!(inputs.read(7))
+            .is_ok()
+            .is_equal_to("friend".to_owned());
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_file_is_not_fetched() {
+        let dir = scratch_dir("missing-file");
+        let inputs = manager(&[&dir]).with_file(Some(dir.join("friend_input.txt")));
+
+        assert_that!(inputs.find(7)).is_none();
+        assert_that!(inputs.read(7)).is_err();
+        assert_that!(dir.exists()).is_false();
+    }
+
+    #[test]
+    fn missing_input() {
+        let dir = scratch_dir("missing-input");
+        let (first, second) = (dir.join("first"), dir.join("second"));
+        create_dir_all(second.join("2022")).unwrap();
+        write(second.join("2022/day_1_input.txt"), "1000").unwrap();
+        let inputs = manager(&[&first, &second]);
+
+        assert_that!(inputs.missing(1)).is_none();
+        // a session in the environment would download the input instead
+        if inputs.session().is_none() {
+            let missing = inputs.missing(2);
+            assert_that!(missing).is_equal_to(Some(MissingInput {
+                year: 2022,
+                day: 2,
+                expected: first.join("2022/day_2_input.txt"),
+                searched: vec![second.clone()],
+            }));
+            assert_that!(inputs.read(2).unwrap_err().to_string())
+                .is_equal_to(missing.unwrap().to_string());
+        }
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn explicit_directory_first() {
+        let inputs = InputManager::new(2022, Some(PathBuf::from("mine")));
+
+        assert_that!(inputs.dirs.first()).is_equal_to(Some(&PathBuf::from("mine")));
+        assert_that!(inputs.dirs.last()).is_equal_to(Some(&PathBuf::from(DATA_DIR)));
+    }
+
+    #[test]
+    fn url() {
+        assert_that!(input_url(2022, 7).as_str())
+            .is_equal_to("https://adventofcode.com/2022/day/7/input");
+    }
+}
@@ -0,0 +1,28 @@
+pub mod answer;
+pub mod check;
+pub mod color;
+pub mod config;
+pub mod days;
+pub mod diagnostics;
+pub mod error;
+pub mod history;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod inputs;
+pub mod memory;
+pub mod output;
+pub mod parsers;
+#[cfg(feature = "profiling")]
+pub mod profiling;
+pub mod progress;
+pub mod ranges;
+pub mod runner;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod server;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tui;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod year2022;
+
+pub use answer::{Answer, Part};
+pub use days::solve;
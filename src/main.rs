@@ -1,157 +1,622 @@
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-use std::path::PathBuf;
-
-use miette::GraphicalReportHandler;
-use nom_supreme::{
-    error::{BaseErrorKind, ErrorTree, GenericErrorTree},
-    final_parser::final_parser,
-};
-
-#[derive(thiserror::Error, Debug, miette::Diagnostic)]
-#[error("bad input")]
-struct BadInput<'a> {
-    #[source_code]
-    src: &'a str,
-
-    #[label("{kind}")]
-    bad_bit: miette::SourceSpan,
-
-    kind: BaseErrorKind<&'a str, Box<dyn std::error::Error + Send + Sync>>,
-}
-
-fn main() {
-    // day1
-    let calories = day1::calories_carried(&PathBuf::from("data/day_1_input.txt"));
-    match calories {
-        Ok(calories) => println!("{} calories brought by the most loaded elf", calories),
-        Err(_) => eprintln!("Something went wrong…"),
-    }
-
-    // day2
-    let score = day2::rock_paper_scissors(&PathBuf::from("data/day_2_input.txt"));
-    match score {
-        Ok(score) => println!("Rock Paper Scissors score={}", score),
-        Err(_) => eprintln!("Something went wrong…"),
-    }
-
-    // day3
-    let priorities = day3::priorities(&PathBuf::from("data/day_3_input.txt"));
-    match priorities {
-        Ok(priorities) => println!("total priorities: {}", priorities),
-        Err(_) => eprintln!("Something went wrong…"),
-    }
-
-    let priorities_2 = day3::priorities_2(&PathBuf::from("data/day_3_part2_input.txt"));
-    match priorities_2 {
-        Ok(priorities_2) => println!("total priorities: {}", priorities_2),
-        Err(_) => eprintln!("Something went wrong…"),
-    }
-
-    let ship_unload_overlaps = day4::ship_unload_overlaps(&PathBuf::from("data/day_4_input.txt"));
-    match ship_unload_overlaps {
-        Ok(ship_unload_overlaps) => println!("Overlaps: {}", ship_unload_overlaps),
-        Err(_) => eprintln!("Something went wrong…"),
-    }
-
-    let top_crate_of_stacks = day5::top_crate_of_stacks(&PathBuf::from("data/day_5_input.txt"));
-    match top_crate_of_stacks {
-        Ok(top_crate_of_stacks) => println!("Top crates of stacks: {}", top_crate_of_stacks),
-        Err(_) => eprintln!("Something went wrong…"),
-    }
-
-    let markers = day6::fix_device(&PathBuf::from("data/day_6_input.txt"));
-    match markers {
-        Ok((start_stream, start_message)) => println!(
-            "Markers: start stream at {}, message at {}",
-            start_stream, start_message
-        ),
-        Err(_) => eprintln!("Something went wrong…"),
-    }
-
-    let small_directories = day7::total_size_of_small_directories_and_smallest_to_delete(
-        &PathBuf::from("data/day_7_input.txt"),
-    );
-    match small_directories {
-        Ok((total_small_directories_size, smallest_to_delete_size)) => {
-            println!(
-                "Total size of small directories: {}; smallest to delete: {}",
-                total_small_directories_size, smallest_to_delete_size
-            )
-        }
-        Err(_) => eprintln!("Something went wrong…"),
+use anyhow::{bail, Context};
+use aoc2022::color::{self, Color, ColorChoice};
+use aoc2022::config::Config;
+use aoc2022::days;
+use aoc2022::history::{self, History};
+use aoc2022::inputs::InputManager;
+use aoc2022::output::{self, DayStatus, Format, InputStatus};
+use aoc2022::runner::{self, Input};
+use aoc2022::year2022::{self, day01, day03, day05, day07, day08, day09, day10, day12};
+use aoc2022::{check, server, tui};
+use clap::{Parser, Subcommand};
+use indicatif::MultiProgress;
+use std::io::{self, IsTerminal};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::Level;
+
+#[derive(Parser)]
+#[command(
+    about = "Advent of Code 2022 solutions",
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Days to solve (all of them by default)
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    days: Vec<u8>,
+
+    /// Day to solve, in addition to the ones given as arguments
+    #[arg(short, long = "day", value_name = "DAY", value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Vec<u8>,
+
+    /// Year of the puzzles to solve (2022 by default)
+    #[arg(long)]
+    year: Option<u16>,
+
+    /// Number of days solved in parallel (as many as CPUs by default)
+    #[arg(short, long)]
+    jobs: Option<usize>,
+
+    /// Solve the examples of the puzzle statements instead of the real inputs
+    #[arg(long)]
+    example: bool,
+
+    /// Log what happens while solving (-v for debug, -vv for trace)
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Show the progress of the long-running days
+    #[arg(long)]
+    progress: bool,
+
+    /// How to print the results (a table by default)
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+
+    /// Print the results as JSON, same as --format json
+    #[arg(long, conflicts_with = "format")]
+    json: bool,
+
+    /// List the days with the status of their input, example and expected answers, without
+    /// solving them
+    #[arg(long, conflicts_with = "input")]
+    list: bool,
+
+    /// Compare the answers to the expected ones, failing on any mismatch
+    #[arg(long, conflicts_with = "example")]
+    check: bool,
+
+    /// File of expected answers used by --check
+    #[arg(long, default_value = "answers.toml")]
+    answers: PathBuf,
+
+    /// Compare the solve times to the ones of the last run kept in this file (.aoc-history.json
+    /// by default), then record the new ones
+    #[arg(long, value_name = "JSON", num_args = 0..=1, default_missing_value = history::DEFAULT_PATH, conflicts_with = "example")]
+    history: Option<PathBuf>,
+
+    /// Growth of the solve time, in percent, above which --history flags a day
+    #[arg(long, value_name = "PERCENT", default_value_t = 20.0)]
+    threshold: f64,
+
+    /// Profile the solvers, writing a flamegraph of where they spend their time to this file
+    #[cfg(feature = "profiling")]
+    #[arg(long, value_name = "SVG")]
+    profile: Option<PathBuf>,
+
+    /// Save the CRT screen of day 10 as a PNG image
+    #[arg(long, value_name = "PNG")]
+    output: Option<PathBuf>,
+
+    /// Directory to look for the inputs in first, before $AOC_INPUT_DIR, the cache directory
+    /// and data/
+    #[arg(long, global = true, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+
+    /// File to read the input from instead, when solving a single day
+    #[arg(long, global = true, value_name = "FILE")]
+    input: Option<PathBuf>,
+
+    /// When to color the output
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Configuration file providing defaults for the options (aoc.toml if there is one)
+    #[arg(long, global = true, value_name = "TOML")]
+    config: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Serve the solvers over HTTP
+    ///
+    /// `POST /solve/{day}/{part}` with the puzzle input as body answers with JSON.
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:3000")]
+        addr: SocketAddr,
+    },
+    /// Show a dashboard of all the days, to re-run them and look at their output
+    Tui {
+        /// Solve the examples of the puzzle statements instead of the real inputs
+        #[arg(long)]
+        example: bool,
+    },
+    /// Print the calories carried by the most loaded elves of day 1, and their total
+    Calories {
+        /// Number of elves
+        #[arg(long, default_value_t = 3)]
+        top: usize,
+
+        /// Use the example of the puzzle statement instead of the real input
+        #[arg(long)]
+        example: bool,
+    },
+    /// Animate the rope of day 9 in the terminal
+    Rope {
+        /// Number of knots of the rope
+        #[arg(long, default_value_t = day09::LONG_ROPE_SIZE as u8, value_parser = clap::value_parser!(u8).range(2..))]
+        knots: u8,
+
+        /// Time to wait between two steps, in milliseconds
+        #[arg(long, default_value_t = 50)]
+        delay: u64,
+
+        /// Animate the example of the puzzle statement instead of the real input
+        #[arg(long)]
+        example: bool,
+    },
+    /// Print the shortest route of day 12 over the heightmap
+    Route {
+        /// Start from the best square at the lowest elevation (part 2) instead of the one marked
+        /// as the start
+        #[arg(long)]
+        scenic: bool,
+
+        /// Use the example of the puzzle statement instead of the real input
+        #[arg(long)]
+        example: bool,
+    },
+    /// Print the stacks of day 5 after each move of the procedure
+    Stacks {
+        /// Move crates one at a time, like the CrateMover 9000 of part 1, instead of all together
+        #[arg(long)]
+        one_at_a_time: bool,
+
+        /// Use the example of the puzzle statement instead of the real input
+        #[arg(long)]
+        example: bool,
+    },
+    /// Print the forest of day 8 with the trees visible from outside it
+    Trees {
+        /// Shade the scenic score of each tree instead
+        #[arg(long)]
+        scenic: bool,
+
+        /// Use the example of the puzzle statement instead of the real input
+        #[arg(long)]
+        example: bool,
+    },
+    /// Solve day 3 skipping the rucksacks that make no sense, then list them
+    Rucksacks {
+        /// Use the example of the puzzle statement instead of the real input
+        #[arg(long)]
+        example: bool,
+    },
+    /// Print the CRT screen of day 10
+    Crt {
+        /// Character drawn for the lit pixels
+        #[arg(long, default_value_t = day10::CrtStyle::default().lit)]
+        lit: char,
+
+        /// Character drawn for the dark pixels
+        #[arg(long, default_value_t = day10::CrtStyle::default().dark)]
+        dark: char,
+
+        /// Use the example of the puzzle statement instead of the real input
+        #[arg(long)]
+        example: bool,
+    },
+    /// Write a Markdown report of the answers and timings of all the days
+    Report {
+        /// Results previously printed with --format json, instead of solving the days again
+        #[arg(long, value_name = "JSON")]
+        from: Option<PathBuf>,
+
+        /// File to write the report to (the standard output by default)
+        #[arg(long, value_name = "MD")]
+        output: Option<PathBuf>,
+
+        /// Solve the examples of the puzzle statements instead of the real inputs
+        #[arg(long)]
+        example: bool,
+    },
+    /// Export the structure a day works on as a Graphviz graph: the file system of day 7 or
+    /// the road map of day 12
+    Dot {
+        /// Day to export
+        day: u8,
+
+        /// File to write the graph to (the standard output by default)
+        #[arg(long)]
+        output: Option<PathBuf>,
+
+        /// Use the example of the puzzle statement instead of the real input
+        #[arg(long)]
+        example: bool,
+    },
+}
+
+fn init_logs(verbose: u8) {
+    let level = match verbose {
+        0 => Level::WARN,
+        1 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(std::io::stderr)
+        .init();
+}
+
+fn serve(addr: SocketAddr) -> anyhow::Result<()> {
+    tokio::runtime::Runtime::new()?.block_on(server::serve(addr))?;
+    Ok(())
+}
+
+fn save_crt(path: &Path, inputs: &InputManager, example: bool) -> anyhow::Result<()> {
+    let data = puzzle_input(10, inputs, example)?;
+    let screen = day10::crt_display(&day10::parse(&data)?);
+    day10::crt_image(&screen)
+        .save(path)
+        .with_context(|| format!("cannot save the CRT screen to {}", path.display()))
+}
+
+/// The input of a day of 2022, or the example of its puzzle statement.
+fn puzzle_input(day: u8, inputs: &InputManager, example: bool) -> anyhow::Result<String> {
+    match days::day(year2022::YEAR, day) {
+        Some(day) if example => Ok(day.example.to_owned()),
+        _ => inputs.read(day),
     }
+}
+
+fn print_calories(top: usize, inputs: &InputManager, example: bool) -> anyhow::Result<()> {
+    let data = puzzle_input(1, inputs, example)?;
+    let (loads, total) = day01::top_n_calories(data.as_bytes(), top)?;
+    for (rank, load) in (1..).zip(loads) {
+        println!("{rank:>3}. {load}");
+    }
+    println!("total {total}");
+    Ok(())
+}
+
+fn animate_rope(knots: u8, delay: u64, inputs: &InputManager, example: bool) -> anyhow::Result<()> {
+    let data = puzzle_input(9, inputs, example)?;
+    let moves = day09::parse(&data)?;
+    day09::animate(
+        &moves,
+        knots.into(),
+        Duration::from_millis(delay),
+        &mut std::io::stdout().lock(),
+    )?;
+    Ok(())
+}
+
+fn print_route(scenic: bool, inputs: &InputManager, example: bool) -> anyhow::Result<()> {
+    let data = puzzle_input(12, inputs, example)?;
+    let map = day12::parse(&data)?;
+    println!("{}", day12::draw_route(&map, scenic)?);
+    Ok(())
+}
+
+fn print_stacks(one_at_a_time: bool, inputs: &InputManager, example: bool) -> anyhow::Result<()> {
+    let data = puzzle_input(5, inputs, example)?;
+    let procedure = day05::parse(&data)?;
+    println!("{}", day05::draw_stacks(&procedure.0));
+    let crane = if one_at_a_time {
+        day05::CraneModel::CrateMover9000
+    } else {
+        day05::CraneModel::CrateMover9001
+    };
+    for step in day05::rearrangement_steps(&procedure, crane) {
+        let (m, stacks) = step?;
+        println!("\n{m}\n{}", day05::draw_stacks(&stacks));
+    }
+    Ok(())
+}
 
-    let spot = day8::find_best_spot_for_tree_house(&PathBuf::from("data/day_8_input.txt"));
-    match spot {
-        Ok((visible_trees, best_spot)) => println!(
-            "{} visible trees around, {} is the best spot",
-            visible_trees, best_spot
-        ),
-        Err(_) => eprintln!("Something went wrong…"),
+fn print_trees(scenic: bool, inputs: &InputManager, example: bool) -> anyhow::Result<()> {
+    let data = puzzle_input(8, inputs, example)?;
+    let forest = day08::parse(&data)?;
+    if scenic {
+        println!("{}", day08::draw_scenic_scores(&forest));
+    } else {
+        println!("{}", day08::draw_visibility(&forest));
     }
+    Ok(())
+}
 
-    let nb_tail_positions = day9::nb_tail_positions(&PathBuf::from("data/day_9_input.txt"));
-    match nb_tail_positions {
-        Ok(nb_tail_positions) => {
-            println!("Tail gone through {} positions buggy 🙈", nb_tail_positions)
+fn print_rucksacks(colored: bool, inputs: &InputManager, example: bool) -> anyhow::Result<()> {
+    let data = puzzle_input(3, inputs, example)?;
+    let ((misplaced, badges), problems) = day03::solve_lenient(&day03::parse(&data)?);
+    println!("{misplaced}\n{badges}");
+    if !problems.is_empty() {
+        eprintln!("{} skipped:", problems.len());
+        for problem in problems {
+            eprintln!(
+                "{}",
+                color::paint(&problem.to_string(), Color::Yellow, colored)
+            );
         }
-        Err(_) => eprintln!("Something went wrong…"),
     }
+    Ok(())
+}
+
+fn print_crt(
+    style: day10::CrtStyle,
+    colored: bool,
+    inputs: &InputManager,
+    example: bool,
+) -> anyhow::Result<()> {
+    let data = puzzle_input(10, inputs, example)?;
+    let screen = day10::styled_crt_display(&day10::parse(&data)?, style);
+    println!("{}", color::tint(&screen, style.lit, Color::Green, colored));
+    Ok(())
+}
+
+fn list_days(
+    days: &[runner::Day],
+    inputs: &InputManager,
+    answers: &Path,
+    colored: bool,
+) -> anyhow::Result<()> {
+    let answers = if answers.exists() {
+        Some(check::Answers::load(answers)?)
+    } else {
+        None
+    };
+    let statuses = days
+        .iter()
+        .map(|day| DayStatus {
+            day: day.number,
+            input: match (inputs.find(day.number), inputs.missing(day.number)) {
+                (Some(path), _) => InputStatus::Found(path),
+                (None, Some(_)) => InputStatus::Missing,
+                (None, None) => InputStatus::Downloadable,
+            },
+            example: !day.example.is_empty(),
+            answers: answers.as_ref().is_some_and(|a| a.has_day(day.number)),
+        })
+        .collect::<Vec<_>>();
+    print!("{}", output::day_list(&statuses, colored));
+    Ok(())
+}
 
-    let signal_strength = day10::sum_of_signal_strengths(&PathBuf::from("data/day_10_input.txt"));
-    match signal_strength {
-        Ok(signal_strength) => println!("Signal strength total: {}", signal_strength),
-        Err(_) => eprintln!("Something went wrong…"),
+fn export_dot(
+    day: u8,
+    output: Option<&Path>,
+    inputs: &InputManager,
+    example: bool,
+) -> anyhow::Result<()> {
+    let data = puzzle_input(day, inputs, example)?;
+    let dot = match day {
+        7 => day07::to_dot(&day07::parse(&data)?),
+        12 => day12::to_dot(&day12::parse(&data)?)?,
+        _ => bail!("day {day} has no graph to export"),
+    };
+    match output {
+        Some(path) => std::fs::write(path, dot)
+            .with_context(|| format!("cannot write the graph to {}", path.display())),
+        None => {
+            print!("{dot}");
+            Ok(())
+        }
     }
+}
 
-    let input = PathBuf::from("data/day_11_input.txt");
-    let raw_data = std::fs::read_to_string(input).unwrap();
+fn write_report(
+    from: Option<&Path>,
+    output: Option<&Path>,
+    inputs: &InputManager,
+    example: bool,
+) -> anyhow::Result<()> {
+    let entries = match from {
+        Some(path) => {
+            let data = std::fs::read_to_string(path)
+                .with_context(|| format!("cannot read {}", path.display()))?;
+            output::from_json(&data)
+                .with_context(|| format!("invalid results in {}", path.display()))?
+        }
+        None => {
+            let reports = runner::run_all(&year2022::DAYS, None, None, |day| {
+                if example {
+                    Ok(Input::example(day))
+                } else {
+                    inputs.load(day.number)
+                }
+            })?;
+            output::all_entries(&reports)
+        }
+    };
+    let report = output::markdown(year2022::YEAR, &entries);
+    match output {
+        Some(path) => std::fs::write(path, report)
+            .with_context(|| format!("cannot write the report to {}", path.display())),
+        None => {
+            print!("{report}");
+            Ok(())
+        }
+    }
+}
 
-    let data = day11::Span::new(&raw_data);
-    let monkeys: Result<Vec<day11::Monkey>, ErrorTree<day11::Span>> =
-        final_parser(day11::monkeys::<ErrorTree<day11::Span>>)(data);
-    match monkeys {
-        Ok(monkeys) => {
-            let active_monkeys_score = day11::compute_score(&monkeys);
-            println!("Active monkeys score: {}", active_monkeys_score);
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let config = Config::find(cli.config.as_deref())?;
+    let input_dir = cli.input_dir.or(config.input_dir);
+    // the subcommands only deal with the puzzles of 2022
+    let inputs = InputManager::new(year2022::YEAR, input_dir.clone())
+        .with_session_file(config.session_file.clone())
+        .with_file(cli.input.clone());
+    match cli.command {
+        Some(Command::Tui { .. } | Command::Report { .. }) if cli.input.is_some() => {
+            bail!("--input only applies to a single day, not to all of them")
+        }
+        // logs would be drawn over the dashboard
+        Some(Command::Tui { example }) => {
+            return tui::run(year2022::YEAR, &year2022::DAYS, inputs, example)
+        }
+        Some(Command::Serve { addr }) => {
+            init_logs(cli.verbose);
+            return serve(addr);
+        }
+        Some(Command::Rope {
+            knots,
+            delay,
+            example,
+        }) => return animate_rope(knots, delay, &inputs, example),
+        Some(Command::Route { scenic, example }) => return print_route(scenic, &inputs, example),
+        Some(Command::Stacks {
+            one_at_a_time,
+            example,
+        }) => return print_stacks(one_at_a_time, &inputs, example),
+        Some(Command::Trees { scenic, example }) => return print_trees(scenic, &inputs, example),
+        Some(Command::Calories { top, example }) => return print_calories(top, &inputs, example),
+        Some(Command::Rucksacks { example }) => {
+            let colored = cli.color.enabled(&io::stderr());
+            return print_rucksacks(colored, &inputs, example);
         }
-        Err(e) => {
-            match e {
-                GenericErrorTree::Base { location, kind } => {
-                    let offset = location.location_offset().into();
-                    let err = BadInput {
-                        src: &raw_data,
-                        bad_bit: miette::SourceSpan::new(offset, 0.into()),
-                        kind,
-                    };
-                    let mut s = String::new();
-                    GraphicalReportHandler::new()
-                        .render_report(&mut s, &err)
-                        .unwrap();
-                    println!("{s}");
+        Some(Command::Crt { lit, dark, example }) => {
+            let colored = cli.color.enabled(&io::stdout());
+            return print_crt(day10::CrtStyle { lit, dark }, colored, &inputs, example);
+        }
+        Some(Command::Report {
+            from,
+            output,
+            example,
+        }) => return write_report(from.as_deref(), output.as_deref(), &inputs, example),
+        Some(Command::Dot {
+            day,
+            output,
+            example,
+        }) => return export_dot(day, output.as_deref(), &inputs, example),
+        None => init_logs(cli.verbose),
+    }
+
+    let expected = if cli.check {
+        Some(check::Answers::load(&cli.answers)?)
+    } else {
+        None
+    };
+    let year = cli.year.or(config.year).unwrap_or(days::DEFAULT_YEAR);
+    let Some(year_days) = days::year(year) else {
+        bail!("no puzzle of {year} is solved");
+    };
+    let inputs = InputManager::new(year, input_dir)
+        .with_session_file(config.session_file)
+        .with_file(cli.input.clone());
+    let mut selected = [cli.days, cli.day].concat();
+    if selected.is_empty() {
+        selected = config.days;
+    }
+    let days = year_days
+        .iter()
+        .filter(|day| selected.is_empty() || selected.contains(&day.number))
+        .copied()
+        .collect::<Vec<_>>();
+    if cli.list {
+        return list_days(
+            &days,
+            &inputs,
+            &cli.answers,
+            cli.color.enabled(&io::stdout()),
+        );
+    }
+    if cli.input.is_some() && (cli.example || days.len() != 1) {
+        bail!("--input needs a single day to solve with its real input, like --day 7");
+    }
+    if cli.output.is_some() && (year != year2022::YEAR || !days.iter().any(|d| d.number == 10)) {
+        bail!("--output saves the CRT screen of day 10 of 2022, which is not solved");
+    }
+    let days = if cli.example {
+        days
+    } else {
+        let colored = cli.color.enabled(&io::stderr());
+        days.into_iter()
+            .filter(|day| match inputs.missing(day.number) {
+                Some(missing) => {
+                    let message = format!("{missing} (skipped)");
+                    eprintln!("{}", color::paint(&message, Color::Yellow, colored));
+                    false
+                }
+                None => true,
+            })
+            .collect()
+    };
+
+    #[cfg(feature = "profiling")]
+    let profiler = cli
+        .profile
+        .as_ref()
+        .map(|_| aoc2022::profiling::Profiler::start())
+        .transpose()?;
+    let progress = cli.progress.then(MultiProgress::new);
+    let reports = if cli.example {
+        runner::run_all(&days, cli.jobs, progress.as_ref(), |day| {
+            Ok(Input::example(day))
+        })?
+    } else {
+        runner::run_all(&days, cli.jobs, progress.as_ref(), |day| {
+            inputs.load(day.number)
+        })?
+    };
+    #[cfg(feature = "profiling")]
+    if let (Some(profiler), Some(path)) = (&profiler, &cli.profile) {
+        profiler.write_flamegraph(path)?;
+    }
+    let format = if cli.json {
+        Format::Json
+    } else {
+        cli.format.or(config.format).unwrap_or_default()
+    };
+    match format {
+        Format::Json => println!("{}", output::json(&reports)?),
+        Format::Csv => print!("{}", output::csv(&reports)),
+        Format::Table => {
+            let colored = cli.color.enabled(&io::stderr());
+            // the graphical reports are drawn for a terminal only
+            let graphical = io::stderr().is_terminal();
+            for report in &reports {
+                if let Err(message) = &report.outcome {
+                    let diagnosis = report.diagnosis.as_ref().filter(|_| graphical);
+                    match diagnosis.and_then(|d| d.render(colored)) {
+                        Some(diagnosis) => eprintln!("Day {} failed:\n{diagnosis}", report.day),
+                        None => {
+                            let message = format!("Day {} failed: {}", report.day, message);
+                            eprintln!("{}", color::paint(&message, Color::Red, colored));
+                        }
+                    }
                 }
-                GenericErrorTree::Stack { .. } => todo!("stack"),
-                GenericErrorTree::Alt(_) => todo!("alt"),
             }
-            return;
+            let colored = cli.color.enabled(&io::stdout());
+            print!("{}", output::summary_table(&reports, colored));
         }
     }
+    if let Some(path) = &cli.history {
+        let mut history = History::load(path)?;
+        for regression in history.update(year, &reports, cli.threshold) {
+            eprintln!("{regression}");
+        }
+        history.save(path)?;
+    }
+    if let Some(path) = &cli.output {
+        save_crt(path, &inputs, cli.example)?;
+    }
 
-    let journey_length = day12::great_journey(&PathBuf::from("data/day_12_input.txt"));
-    match journey_length {
-        Ok(journey_length) => println!("Path length is {journey_length}"),
-        Err(e) => eprintln!("Something went wrong:{e:?}"),
+    if let Some(expected) = expected {
+        let mismatches = check::check(&reports, &expected);
+        let colored = cli.color.enabled(&io::stderr());
+        for mismatch in &mismatches {
+            eprintln!("{}", mismatch.describe(colored));
+        }
+        if !mismatches.is_empty() {
+            bail!(
+                "{} answer(s) do not match {}",
+                mismatches.len(),
+                cli.answers.display()
+            );
+        }
+    }
+    let failed = reports
+        .iter()
+        .filter(|r| r.is_failure())
+        .map(|r| r.day.to_string())
+        .collect::<Vec<_>>();
+    if !failed.is_empty() {
+        bail!("failed to solve day(s) {}", failed.join(", "));
     }
+    Ok(())
 }
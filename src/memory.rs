@@ -0,0 +1,121 @@
+//! Peak memory of the solvers, counted by a global allocator when the `memory` feature is
+//! enabled.
+//!
+//! The allocations are counted per thread, so that days solved in parallel do not add up: what
+//! a solver allocates on other threads (like the rayon workers of day 19) is not counted.
+
+/// Runs `f`, returning the most memory it had allocated at once, in bytes, when counted.
+#[cfg(not(feature = "memory"))]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+    (f(), None)
+}
+
+#[cfg(feature = "memory")]
+pub use counting::measure;
+
+#[cfg(feature = "memory")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    thread_local! {
+        static CURRENT: Cell<usize> = const { Cell::new(0) };
+        static PEAK: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn grow(size: usize) {
+        let _ = CURRENT.try_with(|current| {
+            let allocated = current.get() + size;
+            current.set(allocated);
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(allocated)));
+        });
+    }
+
+    fn shrink(size: usize) {
+        // memory allocated by another thread may be freed by this one
+        let _ = CURRENT.try_with(|current| current.set(current.get().saturating_sub(size)));
+    }
+
+    /// The system allocator, counting what each thread allocates.
+    struct Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            shrink(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                shrink(layout.size());
+                grow(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    /// Runs `f`, returning the most memory it had allocated at once on this thread, in bytes.
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+        let start = CURRENT.with(Cell::get);
+        let outer = PEAK.with(|peak| peak.replace(start));
+        let result = f();
+        let peak = PEAK.with(|peak| peak.replace(outer.max(peak.get())));
+        (result, Some(peak - start))
+    }
+
+    #[cfg(test)]
+    mod tests {
+
+        use super::*;
+        use spectral::prelude::*;
+
+        #[test]
+        fn peak() {
+            let (sum, peak) = measure(|| {
+                let small = vec![1u8; 1000];
+                drop(small);
+                let large = vec![1u8; 100_000];
+                large.iter().map(|b| *b as usize).sum::<usize>()
+            });
+
+            assert_that!(sum).is_equal_to(100_000);
+            assert_that!(peak)
+                .is_some()
+                .is_greater_than_or_equal_to(100_000);
+            assert_that!(peak).is_some().is_less_than(101_000);
+        }
+
+        #[test]
+        fn nested() {
+            let (inner, outer) = measure(|| {
+                let _kept = vec![1u8; 10_000];
+                measure(|| vec![1u8; 1000].len()).1
+            });
+
+            assert_that!(inner).is_some().is_equal_to(1000);
+            assert_that!(outer)
+                .is_some()
+                .is_greater_than_or_equal_to(11_000);
+        }
+    }
+}
@@ -0,0 +1,661 @@
+use crate::answer::Answer;
+use crate::color::{paint, tint, Color};
+use crate::runner::Report;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// How the results are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// A summary table
+    #[default]
+    Table,
+    /// JSON, for other programs
+    Json,
+    /// CSV, for spreadsheets
+    Csv,
+}
+
+const HEADERS: [&str; 5] = ["Day", "Part 1", "Part 2", "Parse", "Solve"];
+const MEMORY_HEADER: &str = "Memory";
+const MISSING: &str = "-";
+const SEE_BELOW: &str = "(see below)";
+
+fn cell(answer: &Option<Answer>) -> String {
+    match answer {
+        Some(answer) if answer.is_multiline() => SEE_BELOW.to_owned(),
+        Some(answer) => answer.to_string(),
+        None => MISSING.to_owned(),
+    }
+}
+
+fn duration(d: Duration) -> String {
+    format!("{d:.2?}")
+}
+
+/// A number of bytes in the largest binary unit below it.
+fn bytes(n: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = n as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{n}B"),
+        _ => format!("{value:.2}{}", UNITS[unit]),
+    }
+}
+
+fn row(report: &Report, memory: bool) -> Vec<String> {
+    let day = report.day.to_string();
+    let mut row = match &report.outcome {
+        Ok((answers, timings)) => vec![
+            day,
+            cell(&answers.part1),
+            cell(&answers.part2),
+            duration(timings.parse),
+            duration(timings.solve),
+        ],
+        Err(_) => vec![
+            day,
+            "failed".to_owned(),
+            MISSING.to_owned(),
+            MISSING.to_owned(),
+            MISSING.to_owned(),
+        ],
+    };
+    if memory {
+        let peak = report
+            .outcome
+            .as_ref()
+            .ok()
+            .and_then(|(_, t)| t.peak_memory);
+        row.push(peak.map_or_else(|| MISSING.to_owned(), bytes));
+    }
+    row
+}
+
+/// The color of the cell in `column` of the row of `report`, if any.
+fn cell_color(report: &Report, column: usize) -> Option<Color> {
+    match (&report.outcome, column) {
+        (Err(_), 1) => Some(Color::Red),
+        (Ok(_), 3..) => Some(Color::Dim),
+        _ => None,
+    }
+}
+
+fn line<S: AsRef<str>>(
+    table: &mut String,
+    cells: &[S],
+    widths: &[usize],
+    color: impl Fn(usize) -> Option<Color>,
+) {
+    let padded = cells
+        .iter()
+        .zip(widths)
+        .enumerate()
+        .map(|(i, (c, w))| {
+            let c = c.as_ref();
+            // padded outside of the escape codes, which take no room
+            let padding = " ".repeat(w.saturating_sub(c.chars().count()));
+            match color(i) {
+                Some(color) => paint(c, color, true) + &padding,
+                None => c.to_owned() + &padding,
+            }
+        })
+        .collect::<Vec<_>>();
+    let _ = writeln!(table, "{}", padded.join(" | ").trim_end());
+}
+
+/// Width of each column of a table, fitting its header and every row.
+fn widths<S: AsRef<str>>(headers: &[&str], rows: &[Vec<S>]) -> Vec<usize> {
+    (0..headers.len())
+        .map(|i| {
+            rows.iter()
+                .map(|r| r[i].as_ref().chars().count())
+                .chain([headers[i].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Renders the reports as a summary table, followed by the total time and the answers too
+/// large to fit in a cell (like the CRT screen of day 10). The peak memory of the days is shown
+/// too when it was counted.
+///
+/// When `colored`, the headers are in bold, the failed days in red, the timings dimmed and the
+/// lit pixels of the large answers in green.
+pub fn summary_table(reports: &[Report], colored: bool) -> String {
+    let memory = reports
+        .iter()
+        .filter_map(|r| r.outcome.as_ref().ok())
+        .any(|(_, timings)| timings.peak_memory.is_some());
+    let mut headers = HEADERS.to_vec();
+    if memory {
+        headers.push(MEMORY_HEADER);
+    }
+    let rows = reports.iter().map(|r| row(r, memory)).collect::<Vec<_>>();
+    let widths = widths(&headers, &rows);
+
+    let mut table = String::new();
+    line(&mut table, &headers, &widths, |_| {
+        colored.then_some(Color::Bold)
+    });
+    let separators = widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>();
+    line(&mut table, &separators, &widths, |_| None);
+    for (report, r) in reports.iter().zip(&rows) {
+        line(&mut table, r, &widths, |i| {
+            cell_color(report, i).filter(|_| colored)
+        });
+    }
+
+    let total = reports
+        .iter()
+        .filter_map(|r| r.outcome.as_ref().ok())
+        .map(|(_, timings)| timings.total())
+        .sum::<Duration>();
+    let total = format!("Total: {}", duration(total));
+    let _ = writeln!(table, "\n{}", paint(&total, Color::Bold, colored));
+
+    for report in reports {
+        if let Ok((answers, _)) = &report.outcome {
+            for (part, answer) in [(1, &answers.part1), (2, &answers.part2)] {
+                if let Some(answer) = answer.as_ref().filter(|a| a.is_multiline()) {
+                    let answer = tint(&answer.to_string(), '#', Color::Green, colored);
+                    let _ = writeln!(table, "\nDay {} part {}:\n{}", report.day, part, answer);
+                }
+            }
+        }
+    }
+    table
+}
+
+/// Where the input of a day stands before solving it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputStatus {
+    Found(PathBuf),
+    /// Missing, but to be downloaded with the session.
+    Downloadable,
+    Missing,
+}
+
+/// What is known about a day before solving it, as listed by [`day_list`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayStatus {
+    pub day: u8,
+    pub input: InputStatus,
+    /// Whether the example of the puzzle statement is embedded.
+    pub example: bool,
+    /// Whether expected answers are recorded for the day.
+    pub answers: bool,
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+/// Renders the status of the days as a table, the missing inputs in yellow when `colored`.
+pub fn day_list(statuses: &[DayStatus], colored: bool) -> String {
+    let headers = ["Day", "Input", "Example", "Answers"];
+    let rows = statuses
+        .iter()
+        .map(|status| {
+            let input = match &status.input {
+                InputStatus::Found(path) => path.display().to_string(),
+                InputStatus::Downloadable => "to download".to_owned(),
+                InputStatus::Missing => "missing".to_owned(),
+            };
+            vec![
+                status.day.to_string(),
+                input,
+                yes_no(status.example).to_owned(),
+                yes_no(status.answers).to_owned(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let widths = widths(&headers, &rows);
+
+    let mut table = String::new();
+    line(&mut table, &headers, &widths, |_| {
+        colored.then_some(Color::Bold)
+    });
+    let separators = widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>();
+    line(&mut table, &separators, &widths, |_| None);
+    for (status, r) in statuses.iter().zip(&rows) {
+        let missing = status.input == InputStatus::Missing;
+        line(&mut table, r, &widths, |i| {
+            (colored && missing && i == 1).then_some(Color::Yellow)
+        });
+    }
+    table
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Failed,
+}
+
+/// One part of a day, as emitted by [`json`].
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<Answer>,
+    /// Time spent parsing and solving the whole day, in milliseconds: both parts are solved
+    /// together.
+    pub duration_ms: Option<f64>,
+    /// Most memory allocated at once by the whole day, when counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_memory_bytes: Option<usize>,
+    pub status: Status,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+fn entries(report: &Report) -> [Entry; 2] {
+    [1, 2].map(|part| match &report.outcome {
+        Ok((answers, timings)) => Entry {
+            day: report.day,
+            part,
+            answer: answers.get(part).cloned(),
+            duration_ms: Some(timings.total().as_secs_f64() * 1000.0),
+            peak_memory_bytes: timings.peak_memory,
+            status: Status::Ok,
+            error: None,
+        },
+        Err(message) => Entry {
+            day: report.day,
+            part,
+            answer: None,
+            duration_ms: None,
+            peak_memory_bytes: None,
+            status: Status::Failed,
+            error: Some(message.clone()),
+        },
+    })
+}
+
+/// The entries of the reports, one per part of each day.
+pub fn all_entries(reports: &[Report]) -> Vec<Entry> {
+    reports.iter().flat_map(entries).collect()
+}
+
+/// Renders the reports as a JSON array with one entry per part of each day, for scripts.
+pub fn json(reports: &[Report]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&all_entries(reports))
+}
+
+/// Reads back the entries written by [`json`].
+pub fn from_json(data: &str) -> serde_json::Result<Vec<Entry>> {
+    serde_json::from_str(data)
+}
+
+/// Quotes a CSV field if needed, as in RFC 4180.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Renders the reports as CSV with one row per part of each day, holding the same fields as
+/// [`json`]. As in [`summary_table`], the `peak_memory_bytes` column is only there when the memory
+/// of a day was counted.
+pub fn csv(reports: &[Report]) -> String {
+    let entries = all_entries(reports);
+    let memory = entries.iter().any(|e| e.peak_memory_bytes.is_some());
+    let mut csv = String::from("day,part,answer,duration_ms,");
+    if memory {
+        csv.push_str("peak_memory_bytes,");
+    }
+    csv.push_str("status,error\n");
+    for entry in entries {
+        let status = match entry.status {
+            Status::Ok => "ok",
+            Status::Failed => "failed",
+        };
+        let answer = entry.answer.as_ref().map(Answer::to_string);
+        let _ = write!(
+            csv,
+            "{},{},{},{},",
+            entry.day,
+            entry.part,
+            csv_field(&answer.unwrap_or_default()),
+            entry.duration_ms.map(|d| d.to_string()).unwrap_or_default()
+        );
+        if memory {
+            let peak = entry.peak_memory_bytes.map(|b| b.to_string());
+            let _ = write!(csv, "{},", peak.unwrap_or_default());
+        }
+        let _ = writeln!(
+            csv,
+            "{},{}",
+            status,
+            csv_field(entry.error.as_deref().unwrap_or_default())
+        );
+    }
+    csv
+}
+
+/// A table cell holding `text` in Markdown.
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// Renders the entries of the days of `year` as a Markdown table, with a link to the module of
+/// each day, relative to the root of the repository. A time that is not a valid duration (as read
+/// from a hand-edited file) is shown as missing.
+pub fn markdown(year: u16, entries: &[Entry]) -> String {
+    let mut table = format!("# Advent of Code {year}\n\n");
+    table.push_str("| Day | Part 1 | Part 2 | Time |\n");
+    table.push_str("| --: | ------ | ------ | ---: |\n");
+    for day in entries.chunk_by(|a, b| a.day == b.day) {
+        let answer = |part| {
+            let entry = day.iter().find(|e| e.part == part);
+            match entry.map(|e| (e.status, &e.answer)) {
+                Some((Status::Failed, _)) => "failed".to_owned(),
+                Some((_, Some(answer))) => markdown_cell(&answer.to_string()),
+                _ => MISSING.to_owned(),
+            }
+        };
+        let time = day
+            .iter()
+            .find_map(|e| e.duration_ms)
+            .and_then(|ms| Duration::try_from_secs_f64(ms / 1000.0).ok())
+            .map(duration)
+            .unwrap_or_else(|| MISSING.to_owned());
+        let _ = writeln!(
+            table,
+            "| [{0}](src/year{year}/day{0:02}/mod.rs) | {1} | {2} | {time} |",
+            day[0].day,
+            answer(1),
+            answer(2)
+        );
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::runner::{Answers, Timings};
+    use spectral::prelude::*;
+
+    fn timings(parse: u64, solve: u64) -> Timings {
+        Timings {
+            parse: Duration::from_millis(parse),
+            solve: Duration::from_millis(solve),
+            peak_memory: None,
+        }
+    }
+
+    fn reports() -> [Report; 4] {
+        [
+            Report {
+                day: 1,
+                diagnosis: None,
+                outcome: Ok((Answers::from((24000, 45000)), timings(1, 2))),
+            },
+            Report {
+                day: 5,
+                diagnosis: None,
+                outcome: Ok((Answers::part2("MCD"), timings(3, 4))),
+            },
+            Report {
+                day: 7,
+                diagnosis: None,
+                outcome: Err("no input".to_owned()),
+            },
+            Report {
+                day: 10,
+                diagnosis: None,
+                outcome: Ok((
+                    Answers::from((13140, Answer::grid("##..\n..##"))),
+                    timings(0, 10),
+                )),
+            },
+        ]
+    }
+
+    #[test]
+    fn table() {
+        assert_that!(summary_table(&reports(), false).as_str()).is_equal_to(
+            r#"Day | Part 1 | Part 2      | Parse  | Solve
+--- | ------ | ----------- | ------ | -------
+1   | 24000  | 45000       | 1.00ms | 2.00ms
+5   | -      | MCD         | 3.00ms | 4.00ms
+7   | failed | -           | -      | -
+10  | 13140  | (see below) | 0.00ns | 10.00ms
+
+Total: 20.00ms
+
+Day 10 part 2:
+##..
+..##
+"#,
+        );
+    }
+
+    #[test]
+    fn table_with_memory() {
+        let reports = [
+            Report {
+                day: 11,
+                diagnosis: None,
+                outcome: Ok((
+                    Answers::from((10605, 2713310158u64)),
+                    Timings {
+                        peak_memory: Some(3 * 1024 * 1024 / 2),
+                        ..timings(1, 2)
+                    },
+                )),
+            },
+            Report {
+                day: 12,
+                diagnosis: None,
+                outcome: Err("no input".to_owned()),
+            },
+        ];
+
+        assert_that!(summary_table(&reports, false).as_str()).is_equal_to(
+            r#"Day | Part 1 | Part 2     | Parse  | Solve  | Memory
+--- | ------ | ---------- | ------ | ------ | -------
+11  | 10605  | 2713310158 | 1.00ms | 2.00ms | 1.50MiB
+12  | failed | -          | -      | -      | -
+
+Total: 3.00ms
+"#,
+        );
+    }
+
+    #[test]
+    fn colored_table() {
+        let table = summary_table(&reports(), true);
+        let lines = table.lines().collect::<Vec<_>>();
+
+        assert_that!(lines[0]).starts_with("\x1b[1mDay\x1b[0m | \x1b[1mPart 1\x1b[0m |");
+        assert_that!(lines[2]).is_equal_to(
+            "1   | 24000  | 45000       | \x1b[2m1.00ms\x1b[0m | \x1b[2m2.00ms\x1b[0m",
+        );
+        assert_that!(lines[4])
+            .is_equal_to("7   | \x1b[31mfailed\x1b[0m | -           | -      | -");
+        assert_that!(lines[7]).is_equal_to("\x1b[1mTotal: 20.00ms\x1b[0m");
+        assert_that!(lines[10]).is_equal_to("\x1b[32m##\x1b[0m..");
+    }
+
+    #[test]
+    fn days_status() {
+        let statuses = [
+            DayStatus {
+                day: 1,
+                input: InputStatus::Found(PathBuf::from("data/2022/day_1_input.txt")),
+                example: true,
+                answers: true,
+            },
+            DayStatus {
+                day: 2,
+                input: InputStatus::Downloadable,
+                example: true,
+                answers: false,
+            },
+            DayStatus {
+                day: 3,
+                input: InputStatus::Missing,
+                example: false,
+                answers: false,
+            },
+        ];
+
+        assert_that!(day_list(&statuses, false).as_str()).is_equal_to(
+            r#"Day | Input                     | Example | Answers
+--- | ------------------------- | ------- | -------
+1   | data/2022/day_1_input.txt | yes     | yes
+2   | to download               | yes     | no
+3   | missing                   | no      | no
+"#,
+        );
+        assert_that!(day_list(&statuses, true).lines().nth(4))
+            .is_some()
+            .is_equal_to("3   | \x1b[33mmissing\x1b[0m                   | no      | no");
+    }
+
+    #[test]
+    fn byte_units() {
+        assert_that!(bytes(512).as_str()).is_equal_to("512B");
+        assert_that!(bytes(2048).as_str()).is_equal_to("2.00KiB");
+        assert_that!(bytes(5 * 1024 * 1024 * 1024).as_str()).is_equal_to("5.00GiB");
+    }
+
+    #[test]
+    fn json_entries() {
+        let json: serde_json::Value = serde_json::from_str(&json(&reports()).unwrap()).unwrap();
+
+        assert_that!(json[0]).is_equal_to(serde_json::json!({
+            "day": 1,
+            "part": 1,
+            "answer": 24000,
+            "duration_ms": 3.0,
+            "status": "ok"
+        }));
+        assert_that!(json[2]).is_equal_to(serde_json::json!({
+            "day": 5,
+            "part": 1,
+            "answer": null,
+            "duration_ms": 7.0,
+            "status": "ok"
+        }));
+        assert_that!(json[5]).is_equal_to(serde_json::json!({
+            "day": 7,
+            "part": 2,
+            "answer": null,
+            "duration_ms": null,
+            "status": "failed",
+            "error": "no input"
+        }));
+        assert_that!(json[3]["answer"]).is_equal_to(serde_json::json!("MCD"));
+        assert_that!(json[7]["answer"]).is_equal_to(serde_json::json!(["##..", "..##"]));
+    }
+
+    #[test]
+    fn csv_rows() {
+        assert_that!(csv(&reports()).as_str()).is_equal_to(
+            r###"day,part,answer,duration_ms,status,error
+1,1,24000,3,ok,
+1,2,45000,3,ok,
+5,1,,7,ok,
+5,2,MCD,7,ok,
+7,1,,,failed,no input
+7,2,,,failed,no input
+10,1,13140,10,ok,
+10,2,"##..
+..##",10,ok,
+"###,
+        );
+    }
+
+    #[test]
+    fn csv_rows_with_memory() {
+        let reports = [
+            Report {
+                day: 11,
+                diagnosis: None,
+                outcome: Ok((
+                    Answers::from((10605, 2713310158u64)),
+                    Timings {
+                        peak_memory: Some(1536),
+                        ..timings(1, 2)
+                    },
+                )),
+            },
+            Report {
+                day: 12,
+                diagnosis: None,
+                outcome: Err("no input".to_owned()),
+            },
+        ];
+
+        assert_that!(csv(&reports).as_str()).is_equal_to(
+            r#"day,part,answer,duration_ms,peak_memory_bytes,status,error
+11,1,10605,3,1536,ok,
+11,2,2713310158,3,1536,ok,
+12,1,,,,failed,no input
+12,2,,,,failed,no input
+"#,
+        );
+    }
+
+    #[test]
+    fn csv_quoting() {
+        assert_that!(csv_field("a,b").as_str()).is_equal_to(r#""a,b""#);
+        assert_that!(csv_field(r#"say "hi""#).as_str()).is_equal_to(r#""say ""hi""""#);
+        assert_that!(csv_field("plain").as_str()).is_equal_to("plain");
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let entries = from_json(&json(&reports()).unwrap());
+
+        assert_that!(entries)
+            .is_ok()
+            .is_equal_to(all_entries(&reports()));
+    }
+
+    #[test]
+    fn markdown_table() {
+        assert_that!(markdown(2022, &all_entries(&reports())).as_str()).is_equal_to(
+            r#"# Advent of Code 2022
+
+| Day | Part 1 | Part 2 | Time |
+| --: | ------ | ------ | ---: |
+| [1](src/year2022/day01/mod.rs) | 24000 | 45000 | 3.00ms |
+| [5](src/year2022/day05/mod.rs) | - | MCD | 7.00ms |
+| [7](src/year2022/day07/mod.rs) | failed | failed | - |
+| [10](src/year2022/day10/mod.rs) | 13140 | ##..<br>..## | 10.00ms |
+"#,
+        );
+    }
+
+    #[test]
+    fn markdown_invalid_time() {
+        let mut entries = all_entries(&reports()[..1]);
+        entries[0].duration_ms = Some(-1.0);
+        entries[1].duration_ms = Some(-1.0);
+
+        assert_that!(markdown(2022, &entries).as_str())
+            .contains("| [1](src/year2022/day01/mod.rs) | 24000 | 45000 | - |");
+    }
+}
@@ -0,0 +1,81 @@
+//! The nom grammars of the days with the most structured inputs, to reuse the parsing layer with
+//! other algorithms.
+//!
+//! The parsers are generic over the nom error type, read a `Span` of the input to locate errors,
+//! and expect whole lines: the last line may miss its line ending.
+//! [`crate::diagnostics::parse_all`] runs them on a whole input, reporting where it is invalid.
+//!
+//! ```
+//! use aoc2022::parsers::day10::{instructions, Instruction, Span};
+//!
+//! let (rest, program) = instructions::<()>(Span::new("noop\naddx -5\n")).unwrap();
+//!
+//! assert!(rest.is_empty());
+//! assert_eq!(program, [Instruction::Noop, Instruction::Addx(-5)]);
+//! ```
+
+/// The stacks of crates and the rearrangement procedure.
+pub mod day05 {
+    pub use crate::year2022::day05::{procedure, Move, Procedure, Span, Stack};
+}
+
+/// The terminal output of the exploration of the file system.
+pub mod day07 {
+    pub use crate::year2022::day07::{terminal_output, Span, TreeBuildCommand};
+}
+
+/// The moves of the head of the rope.
+pub mod day09 {
+    pub use crate::year2022::day09::{moves, Move, Span};
+}
+
+/// The program of the CPU.
+pub mod day10 {
+    pub use crate::year2022::day10::{instructions, Instruction, Span};
+}
+
+/// The notes about the monkeys.
+pub mod day11 {
+    pub use crate::year2022::day11::{monkeys, Monkey, Operation, Span, Term};
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::diagnostics::parse_all;
+    use crate::year2022;
+    use spectral::prelude::*;
+
+    #[test]
+    fn day05_procedure() {
+        let (stacks, moves) = parse_all(year2022::day05::EXAMPLE, day05::procedure).unwrap();
+
+        let crates = stacks
+            .iter()
+            .map(|s| (s.id().0, s.crates().iter().map(|c| c.0).collect::<String>()))
+            .collect::<Vec<_>>();
+        assert_that!(crates).is_equal_to(vec![
+            (1, "ZN".to_owned()),
+            (2, "MCD".to_owned()),
+            (3, "P".to_owned()),
+        ]);
+        assert_that!(moves.first().map(|m| (m.count(), m.from().0, m.to().0)))
+            .is_some()
+            .is_equal_to((1, 2, 1));
+    }
+
+    #[test]
+    fn day11_monkeys() {
+        let monkeys = parse_all(year2022::day11::EXAMPLE, day11::monkeys).unwrap();
+
+        let first = &monkeys[0];
+        assert_that!(first.id()).is_equal_to(0);
+        assert_that!(first.items().collect::<Vec<_>>()).is_equal_to(vec![79, 98]);
+        assert_that!(first.operation()).is_equal_to(day11::Operation::Mul(
+            day11::Term::Old,
+            day11::Term::Constant(19),
+        ));
+        assert_that!((first.divisor(), first.targets())).is_equal_to((23, (2, 3)));
+    }
+}
@@ -0,0 +1,34 @@
+//! Flamegraphs of the solvers, sampled by pprof when the `profiling` feature is enabled.
+
+use anyhow::{Context, Result};
+use pprof::{ProfilerGuard, ProfilerGuardBuilder};
+use std::fs::File;
+use std::path::Path;
+
+/// Samples per second, on each thread.
+const FREQUENCY: i32 = 1000;
+
+/// Samples the stacks of every thread until written as a flamegraph.
+pub struct Profiler(ProfilerGuard<'static>);
+
+impl Profiler {
+    pub fn start() -> Result<Self> {
+        let guard = ProfilerGuardBuilder::default()
+            .frequency(FREQUENCY)
+            // frames of the system libraries only clutter the graph
+            .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+            .build()
+            .context("cannot start the profiler")?;
+        Ok(Self(guard))
+    }
+
+    /// Writes what was sampled so far as a flamegraph SVG to `path`.
+    pub fn write_flamegraph(&self, path: &Path) -> Result<()> {
+        let report = self.0.report().build()?;
+        let file =
+            File::create(path).with_context(|| format!("cannot create {}", path.display()))?;
+        report
+            .flamegraph(file)
+            .with_context(|| format!("cannot write the flamegraph to {}", path.display()))
+    }
+}
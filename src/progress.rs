@@ -0,0 +1,73 @@
+use indicatif::ProgressBar;
+use std::cell::RefCell;
+
+thread_local! {
+    static CURRENT: RefCell<Option<ProgressBar>> = const { RefCell::new(None) };
+}
+
+/// Progress of a long-running solver, shown only when the runner was asked to.
+///
+/// Solvers get it with [`current`] on the thread they are called on, then may share it with
+/// the threads they spawn.
+#[derive(Clone, Debug, Default)]
+pub struct Progress(Option<ProgressBar>);
+
+impl Progress {
+    /// Sets the number of steps to go through.
+    pub fn set_length(&self, length: u64) {
+        if let Some(bar) = &self.0 {
+            bar.set_length(length);
+        }
+    }
+
+    /// Advances by `delta` steps.
+    pub fn inc(&self, delta: u64) {
+        if let Some(bar) = &self.0 {
+            bar.inc(delta);
+        }
+    }
+}
+
+/// The progress of the solver running on this thread, doing nothing if none is reported.
+pub fn current() -> Progress {
+    Progress(CURRENT.with(|current| current.borrow().clone()))
+}
+
+/// Runs `f`, reporting the progress its solvers make on `bar`.
+pub fn report_on<R>(bar: &ProgressBar, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT.with(|current| current.replace(Some(bar.clone())));
+    let result = f();
+    CURRENT.with(|current| current.replace(previous));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn reported() {
+        let bar = ProgressBar::hidden();
+
+        report_on(&bar, || {
+            let progress = current();
+            progress.set_length(10);
+            progress.inc(3);
+        });
+
+        assert_that!(bar.length()).is_equal_to(Some(10));
+        assert_that!(bar.position()).is_equal_to(3);
+    }
+
+    #[test]
+    fn not_reported() {
+        let bar = ProgressBar::hidden();
+        report_on(&bar, || {});
+
+        current().inc(3);
+
+        assert_that!(bar.position()).is_equal_to(0);
+    }
+}
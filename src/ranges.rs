@@ -0,0 +1,122 @@
+//! Operations on inclusive ranges, like the sections assigned to the elves of day 4.
+//!
+//! Empty ranges (whose start is after their end) hold nothing: they are included in any range
+//! and overlap none.
+
+use std::ops::RangeInclusive;
+
+fn min<T: PartialOrd + Copy>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+fn max<T: PartialOrd + Copy>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+/// Whether every value of `inner` is in `outer`.
+pub fn includes<T>(outer: &RangeInclusive<T>, inner: &RangeInclusive<T>) -> bool
+where
+    T: PartialOrd,
+{
+    inner.is_empty() || (outer.start() <= inner.start() && inner.end() <= outer.end())
+}
+
+/// Whether the ranges have a value in common.
+pub fn overlaps<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool
+where
+    T: PartialOrd,
+{
+    !a.is_empty() && !b.is_empty() && a.start() <= b.end() && b.start() <= a.end()
+}
+
+/// The values the ranges have in common, if any.
+pub fn intersection<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> Option<RangeInclusive<T>>
+where
+    T: PartialOrd + Copy,
+{
+    overlaps(a, b).then(|| max(*a.start(), *b.start())..=min(*a.end(), *b.end()))
+}
+
+/// The values of either range, if they make a single range: when the ranges overlap, or one of
+/// them is empty. Ranges that are only next to each other are not merged, as that would take
+/// knowing which values follow each other.
+pub fn union<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> Option<RangeInclusive<T>>
+where
+    T: PartialOrd + Copy,
+{
+    if a.is_empty() {
+        Some(b.clone())
+    } else if b.is_empty() {
+        Some(a.clone())
+    } else {
+        overlaps(a, b).then(|| min(*a.start(), *b.start())..=max(*a.end(), *b.end()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn inclusion() {
+        assert_that!(includes(&(2..=8), &(3..=7))).is_true();
+        assert_that!(includes(&(2..=8), &(2..=8))).is_true();
+        assert_that!(includes(&(3..=7), &(2..=8))).is_false();
+        assert_that!(includes(&(2..=4), &(4..=6))).is_false();
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = 5..=1;
+        assert_that!(includes(&(2..=4), &empty)).is_true();
+        assert_that!(includes(&empty, &(2..=4))).is_false();
+    }
+
+    #[test]
+    fn overlap_is_symmetric() {
+        let cases = [
+            (2..=4, 6..=8, false),
+            (2..=3, 4..=5, false),
+            (5..=7, 7..=9, true),
+            (2..=8, 3..=7, true),
+            (6..=6, 4..=6, true),
+            (2..=6, 4..=8, true),
+        ];
+        for (a, b, expected) in cases {
+            assert_that!(overlaps(&a, &b)).is_equal_to(expected);
+            assert_that!(overlaps(&b, &a)).is_equal_to(expected);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn empty_ranges_overlap_nothing() {
+        assert_that!(overlaps(&(5..=1), &(0..=10))).is_false();
+        assert_that!(overlaps(&(0..=10), &(5..=1))).is_false();
+    }
+
+    #[test]
+    fn intersections() {
+        assert_that!(intersection(&(2..=6), &(4..=8))).is_equal_to(Some(4..=6));
+        assert_that!(intersection(&(2..=8), &(3..=7))).is_equal_to(Some(3..=7));
+        assert_that!(intersection(&(5..=7), &(7..=9))).is_equal_to(Some(7..=7));
+        assert_that!(intersection(&(2..=4), &(6..=8))).is_none();
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn unions() {
+        assert_that!(union(&(2..=6), &(4..=8))).is_equal_to(Some(2..=8));
+        assert_that!(union(&(3..=7), &(2..=8))).is_equal_to(Some(2..=8));
+        assert_that!(union(&(2..=3), &(4..=5))).is_none();
+        assert_that!(union(&(5..=1), &(4..=5))).is_equal_to(Some(4..=5));
+        assert_that!(union(&(1.5..=2.5), &(2.0..=3.0))).is_equal_to(Some(1.5..=3.0));
+    }
+}
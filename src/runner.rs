@@ -0,0 +1,431 @@
+use crate::answer::Answer;
+use crate::diagnostics::{self, Diagnosis};
+use crate::error::Result;
+use crate::memory;
+use crate::progress;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use std::any::Any;
+use std::borrow::Borrow;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{debug, debug_span, info_span};
+use web_time::Instant;
+
+/// Answers of a day, `None` for a part the day does not report.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Answers {
+    pub fn part1(answer: impl Into<Answer>) -> Self {
+        Self {
+            part1: Some(answer.into()),
+            part2: None,
+        }
+    }
+
+    pub fn part2(answer: impl Into<Answer>) -> Self {
+        Self {
+            part1: None,
+            part2: Some(answer.into()),
+        }
+    }
+
+    /// The answer to the given part (1 or 2) of the day.
+    pub fn get(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl<A, B> From<(A, B)> for Answers
+where
+    A: Into<Answer>,
+    B: Into<Answer>,
+{
+    fn from((part1, part2): (A, B)) -> Self {
+        Self {
+            part1: Some(part1.into()),
+            part2: Some(part2.into()),
+        }
+    }
+}
+
+/// Wall-clock time spent in each phase of a day.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub solve: Duration,
+    /// Most memory allocated at once by the day, in bytes, when the `memory` feature counts it.
+    pub peak_memory: Option<usize>,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+pub type Solution = (Answers, Timings);
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    /// Example input of the puzzle statement.
+    pub example: &'static str,
+    pub solver: fn(&str) -> Result<Solution>,
+    /// Solver of the example, for the puzzles whose statement solves it with other parameters
+    /// (a smaller area, fewer rounds...): the same as `solver` by default.
+    pub example_solver: fn(&str) -> Result<Solution>,
+}
+
+impl Day {
+    pub const fn new(
+        number: u8,
+        example: &'static str,
+        solver: fn(&str) -> Result<Solution>,
+    ) -> Self {
+        Self {
+            number,
+            example,
+            solver,
+            example_solver: solver,
+        }
+    }
+
+    /// The same day, solving its example with `example_solver`.
+    pub const fn with_example_solver(self, example_solver: fn(&str) -> Result<Solution>) -> Self {
+        Self {
+            example_solver,
+            ..self
+        }
+    }
+}
+
+/// A puzzle input, with the file it was read from, if any.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Input {
+    pub data: String,
+    pub path: Option<PathBuf>,
+    /// Whether this is the example of the puzzle statement, solved by [`Day::example_solver`].
+    pub example: bool,
+}
+
+impl Input {
+    /// The example of the puzzle statement of `day`.
+    pub fn example(day: &Day) -> Self {
+        Self {
+            example: true,
+            ..Self::from(day.example)
+        }
+    }
+}
+
+impl From<String> for Input {
+    fn from(data: String) -> Self {
+        Self {
+            data,
+            path: None,
+            example: false,
+        }
+    }
+}
+
+impl From<&str> for Input {
+    fn from(data: &str) -> Self {
+        Self::from(data.to_owned())
+    }
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub day: u8,
+    /// The solution, or what went wrong on a line.
+    pub outcome: Result<Solution, String>,
+    /// Where the input is wrong, for a day that could not parse it.
+    pub diagnosis: Option<Box<Diagnosis>>,
+}
+
+impl Report {
+    /// Report of a day whose input could not be read.
+    pub fn unreadable(day: u8, error: &anyhow::Error) -> Self {
+        Self {
+            day,
+            outcome: Err(format!("{error:#}")),
+            diagnosis: None,
+        }
+    }
+
+    /// Report of a day whose solver panicked, with the message of the panic if it has one.
+    pub fn panicked(day: u8, payload: &(dyn Any + Send)) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str));
+        Self {
+            day,
+            outcome: Err(match message {
+                Some(message) => format!("the solver panicked: {message}"),
+                None => "the solver panicked".to_owned(),
+            }),
+            diagnosis: None,
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.outcome.is_err()
+    }
+}
+
+/// Parses then solves the puzzle, timing both phases.
+pub fn timed<P, I, S>(
+    data: &str,
+    parse: impl Fn(&str) -> Result<P>,
+    solve: impl Fn(&I) -> Result<S>,
+) -> Result<Solution>
+where
+    P: Borrow<I>,
+    I: ?Sized,
+    S: Into<Answers>,
+{
+    let start = Instant::now();
+    let input = debug_span!("parse").in_scope(|| parse(data))?;
+    let parsed = Instant::now();
+    debug!(elapsed = ?(parsed - start), "parsed {} bytes", data.len());
+    let answers: Answers = debug_span!("solve")
+        .in_scope(|| solve(input.borrow()))?
+        .into();
+    let solved = Instant::now();
+    debug!(elapsed = ?(solved - parsed), ?answers, "solved");
+
+    Ok((
+        answers,
+        Timings {
+            parse: parsed - start,
+            solve: solved - parsed,
+            peak_memory: None,
+        },
+    ))
+}
+
+/// Solves a day on `input`, describing what went wrong if it fails, along with the file it was
+/// reading.
+pub fn run(day: &Day, input: &Input) -> Report {
+    let _span = info_span!("day", number = day.number).entered();
+    let solver = if input.example {
+        day.example_solver
+    } else {
+        day.solver
+    };
+    let (outcome, peak_memory) = memory::measure(|| solver(&input.data));
+    let name = input.path.as_ref().map(|path| path.display().to_string());
+    let diagnosis = outcome
+        .as_ref()
+        .err()
+        .and_then(|e| Diagnosis::of(e, name.as_deref()))
+        .map(Box::new);
+    let outcome = outcome
+        .map(|(answers, timings)| {
+            (
+                answers,
+                Timings {
+                    peak_memory,
+                    ..timings
+                },
+            )
+        })
+        .map_err(|e| {
+            debug!(error = %e, "failed");
+            match &name {
+                Some(name) => diagnostics::describe_input(&e, name),
+                None => diagnostics::describe(&e),
+            }
+        });
+    Report {
+        day: day.number,
+        outcome,
+        diagnosis,
+    }
+}
+
+fn progress_bar(day: &Day, progress: Option<&MultiProgress>) -> ProgressBar {
+    match progress {
+        Some(multi) => {
+            let style =
+                ProgressStyle::with_template("{prefix:>6} [{bar:40}] {pos}/{len} {elapsed}")
+                    .unwrap_or_else(|_| ProgressStyle::default_bar())
+                    .progress_chars("=> ");
+            multi
+                .add(ProgressBar::new(0))
+                .with_style(style)
+                .with_prefix(format!("Day {}", day.number))
+        }
+        None => ProgressBar::hidden(),
+    }
+}
+
+/// Runs the days on a pool of `jobs` threads (as many as CPUs when `None` or 0), each one on the
+/// input given by `input` (like [`Day::example`]). The solvers report their progress on
+/// `progress` when given.
+///
+/// Reports are returned in the order of the days, whatever order they complete in.
+pub fn run_all<F>(
+    days: &[Day],
+    jobs: Option<usize>,
+    progress: Option<&MultiProgress>,
+    input: F,
+) -> anyhow::Result<Vec<Report>>
+where
+    F: Fn(&Day) -> anyhow::Result<Input> + Sync,
+{
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(jobs.unwrap_or_default())
+        .build()?;
+    Ok(pool.install(|| {
+        days.par_iter()
+            .map(|day| {
+                let bar = progress_bar(day, progress);
+                let report = match input(day) {
+                    Ok(input) => progress::report_on(&bar, || run(day, &input)),
+                    Err(e) => Report::unreadable(day.number, &e),
+                };
+                bar.finish_and_clear();
+                report
+            })
+            .collect()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::diagnostics::BadInput;
+    use spectral::prelude::*;
+    use std::thread::sleep;
+
+    fn parse_numbers(data: &str) -> Result<Vec<u32>> {
+        Ok(data
+            .split(',')
+            .map(|n| n.parse().map_err(|e| BadInput::at(data, n, e)))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn sum_and_max(numbers: &[u32]) -> Result<(u32, u32)> {
+        Ok((
+            numbers.iter().sum(),
+            numbers.iter().copied().max().unwrap_or_default(),
+        ))
+    }
+
+    const SLOW: Day = Day::new(1, "1", |data| {
+        sleep(Duration::from_millis(50));
+        timed(data, parse_numbers, sum_and_max)
+    });
+    const FAST: Day = Day::new(2, "1,5,2", |data| timed(data, parse_numbers, sum_and_max));
+    const ONLY_PART_2: Day = Day::new(3, "1,5,2", |data| {
+        timed(data, parse_numbers, |n: &[u32]| Ok(Answers::part2(n.len())))
+    });
+
+    #[test]
+    fn timed_solution() {
+        let (answers, timings) = timed("1,5,2", parse_numbers, sum_and_max).unwrap();
+
+        assert_that!(answers).is_equal_to(Answers::from((8, 5)));
+        assert_that!(timings.total()).is_equal_to(timings.parse + timings.solve);
+    }
+
+    #[test]
+    fn parse_failure() {
+        let report = run(&FAST, &Input::from("1,a"));
+
+        assert_that!(report.outcome).is_err();
+    }
+
+    #[test]
+    fn failure_in_file() {
+        let input = Input {
+            data: "1,a".to_owned(),
+            path: Some(PathBuf::from("data/2022/day_2_input.txt")),
+            example: false,
+        };
+
+        let report = run(&FAST, &input);
+
+        assert_that!(report.outcome)
+            .is_err()
+            .contains("data/2022/day_2_input.txt");
+    }
+
+    #[test]
+    fn single_part() {
+        let report = run(&ONLY_PART_2, &Input::from("1,5,2"));
+
+        assert_that!(report.outcome.map(|(answers, _)| answers))
+            .is_ok()
+            .is_equal_to(Answers {
+                part1: None,
+                part2: Some(Answer::Number(3)),
+            });
+    }
+
+    #[test]
+    fn reports_keep_the_days_order() {
+        let days = [SLOW, FAST, ONLY_PART_2];
+
+        let reports = run_all(&days, Some(3), None, |_| Ok(Input::from("1"))).unwrap();
+
+        assert_that!(reports.iter().map(|r| r.day).collect::<Vec<_>>()).is_equal_to(vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn missing_input() {
+        let reports =
+            run_all(&[FAST], Some(1), None, |_| Err(anyhow::anyhow!("no input"))).unwrap();
+
+        assert_that!(reports[0].outcome).is_err();
+        assert_that!(reports[0].is_failure()).is_true();
+    }
+
+    #[test]
+    fn example_solver() {
+        let day = ONLY_PART_2.with_example_solver(|data| timed(data, parse_numbers, sum_and_max));
+
+        let example = run(&day, &Input::example(&day));
+        let real = run(&day, &Input::from(day.example));
+
+        assert_that!(example.outcome.map(|(answers, _)| answers))
+            .is_ok()
+            .is_equal_to(Answers::from((8, 5)));
+        assert_that!(real.outcome.map(|(answers, _)| answers))
+            .is_ok()
+            .is_equal_to(Answers::part2(3));
+    }
+
+    #[test]
+    fn panicked_solver() {
+        let payload = std::panic::catch_unwind(|| panic!("index out of bounds")).unwrap_err();
+
+        let report = Report::panicked(8, payload.as_ref());
+
+        assert_that!(report.outcome)
+            .is_err()
+            .is_equal_to("the solver panicked: index out of bounds".to_owned());
+    }
+
+    #[test]
+    fn examples() {
+        let reports = run_all(&[FAST], Some(1), None, |day| Ok(Input::example(day))).unwrap();
+
+        assert_that!(reports[0].outcome.as_ref().map(|(answers, _)| answers))
+            .is_ok()
+            .is_equal_to(&Answers::from((8, 5)));
+    }
+}
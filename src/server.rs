@@ -0,0 +1,139 @@
+use crate::answer::{Answer, Part};
+use crate::diagnostics;
+use crate::error::AocError;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::Serialize;
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+use web_time::Instant;
+
+/// Answer to a part, as returned by `POST /solve/{day}/{part}`.
+#[derive(Serialize, Debug)]
+struct Solved {
+    day: u8,
+    part: u8,
+    answer: Answer,
+    /// Time spent parsing and solving the whole day, in milliseconds.
+    duration_ms: f64,
+}
+
+/// What went wrong with a request.
+#[derive(Serialize, Debug)]
+struct Failure {
+    error: String,
+}
+
+fn failure(status: StatusCode, error: String) -> Response {
+    (status, Json(Failure { error })).into_response()
+}
+
+/// Solves a part of a day of the default year on the input sent as the request body.
+///
+/// Answers with 404 for a day or part that does not exist, with 422 when the input cannot be
+/// solved.
+async fn solve(Path((day, part)): Path<(u8, u8)>, input: String) -> Response {
+    let Ok(part) = Part::try_from(part) else {
+        return failure(StatusCode::NOT_FOUND, format!("no part {part} in a puzzle"));
+    };
+    // solvers are CPU bound: keep them off the threads serving requests
+    let solution = tokio::task::spawn_blocking(move || {
+        let start = Instant::now();
+        crate::solve(day, part, &input).map(|answer| (answer, start.elapsed()))
+    })
+    .await;
+    match solution {
+        Ok(Ok((answer, duration))) => Json(Solved {
+            day,
+            part: part.number(),
+            answer,
+            duration_ms: duration.as_secs_f64() * 1000.0,
+        })
+        .into_response(),
+        Ok(Err(e @ (AocError::UnknownDay(_) | AocError::NoAnswer { .. }))) => {
+            failure(StatusCode::NOT_FOUND, e.to_string())
+        }
+        Ok(Err(e)) => {
+            warn!(day, error = %e, "failed");
+            failure(StatusCode::UNPROCESSABLE_ENTITY, diagnostics::describe(&e))
+        }
+        Err(e) => failure(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+/// Routes of the solving service.
+pub fn router() -> Router {
+    Router::new().route("/solve/{day}/{part}", post(solve))
+}
+
+/// Serves the solvers over HTTP on `addr` until the process is stopped.
+pub async fn serve(addr: SocketAddr) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    info!("listening on {}", listener.local_addr()?);
+    axum::serve(listener, router()).await
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::year2022::{day01, day25};
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use spectral::prelude::*;
+    use tower::ServiceExt;
+
+    async fn post(uri: &str, input: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::post(uri)
+            .body(Body::from(input.to_owned()))
+            .unwrap();
+        let response = router().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn solved() {
+        let (status, body) = post("/solve/1/2", day01::EXAMPLE).await;
+
+        assert_that!(status).is_equal_to(StatusCode::OK);
+        assert_that!(body["answer"]).is_equal_to(serde_json::json!(45000));
+        assert_that!(body["part"]).is_equal_to(serde_json::json!(2));
+    }
+
+    #[tokio::test]
+    async fn unknown_day() {
+        let (status, _) = post("/solve/26/1", "").await;
+
+        assert_that!(status).is_equal_to(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn missing_part() {
+        let (status, _) = post("/solve/25/2", day25::EXAMPLE).await;
+
+        assert_that!(status).is_equal_to(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn unknown_part() {
+        let (status, _) = post("/solve/1/3", day01::EXAMPLE).await;
+
+        assert_that!(status).is_equal_to(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn bad_input() {
+        let (status, body) = post("/solve/2/1", "A W").await;
+
+        assert_that!(status).is_equal_to(StatusCode::UNPROCESSABLE_ENTITY);
+        assert_that!(body["error"].as_str())
+            .is_some()
+            .contains("bad input");
+    }
+}
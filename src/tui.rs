@@ -0,0 +1,340 @@
+use crate::answer::Answer;
+use crate::inputs::InputManager;
+use crate::runner::{self, Day, Input, Report};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+
+const HEADERS: [&str; 6] = ["Day", "Status", "Part 1", "Part 2", "Parse", "Solve"];
+const MISSING: &str = "-";
+const SEE_OUTPUT: &str = "(see output)";
+const HELP: &str = "↑/↓ select · r re-run · a re-run all · enter output · q quit";
+/// How long to wait for a key before looking for finished days again.
+const TICK: Duration = Duration::from_millis(100);
+
+#[derive(Debug)]
+enum Status {
+    Pending,
+    Running,
+    Done(Report),
+}
+
+/// State of the dashboard: the days, what became of them, and what is shown.
+struct App {
+    year: u16,
+    days: Vec<Day>,
+    statuses: Vec<Status>,
+    table: TableState,
+    show_output: bool,
+    quit: bool,
+}
+
+impl App {
+    fn new(year: u16, days: &[Day]) -> Self {
+        Self {
+            year,
+            days: days.to_vec(),
+            statuses: days.iter().map(|_| Status::Pending).collect(),
+            table: TableState::default().with_selected(Some(0)),
+            show_output: false,
+            quit: false,
+        }
+    }
+
+    fn selected(&self) -> usize {
+        self.table.selected().unwrap_or_default()
+    }
+
+    /// Marks the days at `indices` as running, returning those to solve: a day already running
+    /// is not solved twice.
+    fn start(&mut self, indices: impl IntoIterator<Item = usize>) -> Vec<Day> {
+        let mut started = Vec::new();
+        for i in indices {
+            if !matches!(self.statuses[i], Status::Running) {
+                self.statuses[i] = Status::Running;
+                started.push(self.days[i]);
+            }
+        }
+        started
+    }
+
+    fn start_all(&mut self) -> Vec<Day> {
+        self.start(0..self.days.len())
+    }
+
+    /// Handles a key press, returning the days to solve.
+    fn on_key(&mut self, key: KeyCode) -> Vec<Day> {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.table.select(Some(self.selected().saturating_sub(1)))
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.table.select(Some(
+                (self.selected() + 1).min(self.days.len().saturating_sub(1)),
+            )),
+            KeyCode::Enter => self.show_output = !self.show_output,
+            KeyCode::Char('r') => return self.start([self.selected()]),
+            KeyCode::Char('a') => return self.start_all(),
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn on_report(&mut self, report: Report) {
+        if let Some(i) = self.days.iter().position(|day| day.number == report.day) {
+            self.statuses[i] = Status::Done(report);
+        }
+    }
+
+    fn row(&self, i: usize) -> Row<'static> {
+        let cell = |answer: &Option<Answer>| match answer {
+            Some(answer) if answer.is_multiline() => SEE_OUTPUT.to_owned(),
+            Some(answer) => answer.to_string(),
+            None => MISSING.to_owned(),
+        };
+        let day = self.days[i].number.to_string();
+        let cells = match &self.statuses[i] {
+            Status::Pending => [day, "pending".to_owned()].to_vec(),
+            Status::Running => [day, "running".to_owned()].to_vec(),
+            Status::Done(Report {
+                outcome: Ok((answers, timings)),
+                ..
+            }) => [
+                day,
+                "ok".to_owned(),
+                cell(&answers.part1),
+                cell(&answers.part2),
+                format!("{:.2?}", timings.parse),
+                format!("{:.2?}", timings.solve),
+            ]
+            .to_vec(),
+            Status::Done(Report {
+                outcome: Err(_), ..
+            }) => [day, "failed".to_owned()].to_vec(),
+        };
+        Row::new(cells.into_iter().map(Cell::from))
+    }
+
+    /// Full output of the selected day: its answers, with the CRT screen of day 10 and the like,
+    /// or what went wrong.
+    fn output(&self) -> String {
+        match &self.statuses[self.selected()] {
+            Status::Pending => "Not solved yet".to_owned(),
+            Status::Running => "Solving...".to_owned(),
+            Status::Done(Report {
+                outcome: Ok((answers, _)),
+                ..
+            }) => [1, 2]
+                .map(|part| {
+                    let answer = answers.get(part).map(Answer::to_string);
+                    format!("Part {part}:\n{}", answer.as_deref().unwrap_or(MISSING))
+                })
+                .join("\n\n"),
+            Status::Done(Report {
+                outcome: Err(message),
+                ..
+            }) => message.clone(),
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame) {
+        let [main, help] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [days, output] = if self.show_output {
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(main)
+        } else {
+            Layout::horizontal([Constraint::Percentage(100), Constraint::Length(0)]).areas(main)
+        };
+
+        let rows = (0..self.days.len())
+            .map(|i| self.row(i))
+            .collect::<Vec<_>>();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(3),
+                Constraint::Length(7),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ],
+        )
+        .header(Row::new(HEADERS).style(Style::new().add_modifier(Modifier::BOLD)))
+        .block(Block::bordered().title(format!("Advent of Code {}", self.year)))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, days, &mut self.table);
+
+        if self.show_output {
+            let title = format!("Day {}", self.days[self.selected()].number);
+            frame.render_widget(
+                Paragraph::new(self.output()).block(Block::bordered().title(title)),
+                output,
+            );
+        }
+        frame.render_widget(Paragraph::new(HELP), help);
+    }
+}
+
+/// Solves the days in the background, sending their reports to `sender` as they complete.
+fn launch(days: Vec<Day>, inputs: &InputManager, example: bool, sender: &Sender<Report>) {
+    for day in days {
+        let sender = sender.clone();
+        let inputs = inputs.clone();
+        rayon::spawn(move || {
+            let input = if example {
+                Ok(Input::example(&day))
+            } else {
+                inputs.load(day.number)
+            };
+            let report = match input {
+                // a panicking solver must not take the dashboard down with it
+                Ok(input) => panic::catch_unwind(AssertUnwindSafe(|| runner::run(&day, &input)))
+                    .unwrap_or_else(|payload| Report::panicked(day.number, payload.as_ref())),
+                Err(e) => Report::unreadable(day.number, &e),
+            };
+            // the dashboard may have been closed in the meantime
+            let _ = sender.send(report);
+        });
+    }
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    inputs: &InputManager,
+    example: bool,
+    sender: &Sender<Report>,
+    reports: &Receiver<Report>,
+) -> anyhow::Result<()> {
+    while !app.quit {
+        for report in reports.try_iter() {
+            app.on_report(report);
+        }
+        terminal.draw(|frame| app.render(frame))?;
+        if event::poll(TICK)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    launch(app.on_key(key.code), inputs, example, sender);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Shows a dashboard of the days, solving them on their real inputs or on the examples of
+/// the puzzle statements, until the user quits.
+pub fn run(year: u16, days: &[Day], inputs: InputManager, example: bool) -> anyhow::Result<()> {
+    let (sender, reports) = mpsc::channel();
+    let mut app = App::new(year, days);
+    launch(app.start_all(), &inputs, example, &sender);
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app, &inputs, example, &sender, &reports);
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::runner::{run, Answers, Input};
+    use crate::year2022::DAYS;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use spectral::prelude::*;
+
+    fn crt() -> Report {
+        Report {
+            day: 10,
+            diagnosis: None,
+            outcome: Ok((
+                Answers::from((13140, Answer::grid("#..#\n.##."))),
+                Default::default(),
+            )),
+        }
+    }
+
+    fn screen(app: &mut App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|line| line.iter().map(|c| c.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn start_all() {
+        let mut app = App::new(2022, &DAYS);
+
+        assert_that!(app.start_all()).has_length(25);
+        assert_that!(app.start_all()).is_empty();
+    }
+
+    #[test]
+    fn rerun_selected() {
+        let mut app = App::new(2022, &DAYS);
+        app.on_key(KeyCode::Down);
+        app.on_key(KeyCode::Down);
+
+        let started = app.on_key(KeyCode::Char('r'));
+
+        assert_that!(started.iter().map(|day| day.number).collect::<Vec<_>>()).is_equal_to(vec![3]);
+    }
+
+    #[test]
+    fn selection_stays_in_table() {
+        let mut app = App::new(2022, &DAYS[..2]);
+        app.on_key(KeyCode::Up);
+        assert_that!(app.selected()).is_equal_to(0);
+
+        (0..5).for_each(|_| {
+            app.on_key(KeyCode::Down);
+        });
+        assert_that!(app.selected()).is_equal_to(1);
+    }
+
+    #[test]
+    fn quit() {
+        let mut app = App::new(2022, &DAYS);
+        app.on_key(KeyCode::Char('q'));
+
+        assert_that!(app.quit).is_true();
+    }
+
+    #[test]
+    fn rendered_output() {
+        let mut app = App::new(2022, &DAYS[9..10]);
+        app.start_all();
+        app.on_report(crt());
+
+        let table = screen(&mut app);
+        assert_that!(table).contains("13140");
+        assert_that!(table).contains(SEE_OUTPUT);
+
+        app.on_key(KeyCode::Enter);
+        let output = screen(&mut app);
+        assert_that!(output).contains("#..#");
+        assert_that!(output).contains(".##.");
+    }
+
+    #[test]
+    fn failure() {
+        let mut app = App::new(2022, &DAYS[1..2]);
+        app.on_report(run(&DAYS[1], &Input::from("A Y\nA W")));
+
+        // plain, without the styling of the graphical report
+        assert_that!(app.output()).starts_with("bad input at line 2, column 1:");
+    }
+}
@@ -0,0 +1,16 @@
+use crate::answer::Part;
+use crate::diagnostics;
+use wasm_bindgen::prelude::*;
+
+/// Solves a part (1 or 2) of a day of the default year on the given input, for use from
+/// JavaScript.
+///
+/// Fails with a description of the problem when the day or part does not exist or the input
+/// cannot be solved.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, JsError> {
+    let part = Part::try_from(part).map_err(|part| JsError::new(&format!("no part {part}")))?;
+    crate::solve(day, part, input)
+        .map(|answer| answer.to_string())
+        .map_err(|e| JsError::new(&diagnostics::describe(&e)))
+}
@@ -0,0 +1,243 @@
+use crate::diagnostics::BadInput;
+use crate::error::{PuzzleError, Result};
+use std::io::BufRead;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+const TOP_ELVES: usize = 3;
+
+/// Calories carried by each elf.
+pub type Loads = Vec<u32>;
+
+/// The load of each elf, blank lines being skipped when they do not end the items of an elf.
+pub fn parse(data: &str) -> Result<Loads> {
+    let mut loads = vec![];
+    fold_loads(data.as_bytes(), |load| loads.push(load))?;
+    Ok(loads)
+}
+
+/// A load of calories too heavy to be counted, `elf` counting the elves from 1.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("elf {elf}: the load of calories overflows")]
+    Overflow { elf: usize },
+    #[error("the loads of the most loaded elves overflow together")]
+    TotalOverflow,
+}
+
+impl PuzzleError for Error {}
+
+/// The heaviest loads seen so far, from the heaviest down, up to a number of elves.
+#[derive(Debug)]
+struct Podium {
+    loads: Vec<u32>,
+    size: usize,
+}
+
+impl Podium {
+    fn new(size: usize) -> Self {
+        Self {
+            // one slot more than the podium for the insert before `truncate`, capped so that a huge
+            // podium does not preallocate for elves that may not exist
+            loads: Vec::with_capacity(size.min(TOP_ELVES) + 1),
+            size,
+        }
+    }
+
+    fn push(&mut self, load: u32) {
+        let rank = self.loads.partition_point(|&l| l >= load);
+        if rank < self.size {
+            self.loads.insert(rank, load);
+            self.loads.truncate(self.size);
+        }
+    }
+
+    fn total(&self) -> Result<u32, Error> {
+        self.loads.iter().try_fold(0u32, |total, &load| {
+            total.checked_add(load).ok_or(Error::TotalOverflow)
+        })
+    }
+
+    /// Calories carried by the most loaded elf, and by the elves on the podium together.
+    fn answers(&self) -> Result<(u32, u32), Error> {
+        Ok((
+            self.loads.first().copied().unwrap_or_default(),
+            self.total()?,
+        ))
+    }
+}
+
+impl Default for Podium {
+    fn default() -> Self {
+        Self::new(TOP_ELVES)
+    }
+}
+
+/// Calories carried by the most loaded elf, and by the three most loaded elves together.
+pub fn solve(loads: &Loads) -> Result<(u32, u32)> {
+    let mut podium = Podium::default();
+    for &load in loads {
+        podium.push(load);
+    }
+    Ok(podium.answers()?)
+}
+
+/// Reads the inventories line by line and hands over the load of each elf in turn: only the load
+/// of the current elf is kept, however long the list. Blank lines that do not end the items of an
+/// elf are skipped.
+fn fold_loads(reader: impl BufRead, mut each: impl FnMut(u32)) -> Result<()> {
+    let mut elf = 1;
+    // the load of the current elf, if it has items
+    let mut load: Option<u32> = None;
+    for (number, line) in (1..).zip(reader.lines()) {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            if let Some(load) = load.take() {
+                each(load);
+                elf += 1;
+            }
+        } else {
+            let calories = line
+                .parse::<u32>()
+                .map_err(|e| BadInput::on_line(number, line, e))?;
+            let total = load.unwrap_or_default().checked_add(calories);
+            load = Some(total.ok_or(Error::Overflow { elf })?);
+        }
+    }
+    if let Some(load) = load {
+        each(load);
+    }
+    Ok(())
+}
+
+/// The podium of the `n` most loaded elves, reading the inventories line by line.
+fn podium(reader: impl BufRead, n: usize) -> Result<Podium> {
+    let mut podium = Podium::new(n);
+    fold_loads(reader, |load| podium.push(load))?;
+    Ok(podium)
+}
+
+/// Same as [`solve`], reading the inventories line by line.
+pub fn calories_carried(reader: impl BufRead) -> Result<(u32, u32)> {
+    Ok(podium(reader, TOP_ELVES)?.answers()?)
+}
+
+/// Calories carried by each of the `n` most loaded elves, from the heaviest down (fewer if there
+/// are not as many elves), and by all of them together.
+pub fn top_n_calories(reader: impl BufRead, n: usize) -> Result<(Vec<u32>, u32)> {
+    let podium = podium(reader, n)?;
+    let total = podium.total()?;
+    Ok((podium.loads, total))
+}
+
+pub fn calories_carried_from_str(data: &str) -> Result<(u32, u32)> {
+    calories_carried(data.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::error::AocError;
+    use spectral::prelude::*;
+
+    #[test]
+    fn example_most_calories() {
+        assert_that!(calories_carried_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((24000, 45000));
+    }
+
+    #[test]
+    fn example_parsed_first() {
+        assert_that!(parse(EXAMPLE).and_then(|loads| solve(&loads)))
+            .is_ok()
+            .is_equal_to((24000, 45000));
+    }
+
+    #[test]
+    fn podium_keeps_heaviest() {
+        let mut podium = Podium::default();
+        for load in [5, 1, 9, 5, 7, 2] {
+            podium.push(load);
+        }
+
+        assert_that!(podium.loads).is_equal_to(vec![9, 7, 5]);
+    }
+
+    #[test]
+    fn top_elves() {
+        assert_that!(top_n_calories(EXAMPLE.as_bytes(), 2))
+            .is_ok()
+            .is_equal_to((vec![24000, 11000], 35000));
+        assert_that!(top_n_calories(EXAMPLE.as_bytes(), 10))
+            .is_ok()
+            .map(|(loads, _)| loads)
+            .is_equal_to(vec![24000, 11000, 10000, 6000, 4000]);
+        assert_that!(top_n_calories(EXAMPLE.as_bytes(), 0))
+            .is_ok()
+            .is_equal_to((vec![], 0));
+    }
+
+    #[test]
+    fn trailing_blank_lines() {
+        let data = "100\n\n200\n\n\n";
+
+        assert_that!(top_n_calories(data.as_bytes(), 3))
+            .is_ok()
+            .is_equal_to((vec![200, 100], 300));
+        assert_that!(parse(data))
+            .is_ok()
+            .is_equal_to(vec![100, 200]);
+    }
+
+    #[test]
+    fn overflowing_load() {
+        let data = format!("1\n\n{}\n1", u32::MAX);
+
+        assert_that!(calories_carried_from_str(&data))
+            .is_err()
+            .matches(|e| e.to_string() == "elf 2: the load of calories overflows");
+        assert_that!(parse(&data).and_then(|loads| solve(&loads)))
+            .is_err()
+            .matches(|e| e.to_string() == "elf 2: the load of calories overflows");
+    }
+
+    #[test]
+    fn overflowing_podium() {
+        let data = format!("{}\n\n1", u32::MAX);
+
+        assert_that!(calories_carried_from_str(&data))
+            .is_err()
+            .matches(|e| e.to_string() == "the loads of the most loaded elves overflow together");
+    }
+
+    #[test]
+    fn huge_podium() {
+        assert_that!(top_n_calories(EXAMPLE.as_bytes(), usize::MAX))
+            .is_ok()
+            .map(|(_, total)| total)
+            .is_equal_to(55000);
+    }
+
+    #[test]
+    fn bad_line() {
+        let data = "100\n\n2OO\n";
+
+        assert_that!(calories_carried_from_str(data))
+            .is_err()
+            .matches(|e| matches!(e, AocError::Parse(bad) if bad.position() == (3, 1)));
+        assert_that!(parse(data))
+            .is_err()
+            .matches(|e| matches!(e, AocError::Parse(bad) if bad.position() == (3, 1)));
+    }
+
+    #[test]
+    fn fewer_elves_than_podium() {
+        assert_that!(calories_carried_from_str("100\n200"))
+            .is_ok()
+            .is_equal_to((300, 300));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::diagnostics::BadInput;
+use crate::error::Result;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+/// Why a line of the strategy guide cannot be read.
+#[derive(Error, Debug, PartialEq)]
+pub enum ParseError {
+    #[error("{0:?} is not a shape, expected A, B or C")]
+    Shape(String),
+    #[error("{0:?} is not a response, expected X, Y or Z")]
+    Response(String),
+    #[error("expected a shape and a response, found {0} columns")]
+    Columns(usize),
+}
+
+impl FromStr for Shape {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Shape, Self::Err> {
+        match s {
+            "A" | "a" => Ok(Shape::Rock),
+            "B" | "b" => Ok(Shape::Paper),
+            "C" | "c" => Ok(Shape::Scissors),
+            _ => Err(ParseError::Shape(s.to_owned())),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum GameResult {
+    Loss,
+    Draw,
+    Won,
+}
+
+#[derive(Debug, PartialEq)]
+enum Should {
+    Lose,
+    Draw,
+    Win,
+}
+
+/// The second column of the guide, whose meaning depends on the [`Strategy`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Response {
+    X,
+    Y,
+    Z,
+}
+
+impl FromStr for Response {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Response, Self::Err> {
+        match s {
+            "X" | "x" => Ok(Response::X),
+            "Y" | "y" => Ok(Response::Y),
+            "Z" | "z" => Ok(Response::Z),
+            _ => Err(ParseError::Response(s.to_owned())),
+        }
+    }
+}
+
+/// How to read the second column of the guide.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Strategy {
+    /// As the shape to play (part 1).
+    AsShape,
+    /// As the expected outcome of the game (part 2).
+    AsOutcome,
+}
+
+impl Strategy {
+    /// The game played following the round of the guide.
+    fn game(self, (opponent, response): Round) -> Game {
+        let me = match self {
+            Strategy::AsShape => match response {
+                Response::X => Shape::Rock,
+                Response::Y => Shape::Paper,
+                Response::Z => Shape::Scissors,
+            },
+            Strategy::AsOutcome => {
+                let should = match response {
+                    Response::X => Should::Lose,
+                    Response::Y => Should::Draw,
+                    Response::Z => Should::Win,
+                };
+                what_to_play(&(opponent, should))
+            }
+        };
+        (opponent, me)
+    }
+}
+
+type Hint = (Shape, Should);
+
+pub type Game = (Shape, Shape);
+
+/// A line of the strategy guide: what the opponent plays, and the response.
+pub type Round = (Shape, Response);
+
+fn what_to_play(hint: &Hint) -> Shape {
+    match hint {
+        (Shape::Rock, Should::Lose) => Shape::Scissors,
+        (Shape::Rock, Should::Win) => Shape::Paper,
+        (Shape::Paper, Should::Lose) => Shape::Rock,
+        (Shape::Paper, Should::Win) => Shape::Scissors,
+        (Shape::Scissors, Should::Lose) => Shape::Paper,
+        (Shape::Scissors, Should::Win) => Shape::Rock,
+        (p, Should::Draw) => *p,
+    }
+}
+
+/// A round like `A Y`, in any case and with any spacing.
+fn parse_round(s: &str) -> Result<Round, ParseError> {
+    let parts: Vec<&str> = s.split_whitespace().collect();
+    if parts.len() != 2 {
+        Err(ParseError::Columns(parts.len()))
+    } else {
+        Ok((parts[0].parse::<Shape>()?, parts[1].parse::<Response>()?))
+    }
+}
+
+fn run_game(game: &Game) -> GameResult {
+    match game {
+        (Shape::Rock, Shape::Paper) => GameResult::Won,
+        (Shape::Rock, Shape::Scissors) => GameResult::Loss,
+        (Shape::Paper, Shape::Rock) => GameResult::Loss,
+        (Shape::Paper, Shape::Scissors) => GameResult::Won,
+        (Shape::Scissors, Shape::Rock) => GameResult::Won,
+        (Shape::Scissors, Shape::Paper) => GameResult::Loss,
+        _ => GameResult::Draw,
+    }
+}
+
+fn shape_score(game: &Game) -> u32 {
+    match game {
+        (_, Shape::Rock) => 1,
+        (_, Shape::Paper) => 2,
+        (_, Shape::Scissors) => 3,
+    }
+}
+
+fn score(game: &Game) -> u32 {
+    let shape_score = shape_score(game);
+    let outcome_score = match run_game(game) {
+        GameResult::Won => 6,
+        GameResult::Draw => 3,
+        GameResult::Loss => 0,
+    };
+    shape_score + outcome_score
+}
+
+pub type Guide = Vec<Round>;
+
+/// The rounds of the guide, blank lines being skipped.
+pub fn parse(data: &str) -> Result<Guide> {
+    Ok(data
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_round(line).map_err(|e| BadInput::at(data, line, e)))
+        .collect::<Result<_, BadInput>>()?)
+}
+
+/// The total score of following the guide with the given strategy.
+pub fn total_score(guide: &[Round], strategy: Strategy) -> u32 {
+    guide
+        .iter()
+        .map(|round| score(&strategy.game(*round)))
+        .sum()
+}
+
+/// The total score of following the guide of the puzzle input with the given strategy.
+pub fn rock_paper_scissors(data: &str, strategy: Strategy) -> Result<u32> {
+    Ok(total_score(&parse(data)?, strategy))
+}
+
+pub fn solve(guide: &Guide) -> Result<(u32, u32)> {
+    Ok((
+        total_score(guide, Strategy::AsShape),
+        total_score(guide, Strategy::AsOutcome),
+    ))
+}
+
+pub fn rock_paper_scissors_from_str(data: &str) -> Result<(u32, u32)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::diagnostics;
+    use crate::error::AocError;
+    use spectral::prelude::*;
+
+    #[test]
+    fn example_total_scores() {
+        assert_that!(rock_paper_scissors_from_str("A Y\nB X\nC Z\n"))
+            .is_ok()
+            .is_equal_to((15, 12));
+    }
+
+    #[test]
+    fn invalid_game() {
+        assert_that!(parse_round("A W"))
+            .is_err()
+            .is_equal_to(ParseError::Response("W".to_owned()));
+        assert_that!(parse_round("D X"))
+            .is_err()
+            .is_equal_to(ParseError::Shape("D".to_owned()));
+        assert_that!(parse_round("A X Y"))
+            .is_err()
+            .is_equal_to(ParseError::Columns(3));
+    }
+
+    #[test]
+    fn tolerant_guide() {
+        assert_that!(rock_paper_scissors_from_str("a y\n  B\tX  \n\nc   z\n"))
+            .is_ok()
+            .is_equal_to((15, 12));
+    }
+
+    #[test]
+    fn strategies() {
+        assert_that!(Strategy::AsShape.game((Shape::Rock, Response::Y)))
+            .is_equal_to((Shape::Rock, Shape::Paper));
+        assert_that!(Strategy::AsOutcome.game((Shape::Rock, Response::Y)))
+            .is_equal_to((Shape::Rock, Shape::Rock));
+        assert_that!(rock_paper_scissors(EXAMPLE, Strategy::AsShape))
+            .is_ok()
+            .is_equal_to(15);
+        assert_that!(rock_paper_scissors(EXAMPLE, Strategy::AsOutcome))
+            .is_ok()
+            .is_equal_to(12);
+    }
+
+    #[test]
+    fn invalid_guide() {
+        let guide = parse("A Y\nA W\n");
+
+        assert!(matches!(&guide, Err(AocError::Parse(e)) if e.offset() == 4));
+        assert_that!(diagnostics::describe(&guide.unwrap_err()))
+            .contains(r#"at line 2, column 1: "W" is not a response, expected X, Y or Z"#);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(rock_paper_scissors_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((15, 12));
+    }
+}
@@ -0,0 +1,275 @@
+use crate::error::{PuzzleError, Result};
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+pub fn priority(c: &char) -> u32 {
+    let c = *c as u32;
+    let p = c - 'A' as u32;
+    if p > 27 {
+        c - 'a' as u32 + 1
+    } else {
+        p + 27
+    }
+}
+
+/// Set of items, as a bit per priority.
+type Items = u64;
+
+fn items(s: &str) -> Items {
+    s.chars().fold(0, |items, c| items | 1 << priority(&c))
+}
+
+/// Priority of the item shared by some sets, 0 if there is none.
+fn shared_priority(sets: impl IntoIterator<Item = Items>) -> u32 {
+    let shared = sets
+        .into_iter()
+        .fold(Items::MAX, |shared, items| shared & items);
+    match shared {
+        0 => 0,
+        shared => shared.trailing_zeros(),
+    }
+}
+
+/// A rucksack, or a group of rucksacks, that makes no sense, `line` counting the lines of the
+/// input from 1.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("line {line} ({rucksack}): {item:?} is not an item")]
+    NotAnItem {
+        line: usize,
+        rucksack: String,
+        item: char,
+    },
+    #[error("line {line} ({rucksack}): the compartments cannot hold as many items")]
+    Uneven { line: usize, rucksack: String },
+    #[error("line {line} ({rucksack}): no item is in both compartments")]
+    NothingMisplaced { line: usize, rucksack: String },
+    #[error("lines {line} to {}: the elves of the group share no badge", line + 2)]
+    NoBadge { line: usize },
+    #[error("line {line}: the last group has {elves} of its 3 elves only")]
+    IncompleteGroup { line: usize, elves: usize },
+}
+
+impl Error {
+    /// The line of the rucksack, or of the first rucksack of the group.
+    pub fn line(&self) -> usize {
+        match self {
+            Error::NotAnItem { line, .. }
+            | Error::Uneven { line, .. }
+            | Error::NothingMisplaced { line, .. }
+            | Error::NoBadge { line }
+            | Error::IncompleteGroup { line, .. } => *line,
+        }
+    }
+}
+
+impl PuzzleError for Error {}
+
+/// Checks that the rucksack of the line `line` holds nothing but items.
+fn check(line: usize, rucksack: &str) -> Result<(), Error> {
+    match rucksack.chars().find(|c| !c.is_ascii_alphabetic()) {
+        Some(item) => Err(Error::NotAnItem {
+            line,
+            rucksack: rucksack.to_owned(),
+            item,
+        }),
+        None => Ok(()),
+    }
+}
+
+fn misplaced_item_priority(line: usize, rucksack: &str) -> Result<u32, Error> {
+    check(line, rucksack)?;
+    if !rucksack.len().is_multiple_of(2) {
+        return Err(Error::Uneven {
+            line,
+            rucksack: rucksack.to_owned(),
+        });
+    }
+    let (part1, part2) = rucksack.split_at(rucksack.len() / 2);
+    match shared_priority([items(part1), items(part2)]) {
+        0 => Err(Error::NothingMisplaced {
+            line,
+            rucksack: rucksack.to_owned(),
+        }),
+        priority => Ok(priority),
+    }
+}
+
+/// Priority of the badge of the group starting at the line `line`.
+fn badge_priority(line: usize, group: &[String]) -> Result<u32, Error> {
+    for (i, rucksack) in group.iter().enumerate() {
+        check(line + i, rucksack)?;
+    }
+    if group.len() < 3 {
+        return Err(Error::IncompleteGroup {
+            line,
+            elves: group.len(),
+        });
+    }
+    match shared_priority(group.iter().map(|s| items(s))) {
+        0 => Err(Error::NoBadge { line }),
+        priority => Ok(priority),
+    }
+}
+
+fn misplaced_items_priorities(
+    rucksacks: &[String],
+) -> impl Iterator<Item = Result<u32, Error>> + '_ {
+    (1..)
+        .zip(rucksacks)
+        .map(|(line, rucksack)| misplaced_item_priority(line, rucksack))
+}
+
+fn badges_priorities(rucksacks: &[String]) -> impl Iterator<Item = Result<u32, Error>> + '_ {
+    (1..)
+        .step_by(3)
+        .zip(rucksacks.chunks(3))
+        .map(|(line, group)| badge_priority(line, group))
+}
+
+pub fn parse(data: &str) -> Result<Vec<String>> {
+    Ok(data.lines().map(str::to_owned).collect())
+}
+
+pub fn solve(rucksacks: &[String]) -> Result<(u32, u32)> {
+    Ok((
+        misplaced_items_priorities(rucksacks).sum::<Result<_, _>>()?,
+        badges_priorities(rucksacks).sum::<Result<_, _>>()?,
+    ))
+}
+
+/// Sums the priorities of the rucksacks and groups that make sense, skipping the others: their
+/// problems are returned along, in the order of the lines.
+pub fn solve_lenient(rucksacks: &[String]) -> ((u32, u32), Vec<Error>) {
+    let mut problems = vec![];
+    let mut sum = |priorities: &mut dyn Iterator<Item = Result<u32, Error>>| {
+        priorities.fold(0, |sum, priority| match priority {
+            Ok(priority) => sum + priority,
+            Err(e) => {
+                // a rucksack that is not made of items spoils its group as well
+                if !problems.contains(&e) {
+                    problems.push(e);
+                }
+                sum
+            }
+        })
+    };
+    let answers = (
+        sum(&mut misplaced_items_priorities(rucksacks)),
+        sum(&mut badges_priorities(rucksacks)),
+    );
+    problems.sort_by_key(Error::line);
+    (answers, problems)
+}
+
+pub fn priorities_from_str(data: &str) -> Result<u32> {
+    Ok(misplaced_items_priorities(&parse(data)?).sum::<Result<_, _>>()?)
+}
+
+pub fn priorities_2_from_str(data: &str) -> Result<u32> {
+    Ok(badges_priorities(&parse(data)?).sum::<Result<_, _>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn example_answers() {
+        assert_that!(parse(EXAMPLE).and_then(|rucksacks| solve(&rucksacks)))
+            .is_ok()
+            .is_equal_to((157, 70));
+    }
+
+    #[test]
+    fn priorities() {
+        assert_that!(['a', 'z', 'A', 'Z'].map(|c| priority(&c))).is_equal_to([1, 26, 27, 52]);
+    }
+
+    #[test]
+    fn shared_item() {
+        let rucksack = [items("vJrwpWtwJgWr"), items("hcsFMMfFFhFp")];
+        assert_that!(shared_priority(rucksack)).is_equal_to(16);
+        assert_that!(shared_priority([items("ab"), items("cd")])).is_equal_to(0);
+    }
+
+    fn rucksacks(data: &str) -> Vec<String> {
+        parse(data).unwrap()
+    }
+
+    #[test]
+    fn not_an_item() {
+        let rucksacks = rucksacks("vJrwpWtwJgWrhcsFMMfFFhFp\nab1b");
+
+        assert_that!(solve(&rucksacks).map_err(|e| e.to_string()))
+            .is_err()
+            .is_equal_to("line 2 (ab1b): '1' is not an item".to_owned());
+    }
+
+    #[test]
+    fn nothing_misplaced() {
+        let rucksacks = rucksacks("abcd");
+
+        assert_that!(misplaced_items_priorities(&rucksacks).collect::<Vec<_>>()).is_equal_to(vec![
+            Err(Error::NothingMisplaced {
+                line: 1,
+                rucksack: "abcd".to_owned(),
+            }),
+        ]);
+    }
+
+    #[test]
+    fn uneven_compartments() {
+        let rucksacks = rucksacks("aba");
+
+        assert_that!(misplaced_items_priorities(&rucksacks).collect::<Vec<_>>()).is_equal_to(vec![
+            Err(Error::Uneven {
+                line: 1,
+                rucksack: "aba".to_owned(),
+            }),
+        ]);
+    }
+
+    #[test]
+    fn lenient() {
+        let mut data = EXAMPLE.lines().collect::<Vec<_>>();
+        // spoils the first rucksack, and the badge of the first group
+        data[0] = "vJrwp-twJgWrhcsFMMfFFhFp";
+        data.push("abcd");
+        let rucksacks = rucksacks(&data.join("\n"));
+
+        let (answers, problems) = solve_lenient(&rucksacks);
+
+        assert_that!(answers).is_equal_to((157 - 16, 70 - 18));
+        assert_that!(problems).is_equal_to(vec![
+            Error::NotAnItem {
+                line: 1,
+                rucksack: "vJrwp-twJgWrhcsFMMfFFhFp".to_owned(),
+                item: '-',
+            },
+            Error::NothingMisplaced {
+                line: 7,
+                rucksack: "abcd".to_owned(),
+            },
+            Error::IncompleteGroup { line: 7, elves: 1 },
+        ]);
+    }
+
+    #[test]
+    fn incomplete_group() {
+        let mut data = EXAMPLE.lines().collect::<Vec<_>>();
+        data.truncate(5);
+        let rucksacks = rucksacks(&data.join("\n"));
+
+        assert_that!(badges_priorities(&rucksacks).collect::<Vec<_>>()).is_equal_to(vec![
+            Ok(18),
+            Err(Error::IncompleteGroup { line: 4, elves: 2 }),
+        ]);
+        assert_that!(solve(&rucksacks).map_err(|e| e.to_string()))
+            .is_err()
+            .is_equal_to("line 4: the last group has 2 of its 3 elves only".to_owned());
+    }
+}
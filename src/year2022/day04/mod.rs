@@ -0,0 +1,143 @@
+use crate::diagnostics::BadInput;
+use crate::error::Result;
+use crate::ranges;
+use itertools::process_results;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+/// Why a line of the assignments cannot be read.
+#[derive(Error, Debug, PartialEq)]
+enum ParseError {
+    #[error("{0:?} is not a section number")]
+    NotANumber(String),
+    #[error("{0:?} is not a range of sections like 2-4")]
+    NotARange(String),
+    #[error("expected the ranges of two elves, found {0}")]
+    Pair(usize),
+}
+
+fn parse_tokens<T>(s: &str, sep: char) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+{
+    process_results(
+        s.split(sep).map(str::trim).map(|x| {
+            x.parse::<T>()
+                .map_err(|_| ParseError::NotANumber(x.to_owned()))
+        }),
+        |iter| iter.collect::<Vec<T>>(),
+    )
+}
+
+fn parse_range<T>(s: &str) -> Result<RangeInclusive<T>, ParseError>
+where
+    T: Copy + PartialOrd<T> + FromStr,
+{
+    match parse_tokens(s, '-')?[..] {
+        [start, end] => Ok(start..=end),
+        _ => Err(ParseError::NotARange(s.trim().to_owned())),
+    }
+}
+
+fn parse_line<T>(s: &str) -> Result<(RangeInclusive<T>, RangeInclusive<T>), ParseError>
+where
+    T: Copy + PartialOrd<T> + FromStr,
+{
+    let ranges = process_results(s.split(',').map(|e| parse_range::<T>(e)), |iter| {
+        iter.collect::<Vec<RangeInclusive<T>>>()
+    })?;
+    let [r0, r1] = <[_; 2]>::try_from(ranges).map_err(|ranges| ParseError::Pair(ranges.len()))?;
+    Ok((r0, r1))
+}
+
+pub type Assignments = Vec<(RangeInclusive<u32>, RangeInclusive<u32>)>;
+
+/// Counts the pairs where one range fully contains the other, and the pairs that overlap at all.
+fn count_containments_and_overlaps<'a, I>(pairs: I) -> (u32, u32)
+where
+    I: Iterator<Item = &'a (RangeInclusive<u32>, RangeInclusive<u32>)>,
+{
+    pairs.fold((0, 0), |(containments, overlaps), (r0, r1)| {
+        (
+            containments + u32::from(ranges::includes(r0, r1) || ranges::includes(r1, r0)),
+            overlaps + u32::from(ranges::overlaps(r0, r1)),
+        )
+    })
+}
+
+/// The pairs of assignments, blank lines being skipped.
+pub fn parse(data: &str) -> Result<Assignments> {
+    Ok(data
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_line::<u32>(line).map_err(|e| BadInput::at(data, line, e)))
+        .collect::<Result<_, _>>()?)
+}
+
+pub fn solve(assignments: &Assignments) -> Result<(u32, u32)> {
+    Ok(count_containments_and_overlaps(assignments.iter()))
+}
+
+pub fn ship_unload_overlaps_from_str(data: &str) -> Result<(u32, u32)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::diagnostics;
+    use crate::error::AocError;
+    use spectral::prelude::*;
+
+    #[test]
+    fn example_containments_and_overlaps() {
+        let pairs = EXAMPLE
+            .lines()
+            .map(|l| parse_line::<u32>(l).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_that!(count_containments_and_overlaps(pairs.iter())).is_equal_to((2, 4));
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(ship_unload_overlaps_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((2, 4));
+    }
+
+    #[test]
+    fn invalid_lines() {
+        assert_that!(parse_line::<u32>("2-4,6-x"))
+            .is_err()
+            .is_equal_to(ParseError::NotANumber("x".to_owned()));
+        assert_that!(parse_line::<u32>("2-4,6"))
+            .is_err()
+            .is_equal_to(ParseError::NotARange("6".to_owned()));
+        assert_that!(parse_line::<u32>("2-4,6-8,1-2"))
+            .is_err()
+            .is_equal_to(ParseError::Pair(3));
+    }
+
+    #[test]
+    fn whitespaces() {
+        assert_that!(ship_unload_overlaps_from_str(" 2-8 , 3 - 7\n\n6-6,4-6  \n"))
+            .is_ok()
+            .is_equal_to((2, 2));
+    }
+
+    #[test]
+    fn invalid_assignments() {
+        let assignments = parse("2-4,6-8\n\n2-3,4\n");
+
+        assert!(matches!(&assignments, Err(AocError::Parse(e)) if e.offset() == 9));
+        assert_that!(diagnostics::describe(&assignments.unwrap_err()))
+            .contains(r#"at line 3, column 1: "4" is not a range of sections like 2-4"#);
+    }
+}
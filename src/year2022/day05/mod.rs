@@ -0,0 +1,930 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, line_ending, satisfy, space0, space1, u16},
+    combinator::{cut, eof, map, value},
+    error::ParseError,
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    IResult,
+};
+use nom_locate::LocatedSpan;
+
+use crate::diagnostics::parse_all;
+use crate::error::{PuzzleError, Result};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+/// A crate, marked with a letter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Crate(pub char);
+
+impl Display for Crate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "[{}]", self.0)
+    }
+}
+
+/// The number of a stack, written below it.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct StackId(pub u16);
+
+impl Display for StackId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct StacksSpecification {
+    slot_lines: Vec<Vec<Option<Crate>>>,
+    stack_ids: Vec<StackId>,
+}
+
+impl StacksSpecification {
+    fn new(crates: Vec<Vec<Option<Crate>>>, stack_ids: Vec<StackId>) -> Self {
+        Self {
+            slot_lines: crates,
+            stack_ids,
+        }
+    }
+}
+
+/// A stack of crates, from the bottom up.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stack {
+    id: StackId,
+    crates: Vec<Crate>,
+}
+
+impl Stack {
+    pub fn new(id: StackId, crates: Vec<Crate>) -> Self {
+        Self { id, crates }
+    }
+
+    pub fn id(&self) -> StackId {
+        self.id
+    }
+
+    pub fn crates(&self) -> &[Crate] {
+        &self.crates
+    }
+
+    /// The crate on top of the stack, if any.
+    pub fn top(&self) -> Option<Crate> {
+        self.crates.last().copied()
+    }
+}
+
+impl Display for Stack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "{}({})\t:{}",
+            self.id,
+            self.crates.len(),
+            self.crates
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<String>()
+        )
+    }
+}
+
+/// The crane operating the rearrangement procedure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CraneModel {
+    /// Moves crates one at a time (part 1).
+    CrateMover9000,
+    /// Moves several crates at once, so that they keep their order (part 2).
+    CrateMover9001,
+}
+
+/// A move of the procedure that cannot be done, `number` counting the moves from 1.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("move {number} ({m}) uses stack {stack}, which does not exist")]
+    UnknownStack {
+        number: usize,
+        m: Move,
+        stack: StackId,
+    },
+    #[error("move {number} ({m}) takes {} crates from stack {}, which only has {height}", m.num, m.from)]
+    NotEnoughCrates {
+        number: usize,
+        m: Move,
+        height: usize,
+    },
+}
+
+impl PuzzleError for Error {}
+
+/// Moves crates between the stacks with the given crane, `index` being the position of the move
+/// in the procedure.
+fn apply(
+    stacks: &mut BTreeMap<StackId, Stack>,
+    index: usize,
+    m: &Move,
+    crane: CraneModel,
+) -> Result<(), Error> {
+    let unknown = |stack| Error::UnknownStack {
+        number: index + 1,
+        m: *m,
+        stack,
+    };
+    let height = stacks
+        .get(&m.from)
+        .ok_or_else(|| unknown(m.from))?
+        .crates
+        .len();
+    if !stacks.contains_key(&m.to) {
+        return Err(unknown(m.to));
+    }
+    let count = usize::from(m.num);
+    if count > height {
+        return Err(Error::NotEnoughCrates {
+            number: index + 1,
+            m: *m,
+            height,
+        });
+    }
+
+    let mut moved = stacks
+        .get_mut(&m.from)
+        .map(|from| from.crates.split_off(height - count))
+        .unwrap_or_default();
+    if crane == CraneModel::CrateMover9000 {
+        moved.reverse();
+    }
+    if let Some(to) = stacks.get_mut(&m.to) {
+        to.crates.extend(moved);
+    }
+    Ok(())
+}
+
+fn by_id(stacks: &[Stack]) -> BTreeMap<StackId, Stack> {
+    stacks.iter().map(|e| (e.id, e.clone())).collect()
+}
+
+/// The stacks once all the moves are done with the given crane, ordered by id. Fails on the
+/// first move that cannot be done.
+pub fn rearrange(stacks: &[Stack], moves: &[Move], crane: CraneModel) -> Result<Vec<Stack>, Error> {
+    let mut stacks = by_id(stacks);
+    for (i, m) in moves.iter().enumerate() {
+        apply(&mut stacks, i, m, crane)?;
+    }
+    Ok(stacks.into_values().collect())
+}
+
+/// Each move of the procedure with the stacks it leaves, crates being moved with the given
+/// crane, up to the first move that cannot be done.
+pub fn rearrangement_steps(
+    (stacks, moves): &Procedure,
+    crane: CraneModel,
+) -> impl Iterator<Item = Result<(&Move, Vec<Stack>), Error>> {
+    let mut stacks = by_id(stacks);
+    let mut failed = false;
+    moves.iter().enumerate().map_while(move |(i, m)| {
+        if failed {
+            return None;
+        }
+        let step =
+            apply(&mut stacks, i, m, crane).map(|()| (m, stacks.values().cloned().collect()));
+        failed = step.is_err();
+        Some(step)
+    })
+}
+
+/// Draws the stacks the way the puzzle input does, crates on top of their stack id.
+pub fn draw_stacks(stacks: &[Stack]) -> String {
+    let height = stacks.iter().map(|s| s.crates.len()).max().unwrap_or(0);
+    let mut drawing = (0..height)
+        .rev()
+        .map(|level| {
+            stacks
+                .iter()
+                .map(|s| {
+                    s.crates
+                        .get(level)
+                        .map_or("   ".to_owned(), Crate::to_string)
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>();
+    drawing.push(
+        stacks
+            .iter()
+            .map(|s| format!("{:^3}", s.id.0))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    drawing.join("\n")
+}
+
+/// A step of the rearrangement procedure: `num` crates moved from a stack to another.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Move {
+    num: u16,
+    from: StackId,
+    to: StackId,
+}
+
+impl Move {
+    pub fn new(num: u16, from: StackId, to: StackId) -> Self {
+        Move { num, from, to }
+    }
+
+    pub fn count(&self) -> u16 {
+        self.num
+    }
+
+    pub fn from(&self) -> StackId {
+        self.from
+    }
+
+    pub fn to(&self) -> StackId {
+        self.to
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{} --{}--> {}", self.from, self.num, self.to)
+    }
+}
+
+fn parse_stack_id<'a, E>(i: Span<'a>) -> IResult<Span<'a>, StackId, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    map(u16, StackId)(i)
+}
+
+fn parse_crate<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Option<Crate>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    alt((
+        map(
+            delimited(char('['), satisfy(|c| c.is_ascii_uppercase()), char(']')),
+            |id| Some(Crate(id)),
+        ),
+        value(None, tag("   ")),
+    ))(i)
+}
+
+fn parse_stack_def_line<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Vec<Option<Crate>>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    // past a separator, a crate must follow: report what is wrong with it rather than the
+    // separator being left over
+    map(
+        pair(parse_crate, many0(preceded(char(' '), cut(parse_crate)))),
+        |(first, others)| [vec![first], others].concat(),
+    )(i)
+}
+
+fn parse_stack_def_line_nl<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Vec<Option<Crate>>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    terminated(parse_stack_def_line, line_ending)(i)
+}
+
+fn parse_stack_id_line<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Vec<StackId>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    // ids of several digits widen the columns: only the order of the ids matters
+    delimited(space0, separated_list1(space1, parse_stack_id), space0)(i)
+}
+
+fn parse_stack_id_line_nl<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Vec<StackId>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    terminated(parse_stack_id_line, line_ending)(i)
+}
+
+fn parse_move<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Move, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    map(
+        preceded(
+            tag("move "),
+            cut(tuple((
+                u16,
+                tag(" from "),
+                parse_stack_id,
+                tag(" to "),
+                parse_stack_id,
+            ))),
+        ),
+        |(num, _, from, _, to)| Move::new(num, from, to),
+    )(i)
+}
+
+fn parse_move_nl<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Move, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    terminated(parse_move, alt((line_ending, eof)))(i)
+}
+
+fn parse_moves<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Vec<Move>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    many1(parse_move_nl)(i)
+}
+
+fn empty_line<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Span<'a>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    line_ending(i)
+}
+
+fn parse_stacks_specifications<'a, E>(i: Span<'a>) -> IResult<Span<'a>, StacksSpecification, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    map(
+        pair(many1(parse_stack_def_line_nl), parse_stack_id_line_nl),
+        |(stack_def_lines, stack_ids)| StacksSpecification::new(stack_def_lines, stack_ids),
+    )(i)
+}
+
+fn create_stacks(stacks_specs: StacksSpecification) -> Vec<Stack> {
+    let lines = stacks_specs
+        .slot_lines
+        .iter()
+        .rev()
+        .collect::<Vec<&Vec<Option<Crate>>>>();
+    let ids = stacks_specs.stack_ids;
+    let stack_number = ids.len();
+    let mut stacks = Vec::with_capacity(stack_number);
+    for (i, id) in ids.iter().enumerate() {
+        let mut stack = vec![];
+        // trailing empty slots may have been trimmed from a line
+        for c in lines.iter().filter_map(|l| l.get(i).cloned().flatten()) {
+            stack.push(c);
+        }
+        stacks.push(Stack::new(*id, stack));
+    }
+    stacks
+}
+
+fn code(stacks: &[Stack]) -> String {
+    stacks
+        .iter()
+        .map(|s| s.top().map(|e| e.0).unwrap_or(' '))
+        .collect::<String>()
+}
+
+/// The starting stacks of crates, and the rearrangement procedure.
+pub type Procedure = (Vec<Stack>, Vec<Move>);
+
+/// The drawing of the stacks, an empty line, then the moves, one per line.
+pub fn procedure<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Procedure, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    map(
+        tuple((parse_stacks_specifications, empty_line, parse_moves)),
+        |(stacks_specs, _, moves)| (create_stacks(stacks_specs), moves),
+    )(i)
+}
+
+pub fn parse(data: &str) -> Result<Procedure> {
+    Ok(parse_all(data, procedure)?)
+}
+
+/// Crates on top of the stacks with the CrateMover 9000, then with the CrateMover 9001.
+pub fn solve((stacks, moves): &Procedure) -> Result<(String, String)> {
+    Ok((
+        code(&rearrange(stacks, moves, CraneModel::CrateMover9000)?),
+        code(&rearrange(stacks, moves, CraneModel::CrateMover9001)?),
+    ))
+}
+
+pub fn top_crate_of_stacks_from_str(data: &str) -> Result<(String, String)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::error::AocError;
+    use insta::assert_snapshot;
+    use spectral::prelude::*;
+
+    /// What a parser made of its input, with what is left of it.
+    fn fragments<O>(result: IResult<Span<'_>, O, ()>) -> IResult<&str, O, ()> {
+        result.map(|(rest, o)| (*rest.fragment(), o))
+    }
+
+    fn error_offset(data: &str) -> Option<usize> {
+        match parse(data) {
+            Err(AocError::Parse(e)) => Some(e.offset()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn rearrange_complex() {
+        let stacks = vec![
+            Stack::new(StackId(1), vec![Crate('Z'), Crate('N')]),
+            Stack::new(StackId(2), vec![Crate('M'), Crate('C'), Crate('D')]),
+            Stack::new(StackId(3), vec![Crate('P')]),
+        ];
+        let moves = vec![
+            Move::new(1, StackId(2), StackId(1)),
+            Move::new(3, StackId(1), StackId(3)),
+            Move::new(2, StackId(2), StackId(1)),
+            Move::new(1, StackId(1), StackId(2)),
+        ];
+
+        let res = rearrange(&stacks, &moves, CraneModel::CrateMover9000).unwrap();
+
+        assert_eq!(
+            res,
+            vec![
+                Stack::new(StackId(1), vec![Crate('C')]),
+                Stack::new(StackId(2), vec![Crate('M')]),
+                Stack::new(
+                    StackId(3),
+                    vec![Crate('P'), Crate('D'), Crate('N'), Crate('Z')]
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn rearrange_simple() {
+        let stacks = vec![
+            Stack::new(StackId(1), vec![]),
+            Stack::new(StackId(2), vec![Crate('A'), Crate('B')]),
+        ];
+        let moves = vec![Move::new(2, StackId(2), StackId(1))];
+
+        let res = rearrange(&stacks, &moves, CraneModel::CrateMover9000).unwrap();
+
+        assert_eq!(
+            res,
+            vec![
+                Stack::new(StackId(1), vec![Crate('B'), Crate('A')]),
+                Stack::new(StackId(2), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn create_stacks_valid() {
+        let spec: StacksSpecification = StacksSpecification::new(
+            vec![
+                vec![
+                    None,
+                    Some(Crate('V')),
+                    Some(Crate('G')),
+                    None,
+                    None,
+                    None,
+                    Some(Crate('H')),
+                    None,
+                    None,
+                ],
+                vec![
+                    Some(Crate('Z')),
+                    Some(Crate('H')),
+                    Some(Crate('Z')),
+                    None,
+                    None,
+                    Some(Crate('T')),
+                    Some(Crate('S')),
+                    None,
+                    None,
+                ],
+                vec![
+                    Some(Crate('P')),
+                    Some(Crate('D')),
+                    Some(Crate('F')),
+                    None,
+                    None,
+                    Some(Crate('B')),
+                    Some(Crate('V')),
+                    Some(Crate('Q')),
+                    None,
+                ],
+                vec![
+                    Some(Crate('B')),
+                    Some(Crate('M')),
+                    Some(Crate('V')),
+                    Some(Crate('N')),
+                    None,
+                    Some(Crate('F')),
+                    Some(Crate('D')),
+                    Some(Crate('N')),
+                    None,
+                ],
+                vec![
+                    Some(Crate('Q')),
+                    Some(Crate('Q')),
+                    Some(Crate('D')),
+                    Some(Crate('F')),
+                    None,
+                    Some(Crate('Z')),
+                    Some(Crate('Z')),
+                    Some(Crate('P')),
+                    Some(Crate('M')),
+                ],
+                vec![
+                    Some(Crate('M')),
+                    Some(Crate('Z')),
+                    Some(Crate('R')),
+                    Some(Crate('D')),
+                    Some(Crate('Q')),
+                    Some(Crate('V')),
+                    Some(Crate('T')),
+                    Some(Crate('F')),
+                    Some(Crate('R')),
+                ],
+                vec![
+                    Some(Crate('D')),
+                    Some(Crate('L')),
+                    Some(Crate('H')),
+                    Some(Crate('G')),
+                    Some(Crate('F')),
+                    Some(Crate('Q')),
+                    Some(Crate('M')),
+                    Some(Crate('G')),
+                    Some(Crate('W')),
+                ],
+                vec![
+                    Some(Crate('N')),
+                    Some(Crate('C')),
+                    Some(Crate('Q')),
+                    Some(Crate('H')),
+                    Some(Crate('N')),
+                    Some(Crate('D')),
+                    Some(Crate('Q')),
+                    Some(Crate('M')),
+                    Some(Crate('B')),
+                ],
+            ],
+            vec![
+                StackId(1),
+                StackId(2),
+                StackId(3),
+                StackId(4),
+                StackId(5),
+                StackId(6),
+                StackId(7),
+                StackId(8),
+                StackId(9),
+            ],
+        );
+
+        let stacks = create_stacks(spec);
+
+        assert_eq!(
+            stacks,
+            vec![
+                Stack::new(
+                    StackId(1),
+                    vec![
+                        Crate('N'),
+                        Crate('D'),
+                        Crate('M'),
+                        Crate('Q'),
+                        Crate('B'),
+                        Crate('P'),
+                        Crate('Z')
+                    ]
+                ),
+                Stack::new(
+                    StackId(2),
+                    vec![
+                        Crate('C'),
+                        Crate('L'),
+                        Crate('Z'),
+                        Crate('Q'),
+                        Crate('M'),
+                        Crate('D'),
+                        Crate('H'),
+                        Crate('V')
+                    ]
+                ),
+                Stack::new(
+                    StackId(3),
+                    vec![
+                        Crate('Q'),
+                        Crate('H'),
+                        Crate('R'),
+                        Crate('D'),
+                        Crate('V'),
+                        Crate('F'),
+                        Crate('Z'),
+                        Crate('G')
+                    ]
+                ),
+                Stack::new(
+                    StackId(4),
+                    vec![Crate('H'), Crate('G'), Crate('D'), Crate('F'), Crate('N')]
+                ),
+                Stack::new(StackId(5), vec![Crate('N'), Crate('F'), Crate('Q')]),
+                Stack::new(
+                    StackId(6),
+                    vec![
+                        Crate('D'),
+                        Crate('Q'),
+                        Crate('V'),
+                        Crate('Z'),
+                        Crate('F'),
+                        Crate('B'),
+                        Crate('T')
+                    ]
+                ),
+                Stack::new(
+                    StackId(7),
+                    vec![
+                        Crate('Q'),
+                        Crate('M'),
+                        Crate('T'),
+                        Crate('Z'),
+                        Crate('D'),
+                        Crate('V'),
+                        Crate('S'),
+                        Crate('H')
+                    ]
+                ),
+                Stack::new(
+                    StackId(8),
+                    vec![
+                        Crate('M'),
+                        Crate('G'),
+                        Crate('F'),
+                        Crate('P'),
+                        Crate('N'),
+                        Crate('Q')
+                    ]
+                ),
+                Stack::new(
+                    StackId(9),
+                    vec![Crate('B'), Crate('W'), Crate('R'), Crate('M')]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_stack_def_line_nl_valid() {
+        let ins = String::from("[A] [B]     [C]\n");
+        let fff = fragments(parse_stack_def_line_nl(Span::new(&ins)));
+        assert_eq!(
+            fff,
+            Ok((
+                "",
+                vec![Some(Crate('A')), Some(Crate('B')), None, Some(Crate('C'))]
+            ))
+        )
+    }
+
+    #[test]
+    fn parse_crate_with_spaces_leads_to_none() {
+        let crate_s = fragments(parse_crate(Span::new("   ")));
+        assert_eq!(crate_s, Ok(("", None)));
+    }
+
+    #[test]
+    fn parse_crate_with_crate_id() {
+        let crate_s = fragments(parse_crate(Span::new("[C]")));
+        assert_eq!(crate_s, Ok(("", Some(Crate('C')))));
+    }
+
+    #[test]
+    fn parse_stack_def_line_valid() {
+        let stack_def_line = fragments(parse_stack_def_line(Span::new("[Z] [A]     [X] [K]")));
+        assert_eq!(
+            stack_def_line,
+            Ok((
+                "",
+                vec![
+                    Some(Crate('Z')),
+                    Some(Crate('A')),
+                    None,
+                    Some(Crate('X')),
+                    Some(Crate('K'))
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_stack_id_line_valid() {
+        let stack_id_line = fragments(parse_stack_id_line(Span::new(
+            " 1   2   3   4   5   6   7   8   9 ",
+        )));
+        assert_eq!(
+            stack_id_line,
+            Ok((
+                "",
+                vec![
+                    StackId(1),
+                    StackId(2),
+                    StackId(3),
+                    StackId(4),
+                    StackId(5),
+                    StackId(6),
+                    StackId(7),
+                    StackId(8),
+                    StackId(9),
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_move_valid() {
+        let move_s = fragments(parse_move(Span::new("move 42 from 1 to 4")));
+        assert_eq!(move_s, Ok(("", Move::new(42, StackId(1), StackId(4)))));
+    }
+
+    #[test]
+    fn parse_stack_id_line_multi_digit() {
+        let stack_id_line = fragments(parse_stack_id_line(Span::new(
+            " 1   2   3   4   5   6   7   8   9  10  11 ",
+        )));
+        assert_eq!(
+            stack_id_line,
+            Ok(("", (1..=11).map(StackId).collect::<Vec<_>>()))
+        );
+    }
+
+    #[test]
+    fn parse_move_multi_digit() {
+        let move_s = fragments(parse_move(Span::new("move 3 from 12 to 10")));
+        assert_eq!(move_s, Ok(("", Move::new(3, StackId(12), StackId(10)))));
+    }
+
+    #[test]
+    fn more_than_nine_stacks() -> anyhow::Result<()> {
+        let data = [
+            "                                        [K]",
+            "[A] [B] [C] [D] [E] [F] [G] [H] [I] [J] [L]",
+            " 1   2   3   4   5   6   7   8   9  10  11 ",
+            "",
+            "move 2 from 11 to 1",
+            "move 1 from 10 to 2",
+            "",
+        ]
+        .join("\n");
+
+        let procedure = parse(&data)?;
+
+        assert_that!(procedure.0).has_length(11);
+        assert_that!(solve(&procedure))
+            .is_ok()
+            .is_equal_to(("LJCDEFGHI  ".to_owned(), "KJCDEFGHI  ".to_owned()));
+        Ok(())
+    }
+
+    #[test]
+    fn bad_crate() {
+        let data = EXAMPLE.replacen("[C]", "[c]", 1);
+
+        assert_that!(error_offset(&data)).is_equal_to(Some(EXAMPLE.find("[C]").unwrap() + 1));
+    }
+
+    #[test]
+    fn unknown_stack() {
+        let stacks = vec![
+            Stack::new(StackId(1), vec![Crate('A')]),
+            Stack::new(StackId(2), vec![]),
+        ];
+        let moves = vec![
+            Move::new(1, StackId(1), StackId(2)),
+            Move::new(1, StackId(2), StackId(4)),
+        ];
+
+        let res = rearrange(&stacks, &moves, CraneModel::CrateMover9000);
+
+        assert_that!(res).is_err().is_equal_to(Error::UnknownStack {
+            number: 2,
+            m: Move::new(1, StackId(2), StackId(4)),
+            stack: StackId(4),
+        });
+        assert_that!(res.unwrap_err().to_string().as_str())
+            .is_equal_to("move 2 (2 --1--> 4) uses stack 4, which does not exist");
+    }
+
+    #[test]
+    fn not_enough_crates() {
+        let stacks = vec![
+            Stack::new(StackId(1), vec![Crate('A'), Crate('B')]),
+            Stack::new(StackId(2), vec![]),
+        ];
+        let moves = vec![Move::new(3, StackId(1), StackId(2))];
+
+        let res = rearrange(&stacks, &moves, CraneModel::CrateMover9001);
+
+        assert_that!(res)
+            .is_err()
+            .is_equal_to(Error::NotEnoughCrates {
+                number: 1,
+                m: Move::new(3, StackId(1), StackId(2)),
+                height: 2,
+            });
+        assert_that!(res.unwrap_err().to_string().as_str())
+            .is_equal_to("move 1 (1 --3--> 2) takes 3 crates from stack 1, which only has 2");
+    }
+
+    #[test]
+    fn steps_up_to_unknown_stack() -> anyhow::Result<()> {
+        let data = EXAMPLE.replacen("from 1 to 3", "from 1 to 4", 1);
+        let procedure = parse(&data)?;
+
+        let steps = rearrangement_steps(&procedure, CraneModel::CrateMover9000)
+            .map(|step| step.is_ok())
+            .collect::<Vec<_>>();
+
+        assert_that!(steps).is_equal_to(vec![true, false]);
+        assert_that!(top_crate_of_stacks_from_str(&data)).is_err();
+        Ok(())
+    }
+
+    #[test]
+    fn no_final_newline() {
+        assert_that!(top_crate_of_stacks_from_str(EXAMPLE.trim_end()))
+            .is_ok()
+            .is_equal_to(("CMZ".to_owned(), "MCD".to_owned()));
+    }
+
+    #[test]
+    fn bad_move() {
+        let data = EXAMPLE.replacen("from 1 to 3", "from 1 at 3", 1);
+
+        assert_that!(error_offset(&data))
+            .is_equal_to(Some(EXAMPLE.find("from 1 to 3").unwrap() + 6));
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(top_crate_of_stacks_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to(("CMZ".to_owned(), "MCD".to_owned()));
+    }
+
+    #[test]
+    fn draw_example_stacks() -> anyhow::Result<()> {
+        let (stacks, _) = parse(EXAMPLE)?;
+
+        assert_that!(draw_stacks(&stacks))
+            .is_equal_to("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ".to_owned());
+        Ok(())
+    }
+
+    #[test]
+    fn steps() -> anyhow::Result<()> {
+        let procedure = parse(EXAMPLE)?;
+
+        let steps = rearrangement_steps(&procedure, CraneModel::CrateMover9000)
+            .map(|step| step.map(|(m, stacks)| (m.to_string(), draw_stacks(&stacks))))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        assert_that!(steps).has_length(4);
+        assert_that!(steps[0]).is_equal_to((
+            "2 --1--> 1".to_owned(),
+            "[D]        \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ".to_owned(),
+        ));
+        assert_that!(steps[3].1).is_equal_to(
+            "        [Z]\n        [N]\n        [D]\n[C] [M] [P]\n 1   2   3 ".to_owned(),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn drawn_procedure() -> anyhow::Result<()> {
+        let procedure = parse(EXAMPLE)?;
+
+        let drawing = rearrangement_steps(&procedure, CraneModel::CrateMover9001)
+            .map(|step| step.map(|(m, stacks)| format!("{m}\n{}", draw_stacks(&stacks))))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .fold(draw_stacks(&procedure.0), |drawing, step| {
+                drawing + "\n\n" + &step
+            });
+
+        assert_snapshot!(drawing);
+        Ok(())
+    }
+}
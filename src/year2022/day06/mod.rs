@@ -0,0 +1,288 @@
+use crate::diagnostics::BadInput;
+use crate::error::{PuzzleError, Result};
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::io::{BufReader, Read};
+use thiserror::Error;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+const START_MARKER_SIZE: usize = 4;
+const MESSAGE_MARKER_SIZE: usize = 14;
+
+#[derive(Error, Debug, PartialEq)]
+enum Error {
+    #[error("Could not find the marker")]
+    NotFound,
+    #[error("byte {offset} of the signal ({byte:#04x}) is not a lowercase letter")]
+    NotALetter { offset: usize, byte: u8 },
+}
+
+impl PuzzleError for Error {}
+
+/// The last items of a stream, up to the length of a marker.
+///
+/// The window slides one item at a time, keeping the count of each item in it and how many of
+/// them are there more than once, hence a single pass over the items.
+struct Window<T> {
+    items: VecDeque<T>,
+    counts: HashMap<T, usize>,
+    repeated: usize,
+    marker_len: usize,
+}
+
+impl<T> Window<T>
+where
+    T: Eq + Hash + Clone,
+{
+    fn new(marker_len: usize) -> Self {
+        Self {
+            items: VecDeque::with_capacity(marker_len + 1),
+            counts: HashMap::new(),
+            repeated: 0,
+            marker_len,
+        }
+    }
+
+    /// Slides the window over the next item, telling whether it then holds a marker.
+    fn push(&mut self, item: T) -> bool {
+        let added = self.counts.entry(item.clone()).or_insert(0usize);
+        *added += 1;
+        if *added == 2 {
+            self.repeated += 1;
+        }
+        self.items.push_back(item);
+        if self.items.len() > self.marker_len {
+            if let Some(removed) = self
+                .items
+                .pop_front()
+                .and_then(|item| self.counts.get_mut(&item))
+            {
+                *removed -= 1;
+                if *removed == 1 {
+                    self.repeated -= 1;
+                }
+            }
+        }
+        self.items.len() == self.marker_len && self.repeated == 0
+    }
+}
+
+/// A run of items all different from each other.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Marker<T> {
+    /// Number of items read up to the end of the marker.
+    pub end: usize,
+    pub items: Vec<T>,
+}
+
+/// Every run of `marker_len` items all different from each other, by order of their end, runs
+/// overlapping each other: characters, bytes or any kind of token. Empty markers end before
+/// any item is read, then after each of them.
+pub fn markers<I>(items: I, marker_len: usize) -> impl Iterator<Item = Marker<I::Item>>
+where
+    I: IntoIterator,
+    I::Item: Eq + Hash + Clone,
+{
+    let empty = (marker_len == 0).then(|| Marker {
+        end: 0,
+        items: vec![],
+    });
+    let mut window = Window::new(marker_len);
+    empty
+        .into_iter()
+        .chain(items.into_iter().enumerate().filter_map(move |(i, item)| {
+            window.push(item).then(|| Marker {
+                end: i + 1,
+                items: window.items.iter().cloned().collect(),
+            })
+        }))
+}
+
+/// Number of items to read before the end of the first `marker_len` items all different from
+/// each other, if there is such a run of items.
+pub fn find_marker<I>(items: I, marker_len: usize) -> Option<usize>
+where
+    I: IntoIterator,
+    I::Item: Eq + Hash + Clone,
+{
+    markers(items, marker_len).next().map(|marker| marker.end)
+}
+
+pub fn parse(data: &str) -> Result<Vec<char>> {
+    let signal = data.trim_end();
+    if let Some((i, c)) = signal.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+        let bad = &signal[i..i + c.len_utf8()];
+        return Err(BadInput::at(data, bad, "not a lowercase letter").into());
+    }
+    Ok(signal.chars().collect())
+}
+
+pub fn solve(buffer: &[char]) -> Result<(usize, usize)> {
+    let start_stream = find_marker(buffer, START_MARKER_SIZE).ok_or(Error::NotFound)?;
+    let start_message = find_marker(buffer, MESSAGE_MARKER_SIZE).ok_or(Error::NotFound)?;
+    Ok((start_stream, start_message))
+}
+
+/// Same as [`solve`], scanning the signal byte by byte from `reader`, up to its first whitespace:
+/// only the windows of both markers are kept, however long the stream.
+pub fn fix_device(reader: impl Read) -> Result<(usize, usize)> {
+    let mut start = Window::new(START_MARKER_SIZE);
+    let mut message = Window::new(MESSAGE_MARKER_SIZE);
+    let (mut start_stream, mut start_message) = (None, None);
+    for (offset, byte) in BufReader::new(reader).bytes().enumerate() {
+        let byte = byte?;
+        if byte.is_ascii_whitespace() {
+            break;
+        }
+        if !byte.is_ascii_lowercase() {
+            return Err(Error::NotALetter { offset, byte }.into());
+        }
+        if start_stream.is_none() && start.push(byte) {
+            start_stream = Some(offset + 1);
+        }
+        if start_message.is_none() && message.push(byte) {
+            start_message = Some(offset + 1);
+        }
+        if start_stream.is_some() && start_message.is_some() {
+            break;
+        }
+    }
+    Ok((
+        start_stream.ok_or(Error::NotFound)?,
+        start_message.ok_or(Error::NotFound)?,
+    ))
+}
+
+pub fn fix_device_from_str(data: &str) -> Result<(usize, usize)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::error::AocError;
+    use parameterized::parameterized;
+    use spectral::prelude::*;
+    use std::io;
+
+    #[parameterized(
+        input = {
+            "bvwbjplbgvbhsrlpgdmjqwftvncz", "nppdvjthqldpwncqszvftbrmjlhg", "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb", "bvwbjplbgvbhsrlpgdmjqwftvncz", "nppdvjthqldpwncqszvftbrmjlhg", "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+            "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"
+        },
+        marker_size = {
+            4,4,4,4,14,14,14,14,14
+        },
+        index = {
+            5,6,10,11,19,23,23,29,26
+        })
+    ]
+    fn marker_size_tests(input: &str, marker_size: usize, index: usize) {
+        let v: Vec<_> = String::from(input).chars().collect();
+
+        let res = find_marker(&v, marker_size).unwrap();
+
+        assert_eq!(res, index);
+    }
+
+    #[test]
+    fn marker_longer_than_buffer() {
+        let v: Vec<_> = "abc".chars().collect();
+
+        assert_that!(find_marker(&v, 4)).is_none();
+    }
+
+    #[test]
+    fn repeated_letter_leaving_window() {
+        let v: Vec<_> = "aabcd".chars().collect();
+
+        assert_that!(find_marker(&v, 4)).is_some().is_equal_to(5);
+    }
+
+    #[test]
+    fn any_kind_of_item() {
+        assert_that!(find_marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb".bytes(), 4))
+            .is_some()
+            .is_equal_to(7);
+        assert_that!(find_marker("a b a c b d".split(' '), 3))
+            .is_some()
+            .is_equal_to(4);
+        assert_that!(find_marker([1, 1, 1, 1], 2)).is_none();
+        assert_that!(find_marker([1], 0)).is_some().is_equal_to(0);
+    }
+
+    #[test]
+    fn all_markers() {
+        let found = markers("abcabbcd".chars(), 3).collect::<Vec<_>>();
+
+        let expected = [(3, "abc"), (4, "bca"), (5, "cab"), (8, "bcd")]
+            .map(|(end, items)| Marker {
+                end,
+                items: items.chars().collect(),
+            })
+            .to_vec();
+        assert_that!(found).is_equal_to(expected);
+        let ends = markers(EXAMPLE.trim_end().bytes(), MESSAGE_MARKER_SIZE)
+            .map(|marker| marker.end)
+            .collect::<Vec<_>>();
+        assert_that!(ends).is_equal_to(vec![19, 25, 26, 27, 28, 29, 30]);
+    }
+
+    #[test]
+    fn empty_markers() {
+        let ends = markers("ab".chars(), 0)
+            .map(|marker| marker.end)
+            .collect::<Vec<_>>();
+
+        assert_that!(ends).is_equal_to(vec![0, 1, 2]);
+        assert_that!(find_marker("ab".chars(), 0))
+            .is_some()
+            .is_equal_to(0);
+    }
+
+    #[test]
+    fn no_marker() {
+        assert_that!(solve(&parse("abcabcabc").unwrap())).is_err();
+    }
+
+    #[test]
+    fn not_a_letter() {
+        assert_that!(parse("abcD")).is_err();
+        assert_that!(parse("abcd\n")).is_ok().has_length(4);
+    }
+
+    #[test]
+    fn streamed() {
+        assert_that!(fix_device(EXAMPLE.as_bytes()))
+            .is_ok()
+            .is_equal_to((7, 19));
+
+        let noise = 100_000;
+        let stream = io::repeat(b'a')
+            .take(noise)
+            .chain("abcdefghijklmnop\n".as_bytes());
+        assert_that!(fix_device(stream))
+            .is_ok()
+            .is_equal_to((noise as usize + 4, noise as usize + 14));
+    }
+
+    #[test]
+    fn streamed_errors() {
+        assert_that!(fix_device("abcabc\nabcdefghijklmnop".as_bytes())).is_err();
+        assert!(matches!(
+            fix_device("abcD".as_bytes()),
+            Err(AocError::Logic(e)) if e.to_string() == "byte 3 of the signal (0x44) is not a lowercase letter"
+        ));
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(fix_device_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((7, 19));
+    }
+}
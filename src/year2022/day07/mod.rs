@@ -0,0 +1,850 @@
+use crate::diagnostics::{parse_all, BadInput};
+use crate::error::{AocError, PuzzleError, Result};
+use dendron::{
+    traverse::DftEvent::{Close, Open},
+    tree::HierarchyEditGrantError,
+    Node,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::{is_not, tag},
+    character::complete::{digit1, line_ending, space1},
+    combinator::{eof, map, map_res},
+    error::{FromExternalError, ParseError},
+    multi::many1,
+    sequence::{delimited, terminated},
+    IResult,
+};
+use nom_locate::LocatedSpan;
+use std::cmp::Reverse;
+use std::fmt::Write;
+use std::iter;
+use std::num::ParseIntError;
+use thiserror::Error;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+/// What a line of the terminal output does to the file system being explored.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TreeBuildCommand {
+    /// `$ cd ..`
+    MoveToParent,
+    /// `dir <name>` in a listing
+    CreateDir(String),
+    /// `<size> <name>` in a listing
+    CreateFile(String, usize),
+    /// `$ cd <name>`, the root being `/`
+    MoveTo(String),
+    DoNothing, // for ls
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FsNode {
+    FsDirectory(FsNodeInfo),
+    FsFile(FsNodeInfo),
+}
+
+impl FsNode {
+    fn new_file(name: &str, size: usize) -> Self {
+        FsNode::FsFile(FsNodeInfo::new(name, size))
+    }
+
+    fn new_dir(name: &str) -> Self {
+        FsNode::FsDirectory(FsNodeInfo::new(name, 0))
+    }
+
+    pub fn name(&self) -> &str {
+        match &self {
+            FsNode::FsDirectory(info) => &info.name,
+            FsNode::FsFile(info) => &info.name,
+        }
+    }
+
+    /// Size of a file, or of all the files below a directory.
+    pub fn size(&self) -> usize {
+        match &self {
+            FsNode::FsDirectory(info) => info.size,
+            FsNode::FsFile(info) => info.size,
+        }
+    }
+
+    pub fn is_dir(&self) -> bool {
+        matches!(self, FsNode::FsDirectory(_))
+    }
+
+    fn increase_size(&mut self, sz: usize) {
+        if let FsNode::FsDirectory(info) = self {
+            info.size += sz;
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FsNodeInfo {
+    name: String,
+    size: usize,
+}
+
+impl FsNodeInfo {
+    fn new(name: &str, size: usize) -> Self {
+        FsNodeInfo {
+            name: name.to_owned(),
+            size,
+        }
+    }
+}
+
+/// A file or directory of the file system, with where it stands.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    /// Absolute path, `/` for the root directory.
+    pub path: String,
+    /// Number of directories above it, 0 for the root directory.
+    pub depth: usize,
+    pub node: FsNode,
+}
+
+impl Entry {
+    fn new(node: &Node<FsNode>) -> Self {
+        let mut names = node
+            .ancestors_or_self()
+            .map(|n| n.borrow_data().name().to_owned())
+            .collect::<Vec<_>>();
+        // the root directory is named after the separator already
+        names.pop();
+        names.reverse();
+        Self {
+            path: format!("/{}", names.join("/")),
+            depth: names.len(),
+            node: node.borrow_data().clone(),
+        }
+    }
+}
+
+/// The file system explored by the terminal output, to be queried by path.
+#[derive(Debug)]
+pub struct FileSystem(Node<FsNode>);
+
+impl FileSystem {
+    pub fn root(&self) -> &Node<FsNode> {
+        &self.0
+    }
+
+    /// Size of all the files.
+    pub fn size(&self) -> usize {
+        self.0.borrow_data().size()
+    }
+
+    fn find(&self, path: &str) -> Option<Node<FsNode>> {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(self.0.clone(), |node, name| {
+                node.children().find(|c| c.borrow_data().name() == name)
+            })
+    }
+
+    /// The file or directory at an absolute path like `/a/e`, if it showed up in the output.
+    pub fn get(&self, path: &str) -> Option<Entry> {
+        self.find(path).map(|node| Entry::new(&node))
+    }
+
+    /// Size of the file or directory at `path`.
+    pub fn size_of(&self, path: &str) -> Option<usize> {
+        self.find(path).map(|node| node.borrow_data().size())
+    }
+
+    /// Every file and directory, depth first: a directory comes right before its content.
+    pub fn entries(&self) -> impl Iterator<Item = Entry> {
+        self.0
+            .depth_first_traverse()
+            .filter_map(|event| match event {
+                Open(node) => Some(Entry::new(&node)),
+                Close(_) => None,
+            })
+    }
+
+    /// Every directory, depth first.
+    pub fn directories(&self) -> impl Iterator<Item = Entry> {
+        self.entries().filter(|entry| entry.node.is_dir())
+    }
+
+    /// Path and size of every directory, depth first, like `du`.
+    pub fn sizes(&self) -> impl Iterator<Item = (String, usize)> {
+        self.directories()
+            .map(|entry| (entry.path, entry.node.size()))
+    }
+
+    /// The directories whose size satisfies `predicate`, depth first.
+    pub fn directories_where<P>(&self, predicate: P) -> impl Iterator<Item = Entry>
+    where
+        P: Fn(usize) -> bool,
+    {
+        self.directories()
+            .filter(move |entry| predicate(entry.node.size()))
+    }
+
+    /// The `n` largest directories, largest first, directories of the same size in depth first
+    /// order.
+    pub fn largest_directories(&self, n: usize) -> Vec<Entry> {
+        let mut directories = self.directories().collect::<Vec<_>>();
+        directories.sort_by_key(|entry| Reverse(entry.node.size()));
+        directories.truncate(n);
+        directories
+    }
+
+    /// Every file and directory, level by level: the root directory, then its content, then
+    /// the content of its subdirectories...
+    pub fn by_depth(&self) -> impl Iterator<Item = Entry> {
+        iter::successors(Some(vec![self.0.clone()]), |level| {
+            let next = level
+                .iter()
+                .flat_map(|node| node.children())
+                .collect::<Vec<_>>();
+            (!next.is_empty()).then_some(next)
+        })
+        .flatten()
+        .map(|node| Entry::new(&node))
+    }
+}
+
+/// Any name a file or directory may have: everything up to the end of the line, but spaces.
+fn name<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Span<'a>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    is_not(" \t\r\n")(i)
+}
+
+fn file_name<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Span<'a>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    name(i)
+}
+
+fn size<'a, E>(i: Span<'a>) -> IResult<Span<'a>, usize, E>
+where
+    E: ParseError<Span<'a>> + FromExternalError<Span<'a>, ParseIntError>,
+{
+    map_res(digit1, |s: Span| s.parse::<usize>())(i)
+}
+
+fn file_statement<'a, E>(i: Span<'a>) -> IResult<Span<'a>, TreeBuildCommand, E>
+where
+    E: ParseError<Span<'a>> + FromExternalError<Span<'a>, ParseIntError>,
+{
+    let (rest, sz) = size(i)?;
+    let (rest, _) = space1(rest)?;
+    let (rest, file) = file_name(rest)?;
+    let (rest, _) = alt((line_ending, eof))(rest)?;
+    Ok((rest, TreeBuildCommand::CreateFile(file.to_string(), sz)))
+}
+
+fn dir_name<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Span<'a>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    name(i)
+}
+
+fn dir_statement<'a, E>(i: Span<'a>) -> IResult<Span<'a>, TreeBuildCommand, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    map(
+        delimited(tag("dir "), dir_name, alt((line_ending, eof))),
+        |dir| TreeBuildCommand::CreateDir(dir.to_string()),
+    )(i)
+}
+
+fn ls_statement<'a, E>(i: Span<'a>) -> IResult<Span<'a>, TreeBuildCommand, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    map(terminated(tag("$ ls"), alt((line_ending, eof))), |_| {
+        TreeBuildCommand::DoNothing
+    })(i)
+}
+
+fn cd_statement<'a, E>(i: Span<'a>) -> IResult<Span<'a>, TreeBuildCommand, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    map(
+        delimited(tag("$ cd "), dir_name, alt((line_ending, eof))),
+        |dir| {
+            if *dir.fragment() == ".." {
+                TreeBuildCommand::MoveToParent
+            } else {
+                TreeBuildCommand::MoveTo(dir.to_string())
+            }
+        },
+    )(i)
+}
+
+/// The commands typed and their output, one per line.
+pub fn terminal_output<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Vec<TreeBuildCommand>, E>
+where
+    E: ParseError<Span<'a>> + FromExternalError<Span<'a>, ParseIntError>,
+{
+    many1(alt((
+        file_statement,
+        dir_statement,
+        ls_statement,
+        cd_statement,
+    )))(i)
+}
+
+/// A command of the terminal output that makes no sense given the previous ones.
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("line {line}: cannot move above the root directory")]
+    AboveRoot { line: usize },
+    #[error("line {line}: cannot move into {name}, which is a file")]
+    NotADirectory { line: usize, name: String },
+    #[error("line {line}: {name} does not match what was listed before")]
+    Conflict { line: usize, name: String },
+    #[error("the file system cannot be built")]
+    Locked(#[from] HierarchyEditGrantError),
+}
+
+impl Error {
+    /// The line of the terminal output at fault, if any.
+    fn line(&self) -> Option<usize> {
+        match self {
+            Error::AboveRoot { line }
+            | Error::NotADirectory { line, .. }
+            | Error::Conflict { line, .. } => Some(*line),
+            Error::Locked(_) => None,
+        }
+    }
+}
+
+impl PuzzleError for Error {}
+
+fn child(node: &Node<FsNode>, name: &str) -> Option<Node<FsNode>> {
+    node.children().find(|c| c.borrow_data().name() == name)
+}
+
+/// Replays the commands of the terminal output, building the file system they explore.
+///
+/// A directory may be entered before its parent was listed, and listed several times: its
+/// content is only counted once.
+fn build(commands: Vec<TreeBuildCommand>) -> Result<Node<FsNode>, Error> {
+    let root = Node::new_tree(FsNode::new_dir("/"));
+    let grant = root.tree().grant_hierarchy_edit()?;
+    let mut node = root.clone();
+    // each command stands on its own line
+    for (line, command) in (1..).zip(commands) {
+        node = match command {
+            TreeBuildCommand::MoveToParent => node.parent().ok_or(Error::AboveRoot { line })?,
+            TreeBuildCommand::MoveTo(dir) if dir == "/" => root.clone(),
+            TreeBuildCommand::MoveTo(dir) => match child(&node, &dir) {
+                Some(c) if c.borrow_data().is_dir() => c,
+                Some(_) => return Err(Error::NotADirectory { line, name: dir }),
+                None => node.create_as_last_child(&grant, FsNode::new_dir(&dir)),
+            },
+            TreeBuildCommand::CreateDir(dir) => {
+                match child(&node, &dir) {
+                    Some(c) if c.borrow_data().is_dir() => {}
+                    Some(_) => return Err(Error::Conflict { line, name: dir }),
+                    None => {
+                        node.create_as_last_child(&grant, FsNode::new_dir(&dir));
+                    }
+                }
+                node
+            }
+            TreeBuildCommand::CreateFile(file, sz) => {
+                match child(&node, &file) {
+                    Some(c) if *c.borrow_data() == FsNode::new_file(&file, sz) => {}
+                    Some(_) => return Err(Error::Conflict { line, name: file }),
+                    None => {
+                        node.create_as_last_child(&grant, FsNode::new_file(&file, sz));
+                        node.ancestors_or_self()
+                            .for_each(|n| n.borrow_data_mut().increase_size(sz));
+                    }
+                }
+                node
+            }
+            TreeBuildCommand::DoNothing => node,
+        };
+    }
+    Ok(root)
+}
+
+fn total_size_of_directories_up_to(fs: &Node<FsNode>, max_size: usize) -> usize {
+    fs.depth_first_traverse()
+        .filter_map(|e| match e {
+            Close(e) => {
+                let node: &FsNode = &e.borrow_data();
+                match node {
+                    FsNode::FsDirectory(info) if info.size <= max_size => Some(info.size),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .sum()
+}
+
+fn smallest_directory_to_delete_size(fs: &Node<FsNode>, min_size: usize) -> usize {
+    fs.depth_first_traverse()
+        .filter_map(|e| match e {
+            Close(e) => {
+                let node: &FsNode = &e.borrow_data();
+                match node {
+                    FsNode::FsDirectory(info) if info.size >= min_size => Some(info.size),
+                    FsNode::FsDirectory(_) => None,
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+/// Quotes a label for Graphviz.
+fn dot_label(label: &str) -> String {
+    format!("\"{}\"", label.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Writes `node` and its descendants as DOT statements, numbering the nodes from `next_id`.
+/// Returns the id of `node`.
+fn write_dot_node(dot: &mut String, node: &Node<FsNode>, next_id: &mut usize) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+    let (shape, label) = match &*node.borrow_data() {
+        FsNode::FsDirectory(info) => ("box", format!("{} ({})", info.name, info.size)),
+        FsNode::FsFile(info) => ("plaintext", format!("{} ({})", info.name, info.size)),
+    };
+    let _ = writeln!(dot, "  n{id} [shape={shape}, label={}];", dot_label(&label));
+    for child in node.children() {
+        let child_id = write_dot_node(dot, &child, next_id);
+        let _ = writeln!(dot, "  n{id} -> n{child_id};");
+    }
+    id
+}
+
+/// Renders the file system as a Graphviz graph: directories are boxes labelled with their total
+/// size, files are leaves labelled with theirs.
+pub fn to_dot(fs: &FileSystem) -> String {
+    let mut dot = String::from("digraph filesystem {\n  rankdir=LR;\n");
+    write_dot_node(&mut dot, fs.root(), &mut 0);
+    dot.push_str("}\n");
+    dot
+}
+
+/// The file system explored by the terminal output, a command that makes no sense being
+/// pointed at in the output.
+pub fn parse(data: &str) -> Result<FileSystem> {
+    let commands = parse_all(data, terminal_output)?;
+    let root = build(commands).map_err(|e| {
+        match e.line().and_then(|line| data.lines().nth(line - 1)) {
+            Some(command) => AocError::from(BadInput::at(data, command, e)),
+            None => e.into(),
+        }
+    })?;
+    Ok(FileSystem(root))
+}
+
+/// Size of the disk of the device.
+const DISK_SIZE: usize = 70_000_000;
+/// Free space the update needs.
+const UPDATE_SIZE: usize = 30_000_000;
+
+/// Total size of the directories of at most 100000, and size of the smallest directory to delete
+/// to make room for the update, 0 if there is room already.
+pub fn solve(fs: &FileSystem) -> Result<(usize, usize)> {
+    let total_size = total_size_of_directories_up_to(fs.root(), 100000);
+    let smallest = match fs.size().saturating_sub(DISK_SIZE - UPDATE_SIZE) {
+        0 => 0,
+        space_to_clear => smallest_directory_to_delete_size(fs.root(), space_to_clear),
+    };
+    Ok((total_size, smallest))
+}
+
+pub fn total_size_of_small_directories_and_smallest_to_delete_from_str(
+    data: &str,
+) -> Result<(usize, usize)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics;
+    use dendron::tree_node;
+    use insta::assert_snapshot;
+    use spectral::prelude::*;
+
+    /// What a parser made of its input, with what is left of it.
+    fn fragments<O>(result: IResult<Span<'_>, O, ()>) -> IResult<&str, O, ()> {
+        result.map(|(rest, o)| (*rest.fragment(), o))
+    }
+
+    /// The name a parser read, with what is left of its input.
+    fn name_fragments<'a>(
+        result: IResult<Span<'a>, Span<'a>, ()>,
+    ) -> IResult<&'a str, &'a str, ()> {
+        result.map(|(rest, name)| (*rest.fragment(), *name.fragment()))
+    }
+
+    #[test]
+    fn parse_dir_name() {
+        let d = name_fragments(dir_name(Span::new("adirectory")));
+        assert_eq!(d, Ok(("", "adirectory")));
+    }
+
+    #[test]
+    fn parse_root() {
+        let root = name_fragments(dir_name(Span::new("/")));
+        assert_eq!(root, Ok(("", "/")));
+    }
+
+    #[test]
+    fn parse_dir_statement() {
+        let dir_stmt = fragments(dir_statement(Span::new("dir aeisnieuianst\n")));
+        assert_eq!(
+            dir_stmt,
+            Ok(("", TreeBuildCommand::CreateDir("aeisnieuianst".to_string())))
+        );
+    }
+
+    #[test]
+    fn parse_file_name() {
+        let f = name_fragments(file_name(Span::new("toto.txt")));
+        assert_eq!(f, Ok(("", "toto.txt")));
+    }
+
+    #[test]
+    fn parse_real_world_names() {
+        for name in [
+            "my-dir",
+            "snake_case",
+            "archive.tar.gz",
+            ".hidden",
+            "v1.2-rc_3",
+        ] {
+            assert_eq!(name_fragments(file_name(Span::new(name))), Ok(("", name)));
+            assert_eq!(name_fragments(dir_name(Span::new(name))), Ok(("", name)));
+        }
+    }
+
+    #[test]
+    fn parse_name_up_to_end_of_line() {
+        let file_stmt = fragments(file_statement(Span::new("42 some-file.tar.gz\n$ ls")));
+        assert_eq!(
+            file_stmt,
+            Ok((
+                "$ ls",
+                TreeBuildCommand::CreateFile("some-file.tar.gz".to_string(), 42)
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_cd_dotted_dir() {
+        let cd_dir = fragments(cd_statement(Span::new("$ cd .config")));
+        assert_eq!(
+            cd_dir,
+            Ok(("", TreeBuildCommand::MoveTo(".config".to_string())))
+        );
+    }
+
+    #[test]
+    fn parse_size() {
+        let s = fragments(size(Span::new("123457")));
+        assert_eq!(s, Ok(("", 123457)));
+    }
+
+    #[test]
+    fn parse_file_statement() {
+        let file_stmt = fragments(file_statement(Span::new("123242 totobubu.sql")));
+        assert_eq!(
+            file_stmt,
+            Ok((
+                "",
+                TreeBuildCommand::CreateFile("totobubu.sql".to_string(), 123242usize)
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_cd_up() {
+        let cd_up = fragments(cd_statement(Span::new("$ cd ..")));
+        assert_eq!(cd_up, Ok(("", TreeBuildCommand::MoveToParent)));
+    }
+
+    #[test]
+    fn parse_cd_dir() {
+        let cd_dir = fragments(cd_statement(Span::new("$ cd toto")));
+        assert_eq!(
+            cd_dir,
+            Ok(("", TreeBuildCommand::MoveTo("toto".to_string())))
+        );
+    }
+
+    #[test]
+    fn parse_tree() {
+        let data = r#"$ cd /
+$ ls
+dir abc
+dir cde
+12345 a.c
+$ cd abc
+$ ls
+432 b.rs
+324 c.cpp
+$ cd ..
+$ cd cde
+$ ls
+48730 x.java"#;
+
+        let commands = terminal_output::<()>(Span::new(data));
+
+        assert!(commands.is_ok());
+        let tree = build(commands.unwrap().1).unwrap();
+
+        let expected = tree_node! {
+            FsNode::FsDirectory(FsNodeInfo::new("/", 61831)), [
+                /(FsNode::FsDirectory(FsNodeInfo::new("abc", 756)), [
+                    FsNode::new_file("b.rs", 432),
+                    FsNode::new_file("c.cpp", 324)
+                ]),
+                /(FsNode::FsDirectory(FsNodeInfo::new("cde", 48730)), [
+                    FsNode::new_file("x.java", 48730)
+                ]),
+                FsNode::new_file("a.c", 12345)
+            ]
+        };
+
+        assert_eq!(tree.tree(), expected.tree());
+    }
+
+    fn build_from_str(data: &str) -> Result<FileSystem, Error> {
+        build(terminal_output::<()>(Span::new(data)).unwrap().1).map(FileSystem)
+    }
+
+    #[test]
+    fn cd_root() -> anyhow::Result<()> {
+        let fs = build_from_str("$ cd /\n$ cd a\n$ cd b\n$ cd /\n$ ls\n10 x")?;
+
+        assert_that!(fs.size_of("/x")).is_equal_to(Some(10));
+        assert_that!(fs.size_of("/a/b")).is_equal_to(Some(0));
+        Ok(())
+    }
+
+    #[test]
+    fn cd_unlisted_directory() -> anyhow::Result<()> {
+        let fs = build_from_str("$ cd /\n$ cd a\n$ ls\n10 x\n$ cd ..\n$ ls\ndir a\n5 y")?;
+
+        assert_that!(fs.size_of("/a")).is_equal_to(Some(10));
+        assert_that!(fs.size()).is_equal_to(15);
+        assert_that!(fs.directories().count()).is_equal_to(2);
+        Ok(())
+    }
+
+    #[test]
+    fn listed_twice() -> anyhow::Result<()> {
+        let fs = build_from_str("$ cd /\n$ ls\ndir a\n10 x\n$ ls\ndir a\n10 x")?;
+
+        assert_that!(fs.size()).is_equal_to(10);
+        assert_that!(fs.entries().count()).is_equal_to(3);
+        Ok(())
+    }
+
+    #[test]
+    fn above_root() {
+        assert!(matches!(
+            build_from_str("$ cd /\n$ cd .."),
+            Err(Error::AboveRoot { line: 2 })
+        ));
+    }
+
+    #[test]
+    fn cd_into_file() {
+        assert!(matches!(
+            build_from_str("$ ls\n10 x\n$ cd x"),
+            Err(Error::NotADirectory { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn conflicting_listings() {
+        assert!(matches!(
+            build_from_str("$ ls\n10 x\n$ ls\n20 x"),
+            Err(Error::Conflict { line: 4, .. })
+        ));
+        assert!(matches!(
+            build_from_str("$ ls\n10 x\ndir x"),
+            Err(Error::Conflict { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn faulty_command_pointed_at() {
+        let data = "$ cd /\n$ ls\n10 x\n$ cd x";
+
+        let fs = parse(data);
+
+        assert!(
+            matches!(&fs, Err(AocError::Parse(e)) if e.offset() == data.find("$ cd x").unwrap())
+        );
+        assert_that!(diagnostics::describe(&fs.unwrap_err())).contains("cannot move into x");
+    }
+
+    #[test]
+    fn room_for_the_update() {
+        assert_that!(
+            total_size_of_small_directories_and_smallest_to_delete_from_str(
+                "$ cd /\n$ ls\n100 a.txt"
+            )
+        )
+        .is_ok()
+        .is_equal_to((100, 0));
+    }
+
+    #[test]
+    fn directory_of_exactly_the_limit() {
+        assert_that!(
+            total_size_of_small_directories_and_smallest_to_delete_from_str(
+                "$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n100000 a.txt"
+            )
+        )
+        .is_ok()
+        .is_equal_to((200000, 0));
+    }
+
+    #[test]
+    fn find_small_directories() {
+        let fs = tree_node! {
+            FsNode::FsDirectory(FsNodeInfo::new("/", 61831)), [
+                /(FsNode::FsDirectory(FsNodeInfo::new("abc", 756)), [
+                    FsNode::new_file("b.rs", 432),
+                    FsNode::new_file("c.cpp", 324)
+                ]),
+                /(FsNode::FsDirectory(FsNodeInfo::new("cde", 48730)), [
+                    FsNode::new_file("x.java", 48730)
+                ]),
+                FsNode::new_file("a.c", 12345)
+            ]
+        };
+
+        assert_eq!(total_size_of_directories_up_to(&fs, 100000), 111317);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(total_size_of_small_directories_and_smallest_to_delete_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((95437, 24933642));
+    }
+
+    #[test]
+    fn example_sizes() -> anyhow::Result<()> {
+        let fs = parse(EXAMPLE)?;
+
+        assert_that!(fs.size_of("/")).is_equal_to(Some(48381165));
+        assert_that!(fs.size_of("/a/e")).is_equal_to(Some(584));
+        assert_that!(fs.size_of("/a/e/i")).is_equal_to(Some(584));
+        assert_that!(fs.size_of("/d/")).is_equal_to(Some(24933642));
+        assert_that!(fs.size_of("/x")).is_none();
+        Ok(())
+    }
+
+    #[test]
+    fn example_directories() -> anyhow::Result<()> {
+        let fs = parse(EXAMPLE)?;
+
+        let directories = fs
+            .directories()
+            .map(|entry| (entry.path, entry.node.size()))
+            .collect::<Vec<_>>();
+
+        assert_that!(directories).is_equal_to(vec![
+            ("/".to_owned(), 48381165),
+            ("/a".to_owned(), 94853),
+            ("/a/e".to_owned(), 584),
+            ("/d".to_owned(), 24933642),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn example_queries() -> anyhow::Result<()> {
+        let fs = parse(EXAMPLE)?;
+
+        assert_that!(fs.sizes().nth(1)).is_equal_to(Some(("/a".to_owned(), 94853)));
+        let small = fs
+            .directories_where(|size| size <= 100_000)
+            .map(|entry| entry.path)
+            .collect::<Vec<_>>();
+        assert_that!(small).is_equal_to(vec!["/a".to_owned(), "/a/e".to_owned()]);
+        let largest = fs
+            .largest_directories(2)
+            .into_iter()
+            .map(|entry| entry.path)
+            .collect::<Vec<_>>();
+        assert_that!(largest).is_equal_to(vec!["/".to_owned(), "/d".to_owned()]);
+        assert_that!(fs.largest_directories(10)).has_length(4);
+        Ok(())
+    }
+
+    #[test]
+    fn example_by_depth() -> anyhow::Result<()> {
+        let fs = parse(EXAMPLE)?;
+
+        let depths = fs.by_depth().map(|entry| entry.depth).collect::<Vec<_>>();
+
+        assert_that!(depths).has_length(fs.entries().count());
+        assert_that!(depths.windows(2).all(|w| w[0] <= w[1])).is_true();
+        assert_that!(fs.get("/a/e/i"))
+            .is_some()
+            .matches(|entry| entry.depth == 3);
+        Ok(())
+    }
+
+    #[test]
+    fn dot_export() {
+        let fs = tree_node! {
+            FsNode::FsDirectory(FsNodeInfo::new("/", 12777)), [
+                /(FsNode::FsDirectory(FsNodeInfo::new("abc", 432)), [
+                    FsNode::new_file("b.rs", 432),
+                ]),
+                FsNode::new_file("a.c", 12345)
+            ]
+        };
+
+        assert_that!(to_dot(&FileSystem(fs))).is_equal_to(
+            r#"digraph filesystem {
+  rankdir=LR;
+  n0 [shape=box, label="/ (12777)"];
+  n1 [shape=box, label="abc (432)"];
+  n2 [shape=plaintext, label="b.rs (432)"];
+  n1 -> n2;
+  n0 -> n1;
+  n3 [shape=plaintext, label="a.c (12345)"];
+  n0 -> n3;
+}
+"#
+            .to_owned(),
+        );
+    }
+
+    #[test]
+    fn example_dot_export() -> anyhow::Result<()> {
+        let fs = parse(EXAMPLE)?;
+
+        assert_snapshot!(to_dot(&fs));
+        Ok(())
+    }
+
+    #[test]
+    fn dot_label_escaping() {
+        assert_that!(dot_label(r#"a"b\c"#)).is_equal_to(r#""a\"b\\c""#.to_owned());
+    }
+}
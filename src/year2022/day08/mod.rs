@@ -0,0 +1,234 @@
+use crate::diagnostics::BadInput;
+use crate::error::Result;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+/// Whether each tree can be seen from outside the grid.
+fn visibility(data: &[u8], map_size: (usize, usize)) -> Vec<bool> {
+    let (nb_rows, nb_columns) = map_size;
+    data.iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let to_north = (i % nb_columns)..i;
+            let to_south = (i + nb_columns)..((nb_rows * nb_columns) + (i % nb_columns));
+            let to_west = (i - i % nb_columns)..i;
+            let to_east = i + 1..(i - (i % nb_columns) + nb_columns);
+            to_north
+                .into_iter()
+                .step_by(nb_columns)
+                .all(|ix| data[ix] < *c)
+                || to_south
+                    .into_iter()
+                    .step_by(nb_columns)
+                    .all(|ix| data[ix] < *c)
+                || to_west.into_iter().all(|ix| data[ix] < *c)
+                || to_east.into_iter().all(|ix| data[ix] < *c)
+        })
+        .collect()
+}
+
+fn count_visible_trees(data: &[u8], map_size: (usize, usize)) -> usize {
+    visibility(data, map_size)
+        .into_iter()
+        .filter(|visible| *visible)
+        .count()
+}
+
+/// Multiplies the score of each tree of a line (given by the indices of its trees in `data`) by
+/// its viewing distance towards the start of the line: up to the first tree at least as tall, or
+/// the edge.
+///
+/// The trees that may still block the view are kept on a stack, from the tallest to the
+/// shortest, so that each tree is pushed and popped once.
+fn look_back(data: &[u8], line: &[usize], scores: &mut [u32]) {
+    let mut blocking: Vec<usize> = Vec::with_capacity(line.len());
+    for (position, &ix) in line.iter().enumerate() {
+        while blocking.last().is_some_and(|&p| data[line[p]] < data[ix]) {
+            blocking.pop();
+        }
+        let distance = blocking.last().map_or(position, |&p| position - p);
+        scores[ix] *= distance as u32;
+        blocking.push(position);
+    }
+}
+
+/// Scenic score of each tree.
+fn scenic_scores(data: &[u8], map_size: (usize, usize)) -> Vec<u32> {
+    let (nb_rows, nb_columns) = map_size;
+    let mut scores = vec![1; data.len()];
+    let rows = (0..nb_rows).map(|r| (r * nb_columns..(r + 1) * nb_columns).collect::<Vec<_>>());
+    let columns = (0..nb_columns).map(|c| (c..data.len()).step_by(nb_columns).collect());
+    for mut line in rows.chain(columns) {
+        look_back(data, &line, &mut scores);
+        line.reverse();
+        look_back(data, &line, &mut scores);
+    }
+    scores
+}
+
+fn find_best_spot(data: &[u8], map_size: (usize, usize)) -> u32 {
+    scenic_scores(data, map_size).into_iter().max().unwrap_or(0)
+}
+
+/// Shades of the scenic score heatmap, from the lowest score to the highest.
+const SHADES: &[u8] = b" .:-=+*#%@";
+
+fn draw<T>(cells: &[T], (_, nb_columns): (usize, usize), draw_cell: impl Fn(&T) -> char) -> String {
+    cells
+        .chunks(nb_columns.max(1))
+        .map(|row| row.iter().map(&draw_cell).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Draws the forest with the visible trees as their height and the hidden ones as `.`.
+pub fn draw_visibility((data, map_size): &Forest) -> String {
+    let visibility = visibility(data, *map_size);
+    let cells = data.iter().zip(visibility).collect::<Vec<_>>();
+    draw(&cells, *map_size, |(height, visible)| {
+        if *visible {
+            **height as char
+        } else {
+            '.'
+        }
+    })
+}
+
+/// Draws the scenic score of each tree as a shade, on a logarithmic scale as the best spots
+/// score orders of magnitude higher than the others.
+pub fn draw_scenic_scores((data, map_size): &Forest) -> String {
+    let scores = scenic_scores(data, *map_size);
+    let max = scores.iter().max().map_or(0.0, |m| f64::from(*m).ln_1p());
+    draw(&scores, *map_size, |score| {
+        let level = if max > 0.0 {
+            f64::from(*score).ln_1p() / max
+        } else {
+            0.0
+        };
+        SHADES[(level * (SHADES.len() - 1) as f64).round() as usize] as char
+    })
+}
+
+/// Tree heights row after row, and the size of the map as (rows, columns).
+pub type Forest = (Vec<u8>, (usize, usize));
+
+/// The forest, checked to be a rectangle of tree heights.
+pub fn parse(data: &str) -> Result<Forest> {
+    let rows = data
+        .lines()
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    let nb_rows = rows.len();
+    let nb_columns = rows.first().map_or(0, |row| row.len());
+    for row in &rows {
+        if let Some((j, c)) = row.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+            let bad = &row[j..j + c.len_utf8()];
+            return Err(BadInput::at(data, bad, "not a tree height").into());
+        }
+        if row.len() != nb_columns {
+            let kind = format!("{} trees wide instead of {nb_columns}", row.len());
+            return Err(BadInput::at(data, row, kind).into());
+        }
+    }
+
+    Ok((rows.concat().into_bytes(), (nb_rows, nb_columns)))
+}
+
+pub fn solve((data, map_size): &Forest) -> Result<(usize, u32)> {
+    let visible_trees = count_visible_trees(data, *map_size);
+    let best_spot = find_best_spot(data, *map_size);
+
+    Ok((visible_trees, best_spot))
+}
+
+pub fn find_best_spot_for_tree_house_from_str(data: &str) -> Result<(usize, u32)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::diagnostics;
+    use crate::error::AocError;
+    use insta::assert_snapshot;
+    use spectral::prelude::*;
+
+    #[test]
+    fn best_spot() {
+        let data = "3037325512653323354935390".bytes().collect::<Vec<u8>>();
+
+        let score = find_best_spot(&data, (5, 5));
+
+        assert_eq!(score, 8);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(find_best_spot_for_tree_house_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((21, 8));
+    }
+
+    #[test]
+    fn many_visible_trees() {
+        let data = vec![b'5'; 70_000];
+
+        assert_that!(count_visible_trees(&data, (1, 70_000))).is_equal_to(70_000);
+    }
+
+    #[test]
+    fn ragged_forest() {
+        let forest = parse("123\n12");
+
+        assert!(matches!(&forest, Err(AocError::Parse(e)) if e.offset() == 4));
+        assert_that!(diagnostics::describe(&forest.unwrap_err()))
+            .contains("2 trees wide instead of 3");
+    }
+
+    #[test]
+    fn not_a_tree_height() {
+        let forest = parse("123\n1x3");
+
+        assert!(matches!(&forest, Err(AocError::Parse(e)) if e.offset() == 5));
+        assert_that!(diagnostics::describe(&forest.unwrap_err())).contains("not a tree height");
+    }
+
+    #[test]
+    fn example_scenic_scores() -> anyhow::Result<()> {
+        let (data, map_size) = parse(EXAMPLE)?;
+
+        let scores = scenic_scores(&data, map_size);
+
+        assert_that!(scores[7]).is_equal_to(4);
+        assert_that!(scores[17]).is_equal_to(8);
+        assert_that!(scores.iter().filter(|s| **s > 0).count()).is_equal_to(9);
+        Ok(())
+    }
+
+    #[test]
+    fn visibility_drawing() -> anyhow::Result<()> {
+        let forest = parse(EXAMPLE)?;
+
+        assert_snapshot!(draw_visibility(&forest));
+        Ok(())
+    }
+
+    #[test]
+    fn scenic_scores_drawing() -> anyhow::Result<()> {
+        let forest = parse(EXAMPLE)?;
+
+        let drawing = draw_scenic_scores(&forest);
+
+        // trees on the edges see nothing in one direction, the best spot is at row 3, column 2
+        assert_that!(drawing.lines().next())
+            .is_some()
+            .is_equal_to("     ");
+        assert_that!(drawing.lines().nth(3).and_then(|row| row.chars().nth(2)))
+            .is_some()
+            .is_equal_to('@');
+        assert_snapshot!(drawing);
+        Ok(())
+    }
+}
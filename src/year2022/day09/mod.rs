@@ -0,0 +1,491 @@
+use crate::diagnostics::parse_all;
+use crate::error::Result;
+use nom::{
+    branch::alt,
+    character::complete::{char, line_ending, u32},
+    combinator::{eof, map},
+    error::ParseError,
+    multi::many1,
+    sequence::{separated_pair, terminated},
+    IResult,
+};
+use nom_locate::LocatedSpan;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+use tracing::{debug, trace};
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+/// A move of the head of the rope, by a number of steps.
+#[derive(Debug, PartialEq)]
+pub enum Move {
+    Up(u32),
+    Down(u32),
+    Right(u32),
+    Left(u32),
+}
+
+impl Move {
+    fn on_x(val: i64) -> Self {
+        if val < 0 {
+            Move::Left(val.unsigned_abs() as u32)
+        } else {
+            Move::Right(val.unsigned_abs() as u32)
+        }
+    }
+
+    fn on_y(val: i64) -> Self {
+        if val < 0 {
+            Move::Down(val.unsigned_abs() as u32)
+        } else {
+            Move::Up(val.unsigned_abs() as u32)
+        }
+    }
+
+    fn distance(&self) -> u32 {
+        match self {
+            Move::Up(d) | Move::Down(d) | Move::Left(d) | Move::Right(d) => *d,
+        }
+    }
+
+    fn small_step(mv: &Self) -> Self {
+        match mv {
+            Move::Up(d) => Move::Up(u32::from(*d != 0)),
+            Move::Down(d) => Move::Down(u32::from(*d != 0)),
+            Move::Left(d) => Move::Left(u32::from(*d != 0)),
+            Move::Right(d) => Move::Right(u32::from(*d != 0)),
+        }
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Move::Up(d) => write!(f, "upwards by {}", d),
+            Move::Down(d) => write!(f, "downwards by {}", d),
+            Move::Left(d) => write!(f, "to the left by {}", d),
+            Move::Right(d) => write!(f, "to the right by {}", d),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+struct Position((i64, i64));
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl Position {
+    #[allow(dead_code)]
+    fn new(point: (i64, i64)) -> Self {
+        Self(point)
+    }
+
+    fn x(&self) -> i64 {
+        self.0 .0
+    }
+
+    fn y(&self) -> i64 {
+        self.0 .1
+    }
+
+    fn move_to(&mut self, mv: &Move) {
+        match mv {
+            Move::Up(d) => self.0 = (self.x(), self.y() + i64::from(*d)),
+            Move::Down(d) => self.0 = (self.x(), self.y() - i64::from(*d)),
+            Move::Left(d) => self.0 = (self.x() - i64::from(*d), self.y()),
+            Move::Right(d) => self.0 = (self.x() + i64::from(*d), self.y()),
+        }
+    }
+
+    /// Takes a single step towards the target, unless it is already touching it.
+    ///
+    /// Knots are moved after each step of the head, so they are never more than one step behind.
+    fn move_next_to(&mut self, target: &Self) {
+        if !self.is_around(target) {
+            self.path_to(target).iter().for_each(|mv| self.move_to(mv));
+        }
+    }
+
+    fn path_to(&self, other: &Self) -> Vec<Move> {
+        match (other.x() - self.x(), other.y() - self.y()) {
+            (0, 0) => vec![],
+            (0, y) => vec![Move::small_step(&Move::on_y(y))],
+            (x, 0) => vec![Move::small_step(&Move::on_x(x))],
+            (x, y) => vec![
+                Move::small_step(&Move::on_x(x)),
+                Move::small_step(&Move::on_y(y)),
+            ],
+        }
+    }
+
+    fn is_around(&self, other: &Self) -> bool {
+        (self.x() - other.x()).abs() <= 1 && (self.y() - other.y()).abs() <= 1
+    }
+}
+
+fn move_statement<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Move, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    map(
+        separated_pair(
+            alt((char('R'), char('U'), char('D'), char('L'))),
+            char(' '),
+            u32,
+        ),
+        |(direction, distance)| match direction {
+            'R' => Move::Right(distance),
+            'D' => Move::Down(distance),
+            'U' => Move::Up(distance),
+            'L' => Move::Left(distance),
+            _ => panic!("Should not happen as the parser only accept R,U,L,D"),
+        },
+    )(i)
+}
+
+/// The moves of the head, one per line like `R 4`.
+pub fn moves<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Vec<Move>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    many1(terminated(move_statement, alt((line_ending, eof))))(i)
+}
+
+const SHORT_ROPE_SIZE: usize = 2;
+/// Number of knots of the rope of part 2.
+pub const LONG_ROPE_SIZE: usize = 10;
+const HEAD_INDEX: usize = 0;
+
+/// Knots of the rope after each step of the head, starting with all of them at the origin.
+fn rope_steps(head_moves: &[Move], rope_size: usize) -> impl Iterator<Item = Vec<Position>> + '_ {
+    let mut rope: Vec<Position> = vec![Position::default(); rope_size];
+    let steps = head_moves.iter().flat_map(|mv| {
+        trace!("moving head {}", mv);
+        (0..mv.distance()).map(move |_| Move::small_step(mv))
+    });
+    std::iter::once(rope.clone()).chain(steps.map(move |step| {
+        if let Some(head) = rope.get_mut(HEAD_INDEX) {
+            head.move_to(&step);
+        }
+        for i in 1..rope.len() {
+            let local_head = rope[i - 1];
+            rope[i].move_next_to(&local_head);
+        }
+        trace!(?rope, "head moved");
+        rope.clone()
+    }))
+}
+
+/// Number of positions visited by the last knot of the rope, none for a rope without knots.
+fn move_rope(head_moves: &[Move], rope_size: usize) -> usize {
+    let tail_visits = rope_steps(head_moves, rope_size)
+        .filter_map(|rope| rope.last().copied())
+        .collect::<BTreeSet<_>>();
+    debug!(rope_size, "tail visited {} positions", tail_visits.len());
+    tail_visits.len()
+}
+
+/// Half the size of the part of the grid drawn around the head.
+const VIEW_RADIUS: (i64, i64) = (30, 12);
+
+fn knot_symbol(index: usize, rope_size: usize) -> char {
+    match index {
+        HEAD_INDEX => 'H',
+        _ if rope_size == SHORT_ROPE_SIZE => 'T',
+        _ => char::from_digit(index as u32, 36).unwrap_or('?'),
+    }
+}
+
+/// Draws the rope and the positions visited by its tail, in the puzzle statement notation, on
+/// the part of the grid around the head.
+fn frame(rope: &[Position], visited: &BTreeSet<Position>) -> String {
+    let head = rope[HEAD_INDEX];
+    let mut frame = String::new();
+    for y in (head.y() - VIEW_RADIUS.1..=head.y() + VIEW_RADIUS.1).rev() {
+        for x in head.x() - VIEW_RADIUS.0..=head.x() + VIEW_RADIUS.0 {
+            let position = Position((x, y));
+            frame.push(match rope.iter().position(|knot| *knot == position) {
+                Some(i) => knot_symbol(i, rope.len()),
+                None if position == Position::default() => 's',
+                None if visited.contains(&position) => '#',
+                None => '.',
+            });
+        }
+        frame.push('\n');
+    }
+    frame
+}
+
+/// Animates the rope on `out` (a terminal), redrawing it after each step of the head and waiting
+/// for `delay` between steps. The rope needs at least a head and a tail.
+pub fn animate(
+    head_moves: &[Move],
+    rope_size: usize,
+    delay: Duration,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    if rope_size < SHORT_ROPE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("a rope needs at least {SHORT_ROPE_SIZE} knots, not {rope_size}"),
+        ));
+    }
+    let mut visited = BTreeSet::new();
+    for (step, rope) in rope_steps(head_moves, rope_size).enumerate() {
+        visited.extend(rope.last());
+        // clear the screen and draw from its top left corner
+        write!(out, "\x1b[2J\x1b[H{}", frame(&rope, &visited))?;
+        writeln!(out, "step {step}, tail visited {} positions", visited.len())?;
+        out.flush()?;
+        thread::sleep(delay);
+    }
+    Ok(())
+}
+
+pub fn parse(data: &str) -> Result<Vec<Move>> {
+    let moves = parse_all(data, moves)?;
+    Ok(moves)
+}
+
+pub fn solve(moves: &[Move]) -> Result<(usize, usize)> {
+    Ok((
+        move_rope(moves, SHORT_ROPE_SIZE),
+        move_rope(moves, LONG_ROPE_SIZE),
+    ))
+}
+
+pub fn nb_tail_positions_from_str(data: &str) -> Result<(usize, usize)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use parameterized::parameterized;
+    use spectral::prelude::*;
+
+    #[test]
+    fn parse_moves() -> anyhow::Result<()> {
+        let commands = "U 3\nR 1\nD 2\nL 4\n";
+
+        let (_, moves) = moves::<()>(Span::new(commands))?;
+
+        assert_eq!(
+            moves,
+            vec![Move::Up(3), Move::Right(1), Move::Down(2), Move::Left(4)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_long_moves() -> anyhow::Result<()> {
+        let (_, moves) = moves::<()>(Span::new("U 300\nL 4000000000\n"))?;
+
+        assert_eq!(moves, vec![Move::Up(300), Move::Left(4_000_000_000)]);
+        Ok(())
+    }
+
+    #[test]
+    fn far_away() {
+        let mut position = Position::default();
+        position.move_to(&Move::Left(4_000_000_000));
+        position.move_to(&Move::Left(4_000_000_000));
+
+        assert_that!(position).is_equal_to(Position::new((-8_000_000_000, 0)));
+    }
+
+    #[parameterized(
+        mv = {
+            &Move::Up(3), &Move::Down(5), &Move::Left(10), &Move::Right(1)
+        },
+        expected_position = {
+            &Position::new((0, 3)), &Position::new((0, -5)), &Position::new((-10, 0)), &Position::new((1, 0))
+        })
+    ]
+    fn move_position(mv: &Move, expected_position: &Position) {
+        let mut position = Position::default();
+
+        position.move_to(mv);
+
+        assert_eq!(position, *expected_position);
+    }
+
+    #[parameterized(
+        other = {
+            &Position::new((0, 1)),
+            &Position::new((1, 1)),
+            &Position::new((1, 0)),
+            &Position::new((0, 0)),
+            &Position::new((0, -1)),
+            &Position::new((-1, 0)),
+            &Position::new((1, -1)),
+            &Position::new((-1, 1)),
+            &Position::new((-1, -1)),
+        }
+    )]
+    fn are_around(other: &Position) {
+        let position = Position::default();
+        assert!(
+            other.is_around(&position),
+            "{:?} is not around {:?}",
+            other,
+            position
+        );
+    }
+
+    #[parameterized(
+        other = {
+            &Position::new((0, 2)),
+            &Position::new((5, 1)),
+            &Position::new((1, 2)),
+            &Position::new((6, 0)),
+            &Position::new((1, -2)),
+            &Position::new((-2, -2)),
+            &Position::new((4, 0)),
+            &Position::new((-2, 1)),
+            &Position::new((-1, -2)),
+        }
+    )]
+    fn are_not_around(other: &Position) {
+        let position = Position::default();
+        assert!(
+            !other.is_around(&position),
+            "{:?} is around {:?}",
+            other,
+            position
+        );
+    }
+
+    #[parameterized(
+        target = {
+            &Position::new((2,0)), &Position::new((2,1)),  &Position::new((2,2)),  &Position::new((1,2)),
+            &Position::new((0,2)), &Position::new((-1,2)), &Position::new((-2,2)), &Position::new((-2,1)),
+            &Position::new((-2,0)),&Position::new((-2,-1)),&Position::new((-2,-2)),&Position::new((-1,-2)),
+            &Position::new((0,-2)),&Position::new((1,-2)), &Position::new((2,-2)), &Position::new((2,-1)),
+        },
+        expected_position = {
+            &Position::new((1,0)), &Position::new((1,1)),  &Position::new((1,1)),  &Position::new((1,1)),
+            &Position::new((0,1)), &Position::new((-1,1)), &Position::new((-1,1)), &Position::new((-1,1)),
+            &Position::new((-1,0)),&Position::new((-1,-1)),&Position::new((-1,-1)),&Position::new((-1,-1)),
+            &Position::new((0,-1)),&Position::new((1,-1)), &Position::new((1,-1)), &Position::new((1,-1)),
+        }
+    )]
+    fn should_move_next_to(target: &Position, expected_position: &Position) {
+        let mut to_move = Position::default();
+
+        to_move.move_next_to(target);
+
+        assert_eq!(to_move, *expected_position);
+    }
+
+    #[test]
+    fn knots_catch_up_diagonally() {
+        // a long move of the head must not let the middle knots jump: each follows step by step
+        let moves = vec![Move::Right(4), Move::Up(4)];
+
+        assert_eq!(move_rope(&moves, LONG_ROPE_SIZE), 1);
+        assert_eq!(move_rope(&moves, 3), 5);
+    }
+
+    #[test]
+    fn parse_and_move_example() -> anyhow::Result<()> {
+        let (_, moves) = moves::<()>(Span::new("R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n"))?;
+
+        assert_eq!(move_rope(&moves, SHORT_ROPE_SIZE), 13);
+        assert_eq!(move_rope(&moves, LONG_ROPE_SIZE), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn move_small_rope_test() {
+        let moves = vec![
+            Move::Right(4),
+            Move::Up(4),
+            Move::Left(3),
+            Move::Down(1),
+            Move::Right(4),
+            Move::Down(1),
+            Move::Left(5),
+            Move::Right(2),
+        ];
+
+        assert_eq!(move_rope(&moves, SHORT_ROPE_SIZE), 13);
+        assert_eq!(move_rope(&moves, LONG_ROPE_SIZE), 1);
+    }
+
+    #[test]
+    fn move_big_rope_test() {
+        let moves = vec![
+            Move::Right(5),
+            Move::Up(8),
+            Move::Left(8),
+            Move::Down(3),
+            Move::Right(17),
+            Move::Down(10),
+            Move::Left(25),
+            Move::Up(20),
+        ];
+
+        assert_eq!(move_rope(&moves, LONG_ROPE_SIZE), 36);
+    }
+
+    #[test]
+    fn ropes_too_short() {
+        let moves = vec![Move::Right(4)];
+
+        assert_eq!(move_rope(&moves, 0), 0);
+        assert_eq!(move_rope(&moves, 1), 5);
+        assert_that!(animate(&moves, 1, Duration::ZERO, &mut io::sink()))
+            .is_err()
+            .matches(|e| e.kind() == io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(nb_tail_positions_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((13, 1));
+    }
+
+    #[test]
+    fn one_rope_per_step() {
+        let moves = vec![Move::Right(4), Move::Up(2)];
+
+        let ropes = rope_steps(&moves, SHORT_ROPE_SIZE).collect::<Vec<_>>();
+
+        assert_that!(ropes).has_length(7);
+        assert_that!(ropes.last())
+            .is_some()
+            .is_equal_to(&vec![Position::new((4, 2)), Position::new((4, 1))]);
+    }
+
+    #[test]
+    fn draw_frame() {
+        let rope = vec![Position::new((2, 1)), Position::new((1, 0))];
+        let visited = BTreeSet::from([Position::default(), Position::new((1, 0))]);
+
+        let frame = frame(&rope, &visited);
+        let lines = frame.lines().collect::<Vec<_>>();
+        let center = VIEW_RADIUS.0 as usize;
+
+        assert_that!(lines).has_length(2 * VIEW_RADIUS.1 as usize + 1);
+        let row = |y: i64| lines[(VIEW_RADIUS.1 - y + 1) as usize];
+        assert_that!(row(1).get(center - 2..=center))
+            .is_some()
+            .is_equal_to("..H");
+        assert_that!(row(0).get(center - 2..=center))
+            .is_some()
+            .is_equal_to("sT.");
+    }
+}
@@ -0,0 +1,452 @@
+use crate::answer::Answer;
+use crate::diagnostics::parse_all;
+use crate::error::Result;
+use image::{GrayImage, Luma};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{i32, line_ending},
+    combinator::{eof, map},
+    error::ParseError,
+    multi::many1,
+    sequence::{delimited, terminated},
+    IResult,
+};
+use nom_locate::LocatedSpan;
+use std::iter;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+/// An instruction of the CPU.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+impl Instruction {
+    /// Number of cycles the instruction takes to complete.
+    fn cycles(&self) -> usize {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::Addx(_) => 2,
+        }
+    }
+}
+
+/// The CPU of the device, emulated cycle by cycle.
+#[derive(Clone, Debug)]
+pub struct Cpu {
+    program: Vec<Instruction>,
+    /// Index of the instruction being executed.
+    pointer: usize,
+    /// Cycles already spent on the instruction being executed.
+    spent: usize,
+    /// Number of completed cycles.
+    cycles: usize,
+    x: i32,
+}
+
+impl Default for Cpu {
+    fn default() -> Self {
+        Self {
+            program: Vec::new(),
+            pointer: 0,
+            spent: 0,
+            cycles: 0,
+            x: 1,
+        }
+    }
+}
+
+impl Cpu {
+    /// Resets the CPU to run `program` from its first cycle.
+    pub fn load(&mut self, program: &[Instruction]) {
+        *self = Self {
+            program: program.to_vec(),
+            ..Self::default()
+        };
+    }
+
+    /// Number of the current cycle, starting from 1.
+    pub fn cycle(&self) -> usize {
+        self.cycles + 1
+    }
+
+    /// Value of the X register during the current cycle.
+    pub fn register_x(&self) -> i32 {
+        self.x
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pointer >= self.program.len()
+    }
+
+    /// Completes the current cycle, returning false if the program was already over.
+    pub fn step(&mut self) -> bool {
+        let Some(instruction) = self.program.get(self.pointer) else {
+            return false;
+        };
+        self.spent += 1;
+        if self.spent == instruction.cycles() {
+            if let Instruction::Addx(v) = instruction {
+                self.x += v;
+            }
+            self.pointer += 1;
+            self.spent = 0;
+        }
+        self.cycles += 1;
+        true
+    }
+
+    /// Runs the program to its end, yielding the number of each cycle and the value of the X
+    /// register during it.
+    pub fn run(mut self) -> impl Iterator<Item = (usize, i32)> {
+        iter::from_fn(move || {
+            let during = (self.cycle(), self.register_x());
+            self.step().then_some(during)
+        })
+    }
+}
+
+fn cpu(program: &[Instruction]) -> Cpu {
+    let mut cpu = Cpu::default();
+    cpu.load(program);
+    cpu
+}
+
+fn noop_instruction<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Instruction, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    map(terminated(tag("noop"), alt((line_ending, eof))), |_| {
+        Instruction::Noop
+    })(i)
+}
+
+fn addx_instruction<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Instruction, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    map(
+        delimited(tag("addx "), i32, alt((line_ending, eof))),
+        Instruction::Addx,
+    )(i)
+}
+
+/// The program, one instruction per line.
+pub fn instructions<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Vec<Instruction>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    many1(alt((noop_instruction, addx_instruction)))(i)
+}
+
+const FIRST_SIGNAL_IDX: usize = 20;
+const SIGNAL_PERIOD: usize = 40;
+
+fn compute_signal_strength(program: &[Instruction]) -> i32 {
+    cpu(program)
+        .run()
+        .filter(|(cycle, _)| *cycle >= FIRST_SIGNAL_IDX)
+        .filter(|(cycle, _)| (cycle - FIRST_SIGNAL_IDX).is_multiple_of(SIGNAL_PERIOD))
+        .map(|(cycle, x)| cycle as i32 * x)
+        .sum()
+}
+
+/// Characters drawn for the pixels of the CRT.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CrtStyle {
+    pub lit: char,
+    pub dark: char,
+}
+
+impl Default for CrtStyle {
+    /// The `#` and `.` of the puzzle statement.
+    fn default() -> Self {
+        Self {
+            lit: '#',
+            dark: '.',
+        }
+    }
+}
+
+impl CrtStyle {
+    /// Full blocks on blanks, easier to read in a terminal.
+    pub const BLOCKS: Self = Self {
+        lit: '█',
+        dark: ' ',
+    };
+}
+
+fn is_lit(index: usize, register_x: i32) -> bool {
+    let sprite_index: i32 = (index % SIGNAL_PERIOD) as i32;
+    register_x == sprite_index - 1 || register_x == sprite_index || register_x == sprite_index + 1
+}
+
+/// Renders the CRT screen, one line per row of pixels.
+pub fn crt_display(program: &[Instruction]) -> String {
+    styled_crt_display(program, CrtStyle::default())
+}
+
+/// Renders the CRT screen like [`crt_display`], drawing its pixels with the characters of
+/// `style`.
+pub fn styled_crt_display(program: &[Instruction], style: CrtStyle) -> String {
+    let mut screen = String::new();
+    for (cycle, x) in cpu(program).run() {
+        let i = cycle - 1;
+        if i > 0 && i % SIGNAL_PERIOD == 0 {
+            screen.push('\n');
+        }
+        screen.push(if is_lit(i, x) { style.lit } else { style.dark });
+    }
+    screen
+}
+
+/// Capital letters drawn on the CRT, each 4 pixels wide and 6 high, row after row.
+const GLYPHS: [(char, &str); 17] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+const GLYPH_WIDTH: usize = 4;
+/// Glyphs are separated by a column of dark pixels.
+const GLYPH_SPACING: usize = 1;
+
+/// Reads the capital letters drawn on a CRT screen rendered by [`crt_display`], if it only
+/// shows letters.
+pub fn read_letters(screen: &str) -> Option<String> {
+    let rows = screen.lines().map(str::as_bytes).collect::<Vec<_>>();
+    let width = rows.first()?.len();
+    if rows.len() != 6 || rows.iter().any(|row| row.len() != width) {
+        return None;
+    }
+    (0..width)
+        .step_by(GLYPH_WIDTH + GLYPH_SPACING)
+        .map(|left| {
+            let glyph = rows
+                .iter()
+                .map(|row| row.get(left..left + GLYPH_WIDTH))
+                .collect::<Option<Vec<_>>>()?
+                .concat();
+            GLYPHS
+                .iter()
+                .find(|(_, pixels)| pixels.as_bytes() == glyph)
+                .map(|(letter, _)| *letter)
+        })
+        .collect()
+}
+
+/// Side of the square drawn for each pixel of the CRT in an image.
+const PIXEL_SCALE: u32 = 10;
+const LIT: Luma<u8> = Luma([0]);
+const DARK: Luma<u8> = Luma([255]);
+
+/// Rasterizes a CRT screen rendered by [`crt_display`], lit pixels drawn in black on white.
+pub fn crt_image(screen: &str) -> GrayImage {
+    let rows = screen.lines().collect::<Vec<_>>();
+    let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0) as u32;
+    GrayImage::from_fn(
+        width * PIXEL_SCALE,
+        rows.len() as u32 * PIXEL_SCALE,
+        |x, y| match rows[(y / PIXEL_SCALE) as usize]
+            .chars()
+            .nth((x / PIXEL_SCALE) as usize)
+        {
+            Some('#') => LIT,
+            _ => DARK,
+        },
+    )
+}
+
+pub fn parse(data: &str) -> Result<Vec<Instruction>> {
+    let program = parse_all(data, instructions)?;
+    Ok(program)
+}
+
+/// The signal strength, and the letters shown on the CRT (or the screen itself when it does not
+/// only show letters, like in the example).
+pub fn solve(program: &[Instruction]) -> Result<(i32, Answer)> {
+    let screen = crt_display(program);
+    let letters = read_letters(&screen).map_or_else(|| Answer::grid(&screen), Answer::Text);
+    Ok((compute_signal_strength(program), letters))
+}
+
+pub fn sum_of_signal_strengths_from_str(data: &str) -> Result<(i32, Answer)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use insta::assert_snapshot;
+    use spectral::prelude::*;
+
+    /// What a parser made of its input, with what is left of it.
+    fn fragments<O>(result: IResult<Span<'_>, O, ()>) -> IResult<&str, O, ()> {
+        result.map(|(rest, o)| (*rest.fragment(), o))
+    }
+
+    #[test]
+    fn parse_noop() {
+        let noop = fragments(noop_instruction(Span::new("noop")));
+
+        assert_that!(noop)
+            .is_ok()
+            .is_equal_to(("", Instruction::Noop));
+    }
+
+    #[test]
+    fn parse_addx_positive() {
+        let addx = fragments(addx_instruction(Span::new("addx 12")));
+
+        assert_that!(addx)
+            .is_ok()
+            .is_equal_to(("", Instruction::Addx(12)));
+    }
+
+    #[test]
+    fn parse_addx_negative() {
+        let addx = fragments(addx_instruction(Span::new("addx -42")));
+
+        assert_that!(addx)
+            .is_ok()
+            .is_equal_to(("", Instruction::Addx(-42)));
+    }
+
+    #[test]
+    fn parse_instructions() {
+        let data = "noop\naddx 3\nnoop\nnoop\naddx -3";
+        let program = fragments(instructions(Span::new(data)));
+
+        assert_that!(program).is_ok().is_equal_to(&(
+            "",
+            vec![
+                Instruction::Noop,
+                Instruction::Addx(3),
+                Instruction::Noop,
+                Instruction::Noop,
+                Instruction::Addx(-3),
+            ],
+        ));
+    }
+
+    #[test]
+    fn small_program() {
+        let mut cpu = cpu(&[
+            Instruction::Noop,
+            Instruction::Addx(3),
+            Instruction::Addx(-5),
+        ]);
+        let mut during = vec![(cpu.cycle(), cpu.register_x())];
+        while cpu.step() {
+            during.push((cpu.cycle(), cpu.register_x()));
+        }
+
+        assert_that!(during).is_equal_to(vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4), (6, -1)]);
+        assert_that!(cpu.is_halted()).is_true();
+    }
+
+    #[test]
+    fn probe_cycles() -> anyhow::Result<()> {
+        let program = parse(EXAMPLE)?;
+        let mut cpu = Cpu::default();
+        cpu.load(&program);
+        while cpu.cycle() < 220 {
+            cpu.step();
+        }
+
+        assert_that!(cpu.register_x()).is_equal_to(18);
+        Ok(())
+    }
+
+    #[test]
+    fn test_compute_signal_strength() -> anyhow::Result<()> {
+        let program = parse(EXAMPLE)?;
+        let res = compute_signal_strength(&program);
+
+        assert_that!(res).is_equal_to(13140i32);
+        Ok(())
+    }
+
+    #[test]
+    fn test_crt_display() -> anyhow::Result<()> {
+        let program = parse(EXAMPLE)?;
+        let screen = crt_display(&program);
+
+        assert_snapshot!(screen);
+        Ok(())
+    }
+
+    #[test]
+    fn styled_crt() -> anyhow::Result<()> {
+        let program = parse(EXAMPLE)?;
+        let screen = styled_crt_display(&program, CrtStyle::BLOCKS);
+
+        assert_that!(screen.lines().next())
+            .is_some()
+            .is_equal_to("██  ██  ██  ██  ██  ██  ██  ██  ██  ██  ");
+        assert_that!(screen.replace('█', "#").replace(' ', ".")).is_equal_to(crt_display(&program));
+        Ok(())
+    }
+
+    #[test]
+    fn crt_rasterized() {
+        let image = crt_image("#.\n.#");
+
+        assert_that!(image.dimensions()).is_equal_to((2 * PIXEL_SCALE, 2 * PIXEL_SCALE));
+        assert_that!(*image.get_pixel(0, 0)).is_equal_to(LIT);
+        assert_that!(*image.get_pixel(PIXEL_SCALE, 0)).is_equal_to(DARK);
+        assert_that!(*image.get_pixel(PIXEL_SCALE * 2 - 1, PIXEL_SCALE * 2 - 1)).is_equal_to(LIT);
+    }
+
+    #[test]
+    fn letters() {
+        let screen = "\
+###..#..#..##...##...##..###..#..#.####.
+#..#.#..#.#..#.#..#.#..#.#..#.#..#....#.
+###..#..#.#....#..#.#....###..#..#...#..
+#..#.#..#.#....####.#....#..#.#..#..#...
+#..#.#..#.#..#.#..#.#..#.#..#.#..#.#....
+###...##...##..#..#..##..###...##..####.";
+
+        assert_that!(read_letters(screen))
+            .is_some()
+            .is_equal_to("BUCACBUZ".to_owned());
+    }
+
+    #[test]
+    fn not_letters() -> anyhow::Result<()> {
+        let program = parse(EXAMPLE)?;
+
+        assert_that!(read_letters(&crt_display(&program))).is_none();
+        Ok(())
+    }
+
+    #[test]
+    fn example_answers() -> anyhow::Result<()> {
+        let (strength, screen) = sum_of_signal_strengths_from_str(EXAMPLE)?;
+
+        assert_that!(strength).is_equal_to(13140);
+        assert_that!(screen).is_equal_to(Answer::grid(&crt_display(&parse(EXAMPLE)?)));
+        Ok(())
+    }
+}
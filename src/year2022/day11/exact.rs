@@ -0,0 +1,106 @@
+//! The simulation of the monkeys with the exact worry levels, however large they grow, to check
+//! the [`WorryStrategy`](super::WorryStrategy) shortcuts against or to try other rules.
+
+use super::{check, Error, Monkey, Operation, Term};
+use num_bigint::BigUint;
+use std::collections::VecDeque;
+
+fn eval_term(term: Term, old: &BigUint) -> BigUint {
+    match term {
+        Term::Old => old.clone(),
+        Term::Constant(c) => BigUint::from(c),
+    }
+}
+
+/// The new worry level, or `None` if it is negative or divided by zero.
+pub fn eval(operation: Operation, old: &BigUint) -> Option<BigUint> {
+    let (l, r) = match operation {
+        Operation::Add(l, r)
+        | Operation::Sub(l, r)
+        | Operation::Mul(l, r)
+        | Operation::Div(l, r)
+        | Operation::Mod(l, r) => (eval_term(l, old), eval_term(r, old)),
+    };
+    match operation {
+        Operation::Add(..) => Some(l + r),
+        Operation::Sub(..) => (l >= r).then(|| l - r),
+        Operation::Mul(..) => Some(l * r),
+        Operation::Div(..) => (r != BigUint::ZERO).then(|| l / r),
+        Operation::Mod(..) => (r != BigUint::ZERO).then(|| l % r),
+    }
+}
+
+/// Number of items each monkey inspects over `n` rounds, worry levels being divided by 3 after
+/// each inspection if `relief`.
+pub fn inspections(monkeys: &[Monkey], n: u16, relief: bool) -> Result<Vec<u64>, Error> {
+    check(monkeys)?;
+    let mut queues = monkeys
+        .iter()
+        .map(|m| m.items.iter().map(|item| BigUint::from(item.0)).collect())
+        .collect::<Vec<VecDeque<_>>>();
+    let mut nb_item_inspections = vec![0u64; monkeys.len()];
+
+    for _ in 0..n {
+        for (mk, monkey) in monkeys.iter().enumerate() {
+            let nb_items = queues[mk].len();
+            nb_item_inspections[mk] += nb_items as u64;
+            for _ in 0..nb_items {
+                if let Some(worry) = queues[mk].pop_front() {
+                    let mut worry =
+                        eval(monkey.operation, &worry).ok_or(Error::Undefined(monkey.id.0))?;
+                    if relief {
+                        worry /= 3u8;
+                    }
+                    let decision = &monkey.throw_decision;
+                    let throw_to = if &worry % decision.modulus == BigUint::ZERO {
+                        decision.if_true
+                    } else {
+                        decision.if_false
+                    };
+                    queues[throw_to.0 as usize].push_back(worry);
+                }
+            }
+        }
+    }
+    Ok(nb_item_inspections)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::super::{parse, rounds, DivideByThree, ModuloDivisors, EXAMPLE};
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn same_as_relief() {
+        let monkeys = parse(EXAMPLE).unwrap();
+
+        assert_that!(inspections(&monkeys, 20, true))
+            .is_ok()
+            .is_equal_to(rounds(&monkeys, 20, &DivideByThree).unwrap());
+    }
+
+    #[test]
+    fn same_as_modulo_divisors() {
+        let monkeys = parse(EXAMPLE).unwrap();
+        let strategy = ModuloDivisors::new(&monkeys).unwrap();
+
+        assert_that!(inspections(&monkeys, 20, false))
+            .is_ok()
+            .is_equal_to(rounds(&monkeys, 20, &strategy).unwrap());
+    }
+
+    #[test]
+    fn large_worry() {
+        let old = BigUint::from(u64::MAX);
+
+        let new = eval(Operation::Mul(Term::Old, Term::Old), &old);
+
+        assert_that!(new)
+            .is_some()
+            .is_greater_than(BigUint::from(u64::MAX));
+        assert_that!(eval(Operation::Sub(Term::Constant(1), Term::Old), &old)).is_none();
+        assert_that!(eval(Operation::Div(Term::Old, Term::Constant(0)), &old)).is_none();
+    }
+}
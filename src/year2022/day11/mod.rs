@@ -0,0 +1,782 @@
+use crate::diagnostics::parse_all;
+use crate::error::{PuzzleError, Result};
+use crate::progress;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, line_ending, one_of, space1, u64, u8},
+    combinator::{eof, map, value},
+    error::ParseError,
+    multi::separated_list1,
+    sequence::{delimited, preceded, terminated, tuple},
+    IResult,
+};
+use nom_locate::LocatedSpan;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use tracing::debug;
+
+#[cfg(feature = "bigint")]
+pub mod exact;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("monkey {monkey} cannot compute the new worry level of an item worth {worry}")]
+    OutOfBounds { monkey: u8, worry: u64 },
+    #[error("monkey {0} divides worry levels, which cannot be kept modulo the tests")]
+    NotModular(u8),
+    #[error("monkey {0} makes a worry level negative or divides it by zero")]
+    Undefined(u8),
+    #[error("monkey {0} tests divisibility by 0")]
+    ZeroDivisor(u8),
+    #[error("monkey {monkey} is noted in place of monkey {position}")]
+    Misplaced { monkey: u8, position: usize },
+    #[error("monkey {monkey} throws to monkey {target}, which is not in the notes")]
+    UnknownTarget { monkey: u8, target: u8 },
+    #[error("the product of the divisors of the tests overflows")]
+    ModulusOverflow,
+}
+
+impl PuzzleError for Error {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operation {
+    Add(Term, Term),
+    Sub(Term, Term),
+    Mul(Term, Term),
+    Div(Term, Term),
+    Mod(Term, Term),
+}
+
+impl Operation {
+    /// The new worry level, or `None` if it is negative, too large or divided by zero.
+    pub fn eval(self, old: u64) -> Option<u64> {
+        match self {
+            Operation::Add(l, r) => l.eval(old).checked_add(r.eval(old)),
+            Operation::Sub(l, r) => l.eval(old).checked_sub(r.eval(old)),
+            Operation::Mul(l, r) => l.eval(old).checked_mul(r.eval(old)),
+            Operation::Div(l, r) => l.eval(old).checked_div(r.eval(old)),
+            Operation::Mod(l, r) => l.eval(old).checked_rem(r.eval(old)),
+        }
+    }
+
+    /// The new worry level modulo `md`, `old` being known modulo `md` only, or `None` if the
+    /// operation does not preserve congruences (division and modulo).
+    pub fn eval_modulo(self, old: u64, md: u64) -> Option<u64> {
+        let (l, r) = match self {
+            Operation::Add(l, r) | Operation::Sub(l, r) | Operation::Mul(l, r) => {
+                (u128::from(l.eval(old) % md), u128::from(r.eval(old) % md))
+            }
+            Operation::Div(..) | Operation::Mod(..) => return None,
+        };
+        let md = u128::from(md);
+        let new = match self {
+            Operation::Add(..) => (l + r) % md,
+            Operation::Sub(..) => (l + md - r) % md,
+            _ => (l * r) % md,
+        };
+        u64::try_from(new).ok()
+    }
+
+    /// Whether worry levels can be kept modulo the tests, see [`ModuloDivisors`].
+    fn is_modular(self) -> bool {
+        !matches!(self, Operation::Div(..) | Operation::Mod(..))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Term {
+    Old,
+    Constant(u64),
+}
+
+impl Term {
+    pub fn eval(self, old: u64) -> u64 {
+        match self {
+            Term::Old => old,
+            Term::Constant(c) => c,
+        }
+    }
+}
+
+/// Rounds watched while worry is relieved (part 1).
+pub const RELIEVED_ROUNDS: u16 = 20;
+/// Rounds watched once worry is no longer relieved (part 2).
+pub const WORRYING_ROUNDS: u16 = 10000;
+/// Number of the most active monkeys whose inspections make the monkey business.
+pub const MOST_ACTIVE: usize = 2;
+
+/// How the monkey business is measured: the product of the number of items the `top` most
+/// active monkeys inspect over `rounds` rounds. Defaults to the measure of part 2.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Business {
+    pub rounds: u16,
+    pub top: usize,
+}
+
+impl Default for Business {
+    fn default() -> Self {
+        Self {
+            rounds: WORRYING_ROUNDS,
+            top: MOST_ACTIVE,
+        }
+    }
+}
+
+/// How worry levels are kept under control after each inspection.
+pub trait WorryStrategy: Debug {
+    /// The worry level of an item after its inspection with `operation`, or `None` if it cannot
+    /// be computed.
+    fn inspect(&self, operation: Operation, worry: u64) -> Option<u64>;
+}
+
+/// Worry is divided by 3, as the item is not damaged (part 1).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DivideByThree;
+
+impl WorryStrategy for DivideByThree {
+    fn inspect(&self, operation: Operation, worry: u64) -> Option<u64> {
+        operation.eval(worry).map(|w| w / 3)
+    }
+}
+
+/// Worry is only kept modulo the product of the divisors of the tests, which preserves every
+/// test (part 2).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModuloDivisors {
+    md: u64,
+}
+
+impl ModuloDivisors {
+    /// Fails if a monkey divides worry levels, as the division of congruences is not one, or
+    /// if the divisors cannot make a modulus.
+    pub fn new(monkeys: &[Monkey]) -> Result<Self, Error> {
+        if let Some(monkey) = monkeys.iter().find(|m| !m.operation.is_modular()) {
+            return Err(Error::NotModular(monkey.id.0));
+        }
+        let md =
+            monkeys
+                .iter()
+                .try_fold(1u64, |md, monkey| match monkey.throw_decision.modulus {
+                    0 => Err(Error::ZeroDivisor(monkey.id.0)),
+                    modulus => md.checked_mul(modulus).ok_or(Error::ModulusOverflow),
+                })?;
+        Ok(Self { md })
+    }
+}
+
+impl WorryStrategy for ModuloDivisors {
+    fn inspect(&self, operation: Operation, worry: u64) -> Option<u64> {
+        operation.eval_modulo(worry, self.md)
+    }
+}
+
+/// Worry is not managed at all: it soon overflows without relief.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Unmanaged;
+
+impl WorryStrategy for Unmanaged {
+    fn inspect(&self, operation: Operation, worry: u64) -> Option<u64> {
+        operation.eval(worry)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Item(u64);
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct MonkeyId(u8);
+
+/// A monkey, as described in the notes.
+#[derive(Debug, PartialEq)]
+pub struct Monkey {
+    id: MonkeyId,
+    items: Vec<Item>,
+    operation: Operation,
+    throw_decision: ThrowDecision,
+}
+
+impl Monkey {
+    fn new(
+        id: MonkeyId,
+        items: &[Item],
+        operation: Operation,
+        throw_decision: ThrowDecision,
+    ) -> Self {
+        Monkey {
+            id,
+            items: items.to_vec(),
+            operation,
+            throw_decision,
+        }
+    }
+
+    pub fn id(&self) -> u8 {
+        self.id.0
+    }
+
+    /// Worry levels of the items the monkey starts with.
+    pub fn items(&self) -> impl Iterator<Item = u64> + '_ {
+        self.items.iter().map(|item| item.0)
+    }
+
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    /// What worry levels are tested to be divisible by.
+    pub fn divisor(&self) -> u64 {
+        self.throw_decision.modulus
+    }
+
+    /// Monkeys the items are thrown to when their worry level is divisible, then when it is not.
+    pub fn targets(&self) -> (u8, u8) {
+        (
+            self.throw_decision.if_true.0,
+            self.throw_decision.if_false.0,
+        )
+    }
+
+    fn inspect_item(
+        &self,
+        item: Item,
+        strategy: &impl WorryStrategy,
+    ) -> Result<(MonkeyId, Item), Error> {
+        let new_worry_value =
+            strategy
+                .inspect(self.operation, item.0)
+                .ok_or(Error::OutOfBounds {
+                    monkey: self.id.0,
+                    worry: item.0,
+                })?;
+
+        Ok((
+            self.throw_decision.take_decision(new_worry_value),
+            Item(new_worry_value),
+        ))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct ThrowDecision {
+    modulus: u64,
+    if_true: MonkeyId,
+    if_false: MonkeyId,
+}
+
+impl ThrowDecision {
+    fn new(modulus: u64, if_true: MonkeyId, if_false: MonkeyId) -> Self {
+        Self {
+            modulus,
+            if_true,
+            if_false,
+        }
+    }
+
+    fn take_decision(&self, item_value: u64) -> MonkeyId {
+        if item_value.is_multiple_of(self.modulus) {
+            self.if_true
+        } else {
+            self.if_false
+        }
+    }
+}
+
+fn monkey_id<'a, E>(i: Span<'a>) -> IResult<Span<'a>, MonkeyId, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    map(
+        delimited(tag("Monkey "), u8, terminated(char(':'), line_ending)),
+        MonkeyId,
+    )(i)
+}
+
+fn items<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Vec<Item>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    map(
+        delimited(
+            preceded(space1, tag("Starting items: ")),
+            separated_list1(tag(", "), u64),
+            line_ending,
+        ),
+        |v| v.into_iter().map(Item).collect::<Vec<_>>(),
+    )(i)
+}
+
+fn term<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Term, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    alt((value(Term::Old, tag("old")), map(u64, Term::Constant)))(i)
+}
+
+fn operation<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Operation, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    let (i, (l, op, r)) = delimited(
+        preceded(space1, tag("Operation: new = ")),
+        tuple((
+            term,
+            preceded(space1, one_of("+-*/%")),
+            preceded(space1, term),
+        )),
+        line_ending,
+    )(i)?;
+    let op = match op {
+        '+' => Operation::Add(l, r),
+        '-' => Operation::Sub(l, r),
+        '*' => Operation::Mul(l, r),
+        '/' => Operation::Div(l, r),
+        '%' => Operation::Mod(l, r),
+        _ => unreachable!(),
+    };
+    Ok((i, op))
+}
+
+fn throw_decision<'a, E>(i: Span<'a>) -> IResult<Span<'a>, ThrowDecision, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    let (rest, modulus) = delimited(
+        preceded(space1, tag("Test: divisible by ")),
+        u64,
+        line_ending,
+    )(i)?;
+    let (rest, if_true) = delimited(
+        preceded(space1, tag("If true: throw to monkey ")),
+        u8,
+        line_ending,
+    )(rest)?;
+    let (rest, if_false) = delimited(
+        preceded(space1, tag("If false: throw to monkey ")),
+        u8,
+        alt((line_ending, eof)),
+    )(rest)?;
+    Ok((
+        rest,
+        ThrowDecision::new(modulus, MonkeyId(if_true), MonkeyId(if_false)),
+    ))
+}
+
+fn monkey<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Monkey, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    let (rest, monkey_id) = monkey_id(i)?;
+    let (rest, items) = items(rest)?;
+    let (rest, operation) = operation(rest)?;
+    let (rest, throw_decision) = throw_decision(rest)?;
+
+    Ok((
+        rest,
+        Monkey::new(monkey_id, &items, operation, throw_decision),
+    ))
+}
+
+/// The notes about the monkeys, separated by empty lines.
+pub fn monkeys<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Vec<Monkey>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    separated_list1(line_ending, monkey)(i)
+}
+
+/// Checks that the monkeys are noted in the order of their ids, test divisibility by something
+/// and only throw to each other.
+fn check(monkeys: &[Monkey]) -> Result<(), Error> {
+    for (position, monkey) in monkeys.iter().enumerate() {
+        if usize::from(monkey.id.0) != position {
+            return Err(Error::Misplaced {
+                monkey: monkey.id.0,
+                position,
+            });
+        }
+        let decision = &monkey.throw_decision;
+        if decision.modulus == 0 {
+            return Err(Error::ZeroDivisor(monkey.id.0));
+        }
+        if let Some(target) = [decision.if_true, decision.if_false]
+            .into_iter()
+            .find(|target| usize::from(target.0) >= monkeys.len())
+        {
+            return Err(Error::UnknownTarget {
+                monkey: monkey.id.0,
+                target: target.0,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn rounds(monkeys: &[Monkey], n: u16, strategy: &impl WorryStrategy) -> Result<Vec<u64>, Error> {
+    check(monkeys)?;
+    let progress = progress::current();
+    // each monkey works through its queue in place, receiving items at the back
+    let mut queues = monkeys
+        .iter()
+        .map(|m| m.items.iter().copied().collect::<VecDeque<_>>())
+        .collect::<Vec<_>>();
+    let mut nb_item_inspections = vec![0u64; monkeys.len()];
+
+    for _ in 0..n {
+        for (mk, monkey) in monkeys.iter().enumerate() {
+            // only the items held at the start of the turn: one thrown to itself waits
+            let nb_items = queues[mk].len();
+            nb_item_inspections[mk] += nb_items as u64;
+            for _ in 0..nb_items {
+                if let Some(item) = queues[mk].pop_front() {
+                    let (throw_to, item) = monkey.inspect_item(item, strategy)?;
+                    queues[throw_to.0 as usize].push_back(item);
+                }
+            }
+        }
+        progress.inc(1);
+    }
+    Ok(nb_item_inspections)
+}
+
+pub fn compute_score(
+    monkeys: &[Monkey],
+    business: Business,
+    strategy: &impl WorryStrategy,
+) -> Result<u64, Error> {
+    let mut inspections = rounds(monkeys, business.rounds, strategy)?;
+    debug!(?strategy, ?inspections, "items inspected by each monkey");
+    inspections.sort_by_key(|&e| std::cmp::Reverse(e));
+    Ok(inspections.iter().take(business.top).product())
+}
+
+pub fn parse(data: &str) -> Result<Vec<Monkey>> {
+    Ok(parse_all(data, monkeys)?)
+}
+
+/// Monkey business with relief, then without.
+pub fn solve(monkeys: &[Monkey]) -> Result<(u64, u64)> {
+    progress::current().set_length((RELIEVED_ROUNDS + WORRYING_ROUNDS).into());
+    Ok((
+        compute_score(
+            monkeys,
+            Business {
+                rounds: RELIEVED_ROUNDS,
+                ..Business::default()
+            },
+            &DivideByThree,
+        )?,
+        compute_score(monkeys, Business::default(), &ModuloDivisors::new(monkeys)?)?,
+    ))
+}
+
+pub fn monkey_business_from_str(data: &str) -> Result<(u64, u64)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn parse_monkey() {
+        let data = Span::new(
+            r#"Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3"#,
+        );
+
+        let monkey = monkey::<nom::error::Error<Span>>(data);
+
+        assert_that!(monkey).is_ok();
+        let monkey = monkey.unwrap().1;
+        assert_that!(monkey).is_equal_to(Monkey::new(
+            MonkeyId(0),
+            &[Item(79), Item(98)],
+            Operation::Mul(Term::Old, Term::Constant(19)),
+            ThrowDecision::new(23, MonkeyId(2), MonkeyId(3)),
+        ));
+    }
+
+    #[test]
+    fn parse_monkey_square() {
+        let data = Span::new(
+            r#"Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * old
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3"#,
+        );
+
+        let monkey = monkey::<nom::error::Error<Span>>(data);
+
+        assert_that!(monkey).is_ok();
+        let monkey = monkey.unwrap().1;
+        assert_that!(monkey).is_equal_to(Monkey::new(
+            MonkeyId(0),
+            &[Item(79), Item(98)],
+            Operation::Mul(Term::Old, Term::Old),
+            ThrowDecision::new(23, MonkeyId(2), MonkeyId(3)),
+        ));
+    }
+
+    #[test]
+    fn parse_monkeys() {
+        let data = Span::new(
+            r#"Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0"#,
+        );
+
+        let monkeys = monkeys::<nom::error::Error<Span>>(data);
+
+        assert_that!(monkeys).is_ok();
+        let monkeys = monkeys.unwrap().1;
+        assert_that!(monkeys).is_equal_to(vec![
+            Monkey::new(
+                MonkeyId(0),
+                &[Item(79), Item(98)],
+                Operation::Mul(Term::Old, Term::Constant(19)),
+                ThrowDecision::new(23, MonkeyId(2), MonkeyId(3)),
+            ),
+            Monkey::new(
+                MonkeyId(1),
+                &[Item(54), Item(65), Item(75), Item(74)],
+                Operation::Add(Term::Old, Term::Constant(6)),
+                ThrowDecision::new(19, MonkeyId(2), MonkeyId(0)),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn items_inspected_after_twenty_rounds() {
+        let monkeys = vec![
+            Monkey::new(
+                MonkeyId(0),
+                &[Item(79), Item(98)],
+                Operation::Mul(Term::Old, Term::Constant(19)),
+                ThrowDecision::new(23, MonkeyId(2), MonkeyId(3)),
+            ),
+            Monkey::new(
+                MonkeyId(1),
+                &[Item(54), Item(65), Item(75), Item(74)],
+                Operation::Add(Term::Old, Term::Constant(6)),
+                ThrowDecision::new(19, MonkeyId(2), MonkeyId(0)),
+            ),
+            Monkey::new(
+                MonkeyId(2),
+                &[Item(79), Item(60), Item(97)],
+                Operation::Mul(Term::Old, Term::Old),
+                ThrowDecision::new(13, MonkeyId(1), MonkeyId(3)),
+            ),
+            Monkey::new(
+                MonkeyId(3),
+                &[Item(74)],
+                Operation::Add(Term::Old, Term::Constant(3)),
+                ThrowDecision::new(17, MonkeyId(0), MonkeyId(1)),
+            ),
+        ];
+
+        let res = rounds(&monkeys, 20, &ModuloDivisors::new(&monkeys).unwrap());
+
+        assert_that!(res).is_ok().is_equal_to(vec![99, 97, 8, 103]);
+
+        let res = rounds(&monkeys, 20, &DivideByThree);
+
+        assert_that!(res).is_ok().is_equal_to(vec![101, 95, 7, 105]);
+    }
+
+    #[test]
+    fn test_compute_score() {
+        let monkeys = vec![
+            Monkey::new(
+                MonkeyId(0),
+                &[Item(79), Item(98)],
+                Operation::Mul(Term::Old, Term::Constant(19)),
+                ThrowDecision::new(23, MonkeyId(2), MonkeyId(3)),
+            ),
+            Monkey::new(
+                MonkeyId(1),
+                &[Item(54), Item(65), Item(75), Item(74)],
+                Operation::Add(Term::Old, Term::Constant(6)),
+                ThrowDecision::new(19, MonkeyId(2), MonkeyId(0)),
+            ),
+            Monkey::new(
+                MonkeyId(2),
+                &[Item(79), Item(60), Item(97)],
+                Operation::Mul(Term::Old, Term::Old),
+                ThrowDecision::new(13, MonkeyId(1), MonkeyId(3)),
+            ),
+            Monkey::new(
+                MonkeyId(3),
+                &[Item(74)],
+                Operation::Add(Term::Old, Term::Constant(3)),
+                ThrowDecision::new(17, MonkeyId(0), MonkeyId(1)),
+            ),
+        ];
+
+        let relieved = Business {
+            rounds: 20,
+            ..Business::default()
+        };
+        assert_that!(compute_score(&monkeys, relieved, &DivideByThree))
+            .is_ok()
+            .is_equal_to(10605);
+        assert_that!(compute_score(
+            &monkeys,
+            Business::default(),
+            &ModuloDivisors::new(&monkeys).unwrap()
+        ))
+        .is_ok()
+        .is_equal_to(2713310158);
+        assert_that!(compute_score(&monkeys, Business::default(), &Unmanaged)).is_err();
+        // 105 * 101 * 95 items inspected by the three most active monkeys
+        assert_that!(compute_score(
+            &monkeys,
+            Business { top: 3, ..relieved },
+            &DivideByThree
+        ))
+        .is_ok()
+        .is_equal_to(1007475);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(monkey_business_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((10605, 2713310158));
+    }
+
+    #[test]
+    fn parse_all_operations() {
+        let operations = ["+", "-", "*", "/", "%"].map(|op| {
+            operation::<nom::error::Error<Span>>(Span::new(&format!(
+                "  Operation: new = old {op} 7\n"
+            )))
+            .map(|(_, operation)| operation)
+            .ok()
+        });
+
+        assert_that!(operations).is_equal_to([
+            Some(Operation::Add(Term::Old, Term::Constant(7))),
+            Some(Operation::Sub(Term::Old, Term::Constant(7))),
+            Some(Operation::Mul(Term::Old, Term::Constant(7))),
+            Some(Operation::Div(Term::Old, Term::Constant(7))),
+            Some(Operation::Mod(Term::Old, Term::Constant(7))),
+        ]);
+    }
+
+    #[test]
+    fn eval_out_of_bounds() {
+        assert_that!(Operation::Sub(Term::Old, Term::Constant(7)).eval(3)).is_none();
+        assert_that!(Operation::Div(Term::Old, Term::Constant(0)).eval(3)).is_none();
+        assert_that!(Operation::Mod(Term::Old, Term::Constant(7)).eval(10))
+            .is_some()
+            .is_equal_to(3);
+    }
+
+    #[test]
+    fn subtraction_modulo() {
+        // 3 stands for 13 modulo 10: 13 - 7 = 6
+        assert_that!(Operation::Sub(Term::Old, Term::Constant(7)).eval_modulo(3, 10))
+            .is_some()
+            .is_equal_to(6);
+    }
+
+    #[test]
+    fn division_cannot_be_kept_modulo() {
+        let monkeys = vec![Monkey::new(
+            MonkeyId(0),
+            &[Item(79)],
+            Operation::Div(Term::Old, Term::Constant(2)),
+            ThrowDecision::new(23, MonkeyId(0), MonkeyId(0)),
+        )];
+
+        let relieved = Business {
+            rounds: 20,
+            ..Business::default()
+        };
+        assert_that!(compute_score(&monkeys, relieved, &DivideByThree)).is_ok();
+        assert_that!(ModuloDivisors::new(&monkeys))
+            .is_err()
+            .is_equal_to(Error::NotModular(0));
+    }
+
+    /// A monkey adding 1 to worry levels and throwing to `if_true` or `if_false`.
+    fn adding_monkey(id: u8, modulus: u64, if_true: u8, if_false: u8) -> Monkey {
+        Monkey::new(
+            MonkeyId(id),
+            &[Item(79)],
+            Operation::Add(Term::Old, Term::Constant(1)),
+            ThrowDecision::new(modulus, MonkeyId(if_true), MonkeyId(if_false)),
+        )
+    }
+
+    #[test]
+    fn zero_divisor() {
+        let monkeys = vec![adding_monkey(0, 2, 1, 1), adding_monkey(1, 0, 0, 0)];
+
+        assert_that!(compute_score(&monkeys, Business::default(), &DivideByThree))
+            .is_err()
+            .is_equal_to(Error::ZeroDivisor(1));
+        assert_that!(ModuloDivisors::new(&monkeys))
+            .is_err()
+            .is_equal_to(Error::ZeroDivisor(1));
+    }
+
+    #[test]
+    fn divisors_overflow() {
+        let monkeys = vec![
+            adding_monkey(0, u64::MAX / 2, 1, 1),
+            adding_monkey(1, 3, 0, 0),
+        ];
+
+        assert_that!(ModuloDivisors::new(&monkeys))
+            .is_err()
+            .is_equal_to(Error::ModulusOverflow);
+    }
+
+    #[test]
+    fn unknown_target() {
+        let monkeys = vec![adding_monkey(0, 2, 1, 5), adding_monkey(1, 3, 0, 0)];
+
+        assert_that!(compute_score(&monkeys, Business::default(), &DivideByThree))
+            .is_err()
+            .is_equal_to(Error::UnknownTarget {
+                monkey: 0,
+                target: 5,
+            });
+    }
+
+    #[test]
+    fn misplaced_monkey() {
+        let monkeys = vec![adding_monkey(1, 2, 0, 0), adding_monkey(0, 3, 1, 1)];
+
+        assert_that!(compute_score(&monkeys, Business::default(), &DivideByThree))
+            .is_err()
+            .is_equal_to(Error::Misplaced {
+                monkey: 1,
+                position: 0,
+            });
+    }
+}
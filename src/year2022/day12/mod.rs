@@ -0,0 +1,404 @@
+use crate::diagnostics::BadInput;
+use crate::error::{PuzzleError, Result};
+use petgraph::dot::{Config, Dot};
+use petgraph::graph::NodeIndex;
+use petgraph::{Direction, Graph};
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+const ROAD_START: char = 'S';
+const ROAD_END: char = 'E';
+const LOWEST_ELEVATION: char = 'a';
+
+type Point = (usize, usize);
+
+type RoadMap = Graph<Point, ()>;
+
+type Location = NodeIndex;
+
+#[derive(Debug)]
+struct Journey {
+    paths: RoadMap,
+    start: Location,
+    possible_starts: Vec<Location>,
+    end: Location,
+}
+
+impl Journey {
+    fn new(paths: RoadMap, start: Location, possible_starts: Vec<Location>, end: Location) -> Self {
+        Self {
+            paths,
+            start,
+            possible_starts,
+            end,
+        }
+    }
+
+    /// Shortest routes to the end, found by a single breadth-first search from the end going
+    /// down the edges backwards: for each square the end can be reached from, the number of hops
+    /// to the end and the next square on the way.
+    fn routes_to_end(&self) -> HashMap<Location, (usize, Location)> {
+        let mut routes = HashMap::from([(self.end, (0, self.end))]);
+        let mut queue = VecDeque::from([self.end]);
+        while let Some(node) = queue.pop_front() {
+            let hops = routes[&node].0 + 1;
+            for previous in self.paths.neighbors_directed(node, Direction::Incoming) {
+                routes.entry(previous).or_insert_with(|| {
+                    queue.push_back(previous);
+                    (hops, node)
+                });
+            }
+        }
+        routes
+    }
+
+    /// The start among `starts` closest to the end, with its number of hops.
+    fn closest(
+        routes: &HashMap<Location, (usize, Location)>,
+        starts: &[Location],
+    ) -> Option<(usize, Location)> {
+        starts
+            .iter()
+            .filter_map(|start| routes.get(start).map(|(hops, _)| (*hops, *start)))
+            .min()
+    }
+
+    /// Route from `start_node` to the end, both included.
+    fn route_from(
+        &self,
+        routes: &HashMap<Location, (usize, Location)>,
+        start_node: Location,
+    ) -> Vec<Location> {
+        let mut route = vec![start_node];
+        let mut current = start_node;
+        while current != self.end {
+            current = routes[&current].1;
+            route.push(current);
+        }
+        route
+    }
+
+    /// Route from the square marked as the start.
+    fn start_route(&self) -> Option<Vec<Location>> {
+        let routes = self.routes_to_end();
+        Self::closest(&routes, &[self.start]).map(|(_, start)| self.route_from(&routes, start))
+    }
+
+    /// Route from the best square at the lowest elevation.
+    fn scenic_route(&self) -> Option<Vec<Location>> {
+        let routes = self.routes_to_end();
+        Self::closest(&routes, &self.possible_starts)
+            .map(|(_, start)| self.route_from(&routes, start))
+    }
+
+    /// Hops from the square marked as the start, then from the best square at the lowest
+    /// elevation.
+    fn hops(&self) -> (Option<usize>, Option<usize>) {
+        let routes = self.routes_to_end();
+        (
+            Self::closest(&routes, &[self.start]).map(|(hops, _)| hops),
+            Self::closest(&routes, &self.possible_starts).map(|(hops, _)| hops),
+        )
+    }
+
+    /// Draws the route on a map of the given size the way the puzzle statement does, with an
+    /// arrow on each square telling where to go next.
+    fn draw(&self, route: &[Location], (height, width): (usize, usize)) -> String {
+        let mut drawing = vec![vec!['.'; width]; height];
+        for step in route.windows(2) {
+            let ((i, j), (next_i, next_j)) = (self.paths[step[0]], self.paths[step[1]]);
+            drawing[i][j] = match (next_i.cmp(&i), next_j.cmp(&j)) {
+                (Ordering::Less, _) => '^',
+                (Ordering::Greater, _) => 'v',
+                (_, Ordering::Less) => '<',
+                _ => '>',
+            };
+        }
+        if let Some(&(i, j)) = route.last().map(|end| &self.paths[*end]) {
+            drawing[i][j] = ROAD_END;
+        }
+        drawing
+            .into_iter()
+            .map(|row| row.into_iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// What keeps from finding the way, rows and columns counting from 1.
+#[derive(thiserror::Error, Debug, PartialEq)]
+enum Error {
+    #[error("Path not found")]
+    PathNotFound,
+    #[error("no square is marked {0}")]
+    Missing(char),
+    #[error("{square:?} at row {row}, column {column} is not an elevation")]
+    NotAnElevation {
+        row: usize,
+        column: usize,
+        square: char,
+    },
+    #[error("row {row} is {width} squares wide instead of {expected}")]
+    Ragged {
+        row: usize,
+        width: usize,
+        expected: usize,
+    },
+}
+
+impl PuzzleError for Error {}
+
+type Elevation = i32;
+
+fn to_elevation(c: char) -> Option<Elevation> {
+    match c {
+        ROAD_START => Some(b'a' as Elevation),
+        ROAD_END => Some(b'z' as Elevation),
+        c if c.is_ascii_lowercase() => Some(c as Elevation),
+        _ => None,
+    }
+}
+
+fn build_journey(map: &[Vec<char>]) -> Result<Journey, Error> {
+    let mut start_node = None;
+    let mut end_node = None;
+    let mut possible_starts = vec![];
+    let width = map.first().map_or(0, Vec::len);
+    let mut graph = RoadMap::with_capacity(width * map.len(), width * map.len() / 2);
+    // elevations of the squares already added, row after row
+    let mut elevations = Vec::with_capacity(width * map.len());
+
+    for (i, row) in map.iter().enumerate() {
+        if row.len() != width {
+            return Err(Error::Ragged {
+                row: i + 1,
+                width: row.len(),
+                expected: width,
+            });
+        }
+        for (j, &square) in row.iter().enumerate() {
+            let elevation = to_elevation(square).ok_or(Error::NotAnElevation {
+                row: i + 1,
+                column: j + 1,
+                square,
+            })?;
+            elevations.push(elevation);
+            let node = graph.add_node((i, j));
+            if square == ROAD_START {
+                start_node = Some(node);
+            }
+            if square == ROAD_END {
+                end_node = Some(node);
+            } else if Some(elevation) == to_elevation(LOWEST_ELEVATION) {
+                possible_starts.push(node);
+            }
+            if i > 0 {
+                let neighbour = NodeIndex::new((i - 1) * width + j);
+                let neighbour_elevation = elevations[neighbour.index()];
+                if (neighbour_elevation - elevation) <= 1 {
+                    graph.add_edge(node, neighbour, ());
+                }
+                if (elevation - neighbour_elevation) <= 1 {
+                    graph.add_edge(neighbour, node, ());
+                }
+            }
+            if j > 0 {
+                let neighbour = NodeIndex::new(i * width + j - 1);
+                let neighbour_elevation = elevations[neighbour.index()];
+                if (neighbour_elevation - elevation) <= 1 {
+                    graph.add_edge(node, neighbour, ());
+                }
+                if (elevation - neighbour_elevation) <= 1 {
+                    graph.add_edge(neighbour, node, ());
+                }
+            }
+        }
+    }
+    Ok(Journey::new(
+        graph,
+        start_node.ok_or(Error::Missing(ROAD_START))?,
+        possible_starts,
+        end_node.ok_or(Error::Missing(ROAD_END))?,
+    ))
+}
+
+/// The heightmap, checked to be a rectangle of elevations.
+pub fn parse(data: &str) -> Result<Vec<Vec<char>>> {
+    let rows = data.lines().collect::<Vec<_>>();
+    let width = rows.first().map_or(0, |row| row.chars().count());
+    for (i, row) in rows.iter().enumerate() {
+        if let Some((j, c)) = row.char_indices().find(|(_, c)| to_elevation(*c).is_none()) {
+            let column = row[..j].chars().count() + 1;
+            let kind = format!("not an elevation (row {}, column {column})", i + 1);
+            return Err(BadInput::at(data, &row[j..j + c.len_utf8()], kind).into());
+        }
+        let row_width = row.chars().count();
+        if row_width != width {
+            let kind = format!(
+                "row {} is {row_width} squares wide instead of {width}",
+                i + 1
+            );
+            return Err(BadInput::at(data, row, kind).into());
+        }
+    }
+    Ok(rows.iter().map(|row| row.chars().collect()).collect())
+}
+
+pub fn solve(map: &[Vec<char>]) -> Result<(usize, usize)> {
+    let journey = build_journey(map)?;
+    let (start_hops, path_hops) = journey.hops();
+
+    Ok((
+        start_hops.ok_or(Error::PathNotFound)?,
+        path_hops.ok_or(Error::PathNotFound)?,
+    ))
+}
+
+/// Draws the shortest route on the heightmap, from the square marked as the start or, if
+/// `scenic`, from the best square at the lowest elevation.
+pub fn draw_route(map: &[Vec<char>], scenic: bool) -> Result<String> {
+    let journey = build_journey(map)?;
+    let route = if scenic {
+        journey.scenic_route()
+    } else {
+        journey.start_route()
+    }
+    .ok_or(Error::PathNotFound)?;
+    let size = (map.len(), map.first().map_or(0, Vec::len));
+    Ok(journey.draw(&route, size))
+}
+
+/// Renders the road map as a Graphviz graph: a node per square, labelled with its coordinates
+/// (row, column) and elevation, and an edge for each climbable step between neighbours.
+pub fn to_dot(map: &[Vec<char>]) -> Result<String> {
+    let journey = build_journey(map)?;
+    let label = |_, (_, &(i, j)): (NodeIndex, &Point)| format!("label=\"({i},{j}) {}\"", map[i][j]);
+    let dot = Dot::with_attr_getters(
+        &journey.paths,
+        &[Config::NodeNoLabel, Config::EdgeNoLabel],
+        &|_, _| String::new(),
+        &label,
+    );
+    // labels are all given as attributes, the weights do not need to be displayable
+    Ok(format!("{dot:?}"))
+}
+
+pub fn great_journey_from_str(data: &str) -> Result<(usize, usize)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::diagnostics;
+    use crate::error::AocError;
+    use insta::assert_snapshot;
+    use spectral::prelude::*;
+
+    #[test]
+    fn path_length() {
+        let map = vec![
+            vec!['S', 'a', 'b', 'q', 'p', 'o', 'n', 'm'],
+            vec!['a', 'b', 'c', 'r', 'y', 'x', 'x', 'l'],
+            vec!['a', 'c', 'c', 's', 'z', 'E', 'x', 'k'],
+            vec!['a', 'c', 'c', 't', 'u', 'v', 'w', 'j'],
+            vec!['a', 'b', 'd', 'e', 'f', 'g', 'h', 'i'],
+        ];
+
+        let journey = build_journey(&map).unwrap();
+
+        assert_that!(journey.hops()).is_equal_to((Some(31), Some(29)));
+    }
+
+    #[test]
+    fn malformed_maps() {
+        assert_that!(build_journey(&[vec!['S', 'b'], vec!['a', 'c']]).err())
+            .is_equal_to(Some(Error::Missing(ROAD_END)));
+        assert_that!(build_journey(&[vec!['S', 'E'], vec!['a', '?']]).err()).is_equal_to(Some(
+            Error::NotAnElevation {
+                row: 2,
+                column: 2,
+                square: '?',
+            },
+        ));
+        assert_that!(build_journey(&[vec!['S', 'b'], vec!['a', 'c', 'E']]).err()).is_equal_to(
+            Some(Error::Ragged {
+                row: 2,
+                width: 3,
+                expected: 2,
+            }),
+        );
+        assert_that!(build_journey(&[]).err()).is_equal_to(Some(Error::Missing(ROAD_START)));
+    }
+
+    #[test]
+    fn malformed_input() {
+        let stray = parse("Sab\nac#E");
+        assert!(matches!(&stray, Err(AocError::Parse(e)) if e.offset() == 6));
+        assert_that!(diagnostics::describe(&stray.unwrap_err())).contains("row 2, column 3");
+
+        let ragged = parse("Sab\nacdE");
+        assert!(matches!(&ragged, Err(AocError::Parse(e)) if e.offset() == 4));
+        assert_that!(diagnostics::describe(&ragged.unwrap_err()))
+            .contains("row 2 is 4 squares wide instead of 3");
+
+        let no_end = great_journey_from_str("Sab\nacd").unwrap_err().to_string();
+        assert_that!(no_end.as_str()).is_equal_to("no square is marked E");
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(great_journey_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((31, 29));
+    }
+
+    #[test]
+    fn route_drawing() -> anyhow::Result<()> {
+        let map = parse(EXAMPLE)?;
+
+        let drawing = draw_route(&map, false)?;
+        let rows = drawing.lines().collect::<Vec<_>>();
+
+        assert_that!(rows).has_length(5);
+        assert_that!(rows[0].chars().next())
+            .is_some()
+            .is_equal_to('v');
+        assert_that!(rows[2].chars().nth(5))
+            .is_some()
+            .is_equal_to(ROAD_END);
+        // several routes are equally short, but all of them take 31 steps
+        assert_that!(drawing.matches(['^', 'v', '<', '>']).count()).is_equal_to(31);
+        assert_snapshot!(drawing);
+        Ok(())
+    }
+
+    #[test]
+    fn scenic_route_drawing() -> anyhow::Result<()> {
+        let map = parse(EXAMPLE)?;
+
+        let drawing = draw_route(&map, true)?;
+
+        assert_that!(drawing.matches(['^', 'v', '<', '>']).count()).is_equal_to(29);
+        assert_snapshot!(drawing);
+        Ok(())
+    }
+
+    #[test]
+    fn dot_export() {
+        let map = vec![vec!['S', 'c'], vec!['b', 'E']];
+
+        let dot = to_dot(&map).unwrap();
+
+        assert_that!(dot).contains(r#"label="(0,0) S""#);
+        assert_that!(dot).contains(r#"label="(1,1) E""#);
+        // S (a) can climb to b but not to c, everything can go down
+        assert_that!(dot).contains("0 -> 2");
+        assert_that!(dot.contains("0 -> 1 ")).is_false();
+        assert_that!(dot).contains("1 -> 0");
+    }
+}
@@ -0,0 +1,190 @@
+use crate::diagnostics::parse_all;
+use crate::error::Result;
+use nom::{
+    branch::alt,
+    character::complete::{char, line_ending, u32},
+    combinator::{map, opt},
+    error::ParseError,
+    multi::{many1, separated_list0, separated_list1},
+    sequence::{delimited, separated_pair, terminated},
+    IResult,
+};
+use nom_locate::LocatedSpan;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+type Span<'a> = LocatedSpan<&'a str>;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Packet {
+    Integer(u32),
+    List(Vec<Packet>),
+}
+
+impl Packet {
+    fn divider(value: u32) -> Self {
+        Packet::List(vec![Packet::List(vec![Packet::Integer(value)])])
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Integer(l), Packet::Integer(r)) => l.cmp(r),
+            (Packet::List(l), Packet::List(r)) => l.cmp(r),
+            (Packet::Integer(l), Packet::List(r)) => [Packet::Integer(*l)][..].cmp(r),
+            (Packet::List(l), Packet::Integer(r)) => l[..].cmp(&[Packet::Integer(*r)]),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Packet::Integer(v) => write!(f, "{}", v),
+            Packet::List(l) => {
+                write!(f, "[")?;
+                for (i, p) in l.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", p)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+fn packet<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Packet, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    alt((
+        map(u32, Packet::Integer),
+        map(
+            delimited(char('['), separated_list0(char(','), packet), char(']')),
+            Packet::List,
+        ),
+    ))(i)
+}
+
+fn packet_pair<'a, E>(i: Span<'a>) -> IResult<Span<'a>, (Packet, Packet), E>
+where
+    E: ParseError<Span<'a>>,
+{
+    terminated(
+        separated_pair(packet, line_ending, packet),
+        opt(line_ending),
+    )(i)
+}
+
+fn packet_pairs<'a, E>(i: Span<'a>) -> IResult<Span<'a>, Vec<(Packet, Packet)>, E>
+where
+    E: ParseError<Span<'a>>,
+{
+    separated_list1(many1(line_ending), packet_pair)(i)
+}
+
+fn sum_of_ordered_pair_indices(pairs: &[(Packet, Packet)]) -> usize {
+    pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, (left, right))| left < right)
+        .map(|(i, _)| i + 1)
+        .sum()
+}
+
+fn decoder_key(pairs: &[(Packet, Packet)]) -> usize {
+    let dividers = [Packet::divider(2), Packet::divider(6)];
+    let mut packets = pairs
+        .iter()
+        .flat_map(|(left, right)| [left, right])
+        .chain(dividers.iter())
+        .collect::<Vec<_>>();
+    packets.sort();
+    packets
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| dividers.contains(p))
+        .map(|(i, _)| i + 1)
+        .product()
+}
+
+pub fn parse(data: &str) -> Result<Vec<(Packet, Packet)>> {
+    let pairs = parse_all(data, packet_pairs)?;
+    Ok(pairs)
+}
+
+pub fn solve(pairs: &[(Packet, Packet)]) -> Result<(usize, usize)> {
+    Ok((sum_of_ordered_pair_indices(pairs), decoder_key(pairs)))
+}
+
+pub fn distress_signal_from_str(data: &str) -> Result<(usize, usize)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn parse_packet() {
+        let p = packet::<()>(Span::new("[1,[2,[]],3]"));
+
+        assert_that!(p).is_ok();
+        assert_that!(p.unwrap().1).is_equal_to(Packet::List(vec![
+            Packet::Integer(1),
+            Packet::List(vec![Packet::Integer(2), Packet::List(vec![])]),
+            Packet::Integer(3),
+        ]));
+    }
+
+    #[test]
+    fn display_packet() {
+        let (_, p) = packet::<()>(Span::new("[[1],[2,3,4]]")).unwrap();
+
+        assert_that!(p.to_string()).is_equal_to("[[1],[2,3,4]]".to_string());
+    }
+
+    #[test]
+    fn compare_mixed_types() {
+        let (_, left) = packet::<()>(Span::new("[9]")).unwrap();
+        let (_, right) = packet::<()>(Span::new("[[8,7,6]]")).unwrap();
+
+        assert_that!(left > right).is_true();
+    }
+
+    #[test]
+    fn example_ordered_pairs() {
+        let (_, pairs) = packet_pairs::<()>(Span::new(EXAMPLE)).unwrap();
+
+        assert_that!(pairs.len()).is_equal_to(8);
+        assert_that!(sum_of_ordered_pair_indices(&pairs)).is_equal_to(13);
+    }
+
+    #[test]
+    fn example_decoder_key() {
+        let (_, pairs) = packet_pairs::<()>(Span::new(EXAMPLE)).unwrap();
+
+        assert_that!(decoder_key(&pairs)).is_equal_to(140);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(distress_signal_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((13, 140));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::diagnostics::parse_all;
+use crate::error::Result;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, line_ending, u32},
+    error::ParseError,
+    multi::separated_list1,
+    sequence::separated_pair,
+    IResult,
+};
+use std::collections::BTreeSet;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+const SAND_SOURCE: Position = (500, 0);
+
+pub type Point = (u32, u32);
+
+pub type RockPath = Vec<Point>;
+
+/// Sand can pile up left of the column 0 on a deep floor, so the cave is signed.
+type Position = (i64, i64);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Bottom {
+    Abyss,
+    Floor,
+}
+
+#[derive(Clone, Debug)]
+struct Cave {
+    blocked: BTreeSet<Position>,
+    lowest_rock: i64,
+}
+
+impl Cave {
+    fn new(paths: &[RockPath]) -> Self {
+        let mut blocked = BTreeSet::new();
+        for path in paths {
+            for segment in path.windows(2) {
+                let ((x0, y0), (x1, y1)) = (segment[0], segment[1]);
+                for x in x0.min(x1)..=x0.max(x1) {
+                    for y in y0.min(y1)..=y0.max(y1) {
+                        blocked.insert((i64::from(x), i64::from(y)));
+                    }
+                }
+            }
+        }
+        let lowest_rock = blocked.iter().map(|(_, y)| *y).max().unwrap_or(0);
+        Self {
+            blocked,
+            lowest_rock,
+        }
+    }
+
+    fn is_free(&self, point: &Position, bottom: Bottom) -> bool {
+        match bottom {
+            Bottom::Floor if point.1 >= self.lowest_rock + 2 => false,
+            _ => !self.blocked.contains(point),
+        }
+    }
+
+    /// Drops a unit of sand from the source, and returns where it came to rest,
+    /// or `None` if it fell into the abyss.
+    fn drop_sand(&self, bottom: Bottom) -> Option<Position> {
+        let (mut x, mut y) = SAND_SOURCE;
+        loop {
+            if bottom == Bottom::Abyss && y > self.lowest_rock {
+                return None;
+            }
+            match [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)]
+                .into_iter()
+                .find(|p| self.is_free(p, bottom))
+            {
+                Some(next) => (x, y) = next,
+                None => return Some((x, y)),
+            }
+        }
+    }
+
+    fn fill(mut self, bottom: Bottom) -> usize {
+        let mut units = 0;
+        while self.is_free(&SAND_SOURCE, bottom) {
+            match self.drop_sand(bottom) {
+                Some(rest) => {
+                    self.blocked.insert(rest);
+                    units += 1;
+                }
+                None => break,
+            }
+        }
+        units
+    }
+}
+
+fn point<'a, E>(i: &'a str) -> IResult<&'a str, Point, E>
+where
+    E: ParseError<&'a str>,
+{
+    separated_pair(u32, char(','), u32)(i)
+}
+
+fn rock_path<'a, E>(i: &'a str) -> IResult<&'a str, RockPath, E>
+where
+    E: ParseError<&'a str>,
+{
+    separated_list1(tag(" -> "), point)(i)
+}
+
+fn rock_paths<'a, E>(i: &'a str) -> IResult<&'a str, Vec<RockPath>, E>
+where
+    E: ParseError<&'a str>,
+{
+    separated_list1(line_ending, rock_path)(i)
+}
+
+pub fn parse(data: &str) -> Result<Vec<RockPath>> {
+    let paths = parse_all(data, rock_paths)?;
+    Ok(paths)
+}
+
+pub fn solve(paths: &[RockPath]) -> Result<(usize, usize)> {
+    let cave = Cave::new(paths);
+    Ok((cave.clone().fill(Bottom::Abyss), cave.fill(Bottom::Floor)))
+}
+
+pub fn units_of_sand_from_str(data: &str) -> Result<(usize, usize)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn parse_rock_path() {
+        let path = rock_path::<()>("498,4 -> 498,6 -> 496,6");
+
+        assert_that!(path)
+            .is_ok()
+            .is_equal_to(("", vec![(498, 4), (498, 6), (496, 6)]));
+    }
+
+    #[test]
+    fn build_cave() {
+        let (_, paths) = rock_paths::<()>(EXAMPLE).unwrap();
+
+        let cave = Cave::new(&paths);
+
+        assert_that!(cave.lowest_rock).is_equal_to(9);
+        assert_that!(cave.blocked.len()).is_equal_to(20);
+    }
+
+    #[test]
+    fn sand_falls_into_abyss() {
+        let (_, paths) = rock_paths::<()>(EXAMPLE).unwrap();
+
+        let units = Cave::new(&paths).fill(Bottom::Abyss);
+
+        assert_that!(units).is_equal_to(24);
+    }
+
+    #[test]
+    fn sand_rests_on_floor() {
+        let (_, paths) = rock_paths::<()>(EXAMPLE).unwrap();
+
+        let units = Cave::new(&paths).fill(Bottom::Floor);
+
+        assert_that!(units).is_equal_to(93);
+    }
+
+    #[test]
+    fn sand_slides_left_of_column_zero() {
+        let cave = Cave {
+            blocked: (0..=500).map(|k| (500 - k, k + 1)).collect(),
+            lowest_rock: 600,
+        };
+
+        assert_that!(cave.drop_sand(Bottom::Floor))
+            .is_some()
+            .is_equal_to((-1, 601));
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(units_of_sand_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((24, 93));
+    }
+}
@@ -0,0 +1,285 @@
+use crate::diagnostics::parse_all;
+use crate::error::{PuzzleError, Result};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{i64, line_ending},
+    combinator::map,
+    error::ParseError,
+    multi::separated_list1,
+    sequence::{preceded, separated_pair, tuple},
+    IResult,
+};
+use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+use thiserror::Error;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+const ROW_TO_CHECK: i64 = 2_000_000;
+const SEARCH_AREA_MAX: i64 = 4_000_000;
+/// Row checked in the example of the puzzle statement.
+pub const EXAMPLE_ROW_TO_CHECK: i64 = 10;
+/// Largest coordinate of the search area of the example of the puzzle statement.
+pub const EXAMPLE_SEARCH_AREA_MAX: i64 = 20;
+const TUNING_FACTOR: i64 = 4_000_000;
+
+type Point = (i64, i64);
+
+#[derive(Error, Debug)]
+#[error("Could not find the distress beacon")]
+struct NotFoundError;
+
+impl PuzzleError for NotFoundError {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sensor {
+    position: Point,
+    beacon: Point,
+}
+
+impl Sensor {
+    fn new(position: Point, beacon: Point) -> Self {
+        Self { position, beacon }
+    }
+
+    fn radius(&self) -> i64 {
+        manhattan_distance(&self.position, &self.beacon)
+    }
+
+    fn covers(&self, point: &Point) -> bool {
+        manhattan_distance(&self.position, point) <= self.radius()
+    }
+
+    fn coverage_on_row(&self, row: i64) -> Option<RangeInclusive<i64>> {
+        let half_width = self.radius() - (self.position.1 - row).abs();
+        if half_width < 0 {
+            None
+        } else {
+            Some((self.position.0 - half_width)..=(self.position.0 + half_width))
+        }
+    }
+}
+
+fn manhattan_distance(a: &Point, b: &Point) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+fn merge(mut ranges: Vec<RangeInclusive<i64>>) -> Vec<RangeInclusive<i64>> {
+    ranges.sort_by_key(|r| *r.start());
+    let mut merged: Vec<RangeInclusive<i64>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if *range.start() <= *last.end() + 1 => {
+                if range.end() > last.end() {
+                    *last = *last.start()..=*range.end();
+                }
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+fn row_coverage(sensors: &[Sensor], row: i64) -> Vec<RangeInclusive<i64>> {
+    merge(
+        sensors
+            .iter()
+            .filter_map(|s| s.coverage_on_row(row))
+            .collect(),
+    )
+}
+
+fn positions_without_beacon(sensors: &[Sensor], row: i64) -> usize {
+    let covered = row_coverage(sensors, row)
+        .iter()
+        .map(|r| (r.end() - r.start() + 1) as usize)
+        .sum::<usize>();
+    let beacons_on_row = sensors
+        .iter()
+        .map(|s| s.beacon)
+        .filter(|b| b.1 == row)
+        .collect::<BTreeSet<_>>()
+        .len();
+    covered - beacons_on_row
+}
+
+/// The distress beacon is the only uncovered point of the area, so it lies on the intersection
+/// of two lines running just outside the sensors’ ranges, or of such a line with the area border
+/// when it sits there: none is found if every candidate in the area is covered.
+fn find_distress_beacon_on_perimeters(sensors: &[Sensor], max: i64) -> Option<Point> {
+    let mut ascending = BTreeSet::new();
+    let mut descending = BTreeSet::new();
+    for s in sensors {
+        let (x, y) = s.position;
+        let r = s.radius() + 1;
+        ascending.insert(x - y - r);
+        ascending.insert(x - y + r);
+        descending.insert(x + y - r);
+        descending.insert(x + y + r);
+    }
+    let crossings = ascending
+        .iter()
+        .flat_map(|a| descending.iter().map(move |d| (a, d)))
+        .filter(|(a, d)| (*a + *d) % 2 == 0)
+        .map(|(a, d)| ((a + d) / 2, (d - a) / 2));
+    let borders = [0, max].into_iter().flat_map(|c| {
+        let on_columns = ascending
+            .iter()
+            .map(move |a| (c, c - a))
+            .chain(descending.iter().map(move |d| (c, d - c)));
+        let on_rows = ascending
+            .iter()
+            .map(move |a| (a + c, c))
+            .chain(descending.iter().map(move |d| (d - c, c)));
+        on_columns.chain(on_rows)
+    });
+    let corners = [(0, 0), (0, max), (max, 0), (max, max)];
+    crossings
+        .chain(borders)
+        .chain(corners)
+        .filter(|(x, y)| (0..=max).contains(x) && (0..=max).contains(y))
+        .find(|p| sensors.iter().all(|s| !s.covers(p)))
+}
+
+fn tuning_frequency(sensors: &[Sensor], max: i64) -> Option<i64> {
+    find_distress_beacon_on_perimeters(sensors, max).map(|(x, y)| x * TUNING_FACTOR + y)
+}
+
+fn point<'a, E>(i: &'a str) -> IResult<&'a str, Point, E>
+where
+    E: ParseError<&'a str>,
+{
+    separated_pair(
+        preceded(tag("x="), i64),
+        tag(", "),
+        preceded(tag("y="), i64),
+    )(i)
+}
+
+fn sensor<'a, E>(i: &'a str) -> IResult<&'a str, Sensor, E>
+where
+    E: ParseError<&'a str>,
+{
+    map(
+        tuple((
+            tag("Sensor at "),
+            point,
+            tag(": closest beacon is at "),
+            point,
+        )),
+        |(_, position, _, beacon)| Sensor::new(position, beacon),
+    )(i)
+}
+
+fn sensors<'a, E>(i: &'a str) -> IResult<&'a str, Vec<Sensor>, E>
+where
+    E: ParseError<&'a str>,
+{
+    separated_list1(line_ending, sensor)(i)
+}
+
+pub fn parse(data: &str) -> Result<Vec<Sensor>> {
+    let sensors = parse_all(data, sensors)?;
+    Ok(sensors)
+}
+
+/// Positions where no beacon can be on `row`, and tuning frequency of the distress beacon in the
+/// search area, from 0 to `search_area_max` on both axes.
+pub fn solve_with(sensors: &[Sensor], row: i64, search_area_max: i64) -> Result<(usize, i64)> {
+    let excluded = positions_without_beacon(sensors, row);
+    let frequency = tuning_frequency(sensors, search_area_max).ok_or(NotFoundError)?;
+    Ok((excluded, frequency))
+}
+
+/// Same as [`solve_with`], on the row and the search area of the real puzzle.
+pub fn solve(sensors: &[Sensor]) -> Result<(usize, i64)> {
+    solve_with(sensors, ROW_TO_CHECK, SEARCH_AREA_MAX)
+}
+
+pub fn beacon_exclusion_zone_from_str(data: &str) -> Result<(usize, i64)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn parse_sensor() {
+        let s = sensor::<()>("Sensor at x=2, y=18: closest beacon is at x=-2, y=15");
+
+        assert_that!(s)
+            .is_ok()
+            .is_equal_to(("", Sensor::new((2, 18), (-2, 15))));
+    }
+
+    #[test]
+    fn merge_ranges() {
+        let merged = merge(vec![5..=8, 0..=2, 3..=4, 10..=12, 11..=11]);
+
+        assert_that!(merged).is_equal_to(vec![0..=8, 10..=12]);
+    }
+
+    #[test]
+    fn example_positions_without_beacon() {
+        let (_, sensors) = sensors::<()>(EXAMPLE).unwrap();
+
+        assert_that!(positions_without_beacon(&sensors, 10)).is_equal_to(26);
+    }
+
+    #[test]
+    fn example_tuning_frequency() {
+        let (_, sensors) = sensors::<()>(EXAMPLE).unwrap();
+
+        assert_that!(tuning_frequency(&sensors, 20))
+            .is_some()
+            .is_equal_to(56000011);
+    }
+
+    #[test]
+    fn beacon_in_a_corner() {
+        let (_, sensors) =
+            sensors::<()>("Sensor at x=20, y=20: closest beacon is at x=20, y=-19").unwrap();
+
+        assert_that!(tuning_frequency(&sensors, 20))
+            .is_some()
+            .is_equal_to(0);
+    }
+
+    #[test]
+    fn beacon_on_an_edge() {
+        let data = "Sensor at x=-1, y=21: closest beacon is at x=-1, y=10
+Sensor at x=10, y=0: closest beacon is at x=10, y=19
+Sensor at x=20, y=20: closest beacon is at x=20, y=-8";
+        let (_, sensors) = sensors::<()>(data).unwrap();
+
+        assert_that!(tuning_frequency(&sensors, 20))
+            .is_some()
+            .is_equal_to(10);
+    }
+
+    #[test]
+    fn area_fully_covered() {
+        let sensors = parse("Sensor at x=10, y=10: closest beacon is at x=10, y=30").unwrap();
+
+        assert_that!(solve_with(&sensors, 10, 20).map_err(|e| e.to_string()))
+            .is_err()
+            .is_equal_to("Could not find the distress beacon".to_owned());
+    }
+
+    #[test]
+    fn example_answers() {
+        let sensors = parse(EXAMPLE).unwrap();
+
+        assert_that!(solve_with(
+            &sensors,
+            EXAMPLE_ROW_TO_CHECK,
+            EXAMPLE_SEARCH_AREA_MAX
+        ))
+        .is_ok()
+        .is_equal_to((26, 56000011));
+    }
+}
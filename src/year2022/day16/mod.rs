@@ -0,0 +1,283 @@
+use crate::diagnostics::parse_all;
+use crate::error::{PuzzleError, Result};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, line_ending, u32},
+    combinator::map,
+    error::ParseError,
+    multi::separated_list1,
+    sequence::{preceded, tuple},
+    IResult,
+};
+use petgraph::algo::floyd_warshall;
+use petgraph::graph::NodeIndex;
+use petgraph::Graph;
+use std::collections::BTreeMap;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+const START_VALVE: &str = "AA";
+const SOLO_MINUTES: u32 = 30;
+const WITH_ELEPHANT_MINUTES: u32 = 26;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Valve {
+    name: String,
+    flow_rate: u32,
+    tunnels: Vec<String>,
+}
+
+impl Valve {
+    fn new(name: &str, flow_rate: u32, tunnels: &[&str]) -> Self {
+        Self {
+            name: name.to_owned(),
+            flow_rate,
+            tunnels: tunnels.iter().map(|t| (*t).to_owned()).collect(),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Tunnel leads to unknown valve {0}")]
+    UnknownValve(String),
+    #[error("Start valve {START_VALVE} not found")]
+    MissingStart,
+    #[error("{0} valves have a positive flow rate, at most {max} can be opened", max = OpenedValves::BITS)]
+    TooManyValves(usize),
+}
+
+impl PuzzleError for Error {}
+
+/// Valves with a positive flow rate (plus the start valve, last), and the travel time between
+/// each pair of them.
+#[derive(Debug)]
+struct Volcano {
+    flow_rates: Vec<u32>,
+    distances: Vec<Vec<u32>>,
+    start: usize,
+}
+
+/// A bit per valve with a positive flow rate.
+type OpenedValves = u32;
+
+impl Volcano {
+    fn new(valves: &[Valve]) -> Result<Self, Error> {
+        let mut graph = Graph::<u32, ()>::new();
+        let nodes: BTreeMap<&str, NodeIndex> = valves
+            .iter()
+            .map(|v| (v.name.as_str(), graph.add_node(v.flow_rate)))
+            .collect();
+        for valve in valves {
+            for tunnel in &valve.tunnels {
+                let to = nodes
+                    .get(tunnel.as_str())
+                    .ok_or_else(|| Error::UnknownValve(tunnel.clone()))?;
+                graph.add_edge(nodes[valve.name.as_str()], *to, ());
+            }
+        }
+        let start_node = *nodes.get(START_VALVE).ok_or(Error::MissingStart)?;
+        let all_distances = floyd_warshall(&graph, |_| 1u32)
+            .expect("unit tunnel lengths cannot form a negative cycle");
+
+        let mut useful = graph
+            .node_indices()
+            .filter(|n| graph[*n] > 0)
+            .collect::<Vec<_>>();
+        if useful.len() > OpenedValves::BITS as usize {
+            return Err(Error::TooManyValves(useful.len()));
+        }
+        useful.push(start_node);
+        let distances = useful
+            .iter()
+            .map(|from| {
+                useful
+                    .iter()
+                    .map(|to| all_distances[&(*from, *to)])
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self {
+            flow_rates: useful.iter().map(|n| graph[*n]).collect(),
+            distances,
+            start: useful.len() - 1,
+        })
+    }
+
+    /// Best pressure released in the given time for every set of opened valves that can be reached.
+    fn best_pressures(&self, minutes: u32) -> BTreeMap<OpenedValves, u32> {
+        let mut best = BTreeMap::new();
+        self.explore(self.start, minutes, 0, 0, &mut best);
+        best
+    }
+
+    fn explore(
+        &self,
+        at: usize,
+        time_left: u32,
+        opened: OpenedValves,
+        released: u32,
+        best: &mut BTreeMap<OpenedValves, u32>,
+    ) {
+        let entry = best.entry(opened).or_insert(0);
+        *entry = (*entry).max(released);
+        for next in 0..self.start {
+            let travel = self.distances[at][next].saturating_add(1);
+            if opened & (1 << next) != 0 || travel >= time_left {
+                continue;
+            }
+            let time_left = time_left - travel;
+            self.explore(
+                next,
+                time_left,
+                opened | (1 << next),
+                released + time_left * self.flow_rates[next],
+                best,
+            );
+        }
+    }
+
+    fn max_pressure_alone(&self, minutes: u32) -> u32 {
+        self.best_pressures(minutes)
+            .into_values()
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn max_pressure_with_elephant(&self, minutes: u32) -> u32 {
+        let mut best = self.best_pressures(minutes).into_iter().collect::<Vec<_>>();
+        best.sort_by_key(|&(_, released)| std::cmp::Reverse(released));
+        let mut max = 0;
+        for (i, (mine, my_released)) in best.iter().enumerate() {
+            if my_released * 2 < max {
+                break;
+            }
+            for (elephant, elephant_released) in &best[i..] {
+                if my_released + elephant_released <= max {
+                    break;
+                }
+                if mine & elephant == 0 {
+                    max = my_released + elephant_released;
+                }
+            }
+        }
+        max
+    }
+}
+
+fn valve<'a, E>(i: &'a str) -> IResult<&'a str, Valve, E>
+where
+    E: ParseError<&'a str>,
+{
+    map(
+        tuple((
+            preceded(tag("Valve "), alpha1),
+            preceded(tag(" has flow rate="), u32),
+            preceded(
+                alt((
+                    tag("; tunnels lead to valves "),
+                    tag("; tunnel leads to valve "),
+                )),
+                separated_list1(tag(", "), alpha1),
+            ),
+        )),
+        |(name, flow_rate, tunnels)| Valve::new(name, flow_rate, &tunnels),
+    )(i)
+}
+
+fn valves<'a, E>(i: &'a str) -> IResult<&'a str, Vec<Valve>, E>
+where
+    E: ParseError<&'a str>,
+{
+    separated_list1(line_ending, valve)(i)
+}
+
+pub fn parse(data: &str) -> Result<Vec<Valve>> {
+    let valves = parse_all(data, valves)?;
+    Ok(valves)
+}
+
+pub fn solve(valves: &[Valve]) -> Result<(u32, u32)> {
+    let volcano = Volcano::new(valves)?;
+    Ok((
+        volcano.max_pressure_alone(SOLO_MINUTES),
+        volcano.max_pressure_with_elephant(WITH_ELEPHANT_MINUTES),
+    ))
+}
+
+pub fn most_released_pressure_from_str(data: &str) -> Result<(u32, u32)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn parse_valve() {
+        let v = valve::<()>("Valve HH has flow rate=22; tunnel leads to valve GG");
+
+        assert_that!(v)
+            .is_ok()
+            .is_equal_to(("", Valve::new("HH", 22, &["GG"])));
+    }
+
+    #[test]
+    fn parse_valve_with_many_tunnels() {
+        let v = valve::<()>("Valve AA has flow rate=0; tunnels lead to valves DD, II, BB");
+
+        assert_that!(v)
+            .is_ok()
+            .is_equal_to(("", Valve::new("AA", 0, &["DD", "II", "BB"])));
+    }
+
+    #[test]
+    fn unknown_tunnel_target() {
+        let valves = vec![Valve::new("AA", 0, &["ZZ"])];
+
+        assert_that!(Volcano::new(&valves)).is_err();
+    }
+
+    #[test]
+    fn too_many_valves() {
+        let names = (0..33u8)
+            .map(|i| String::from_utf8(vec![b'B' + i / 26, b'A' + i % 26]).unwrap())
+            .collect::<Vec<_>>();
+        let mut valves = vec![Valve::new("AA", 0, &["BA"])];
+        valves.extend(names.iter().map(|name| Valve::new(name, 1, &["AA"])));
+
+        assert_that!(Volcano::new(&valves).map_err(|e| e.to_string()))
+            .is_err()
+            .is_equal_to(
+                "33 valves have a positive flow rate, at most 32 can be opened".to_owned(),
+            );
+    }
+
+    #[test]
+    fn example_alone() {
+        let (_, valves) = valves::<()>(EXAMPLE).unwrap();
+        let volcano = Volcano::new(&valves).unwrap();
+
+        assert_that!(volcano.max_pressure_alone(30)).is_equal_to(1651);
+    }
+
+    #[test]
+    fn example_with_elephant() {
+        let (_, valves) = valves::<()>(EXAMPLE).unwrap();
+        let volcano = Volcano::new(&valves).unwrap();
+
+        assert_that!(volcano.max_pressure_with_elephant(26)).is_equal_to(1707);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(most_released_pressure_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((1651, 1707));
+    }
+}
@@ -0,0 +1,217 @@
+use crate::diagnostics::parse_all;
+use crate::error::Result;
+use nom::{
+    branch::alt, character::complete::char, combinator::value, error::ParseError, multi::many1,
+    IResult,
+};
+use std::collections::BTreeMap;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+const FEW_ROCKS: u64 = 2022;
+const MANY_ROCKS: u64 = 1_000_000_000_000;
+/// How many of the topmost rows are compared to detect that the tower repeats itself.
+const PROFILE_DEPTH: usize = 32;
+
+// Each rock is a list of rows from bottom to top, the leftmost column of the chamber being the
+// seventh bit, so that a fresh rock appears two units away from the left wall.
+const ROCKS: [&[u8]; 5] = [
+    &[0b0011110],
+    &[0b0001000, 0b0011100, 0b0001000],
+    &[0b0011100, 0b0000100, 0b0000100],
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    &[0b0011000, 0b0011000],
+];
+const LEFT_WALL: u8 = 0b1000000;
+const RIGHT_WALL: u8 = 0b0000001;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+impl Jet {
+    fn push(self, rock: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            Jet::Left if rock.iter().all(|r| r & LEFT_WALL == 0) => {
+                Some(rock.iter().map(|r| r << 1).collect())
+            }
+            Jet::Right if rock.iter().all(|r| r & RIGHT_WALL == 0) => {
+                Some(rock.iter().map(|r| r >> 1).collect())
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Chamber<'a> {
+    rows: Vec<u8>,
+    jets: &'a [Jet],
+    next_jet: usize,
+    next_rock: usize,
+}
+
+impl<'a> Chamber<'a> {
+    fn new(jets: &'a [Jet]) -> Self {
+        Self {
+            rows: vec![],
+            jets,
+            next_jet: 0,
+            next_rock: 0,
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn fits(&self, rock: &[u8], bottom: usize) -> bool {
+        rock.iter()
+            .enumerate()
+            .all(|(i, r)| self.rows.get(bottom + i).is_none_or(|row| row & r == 0))
+    }
+
+    fn drop_rock(&mut self) {
+        let mut rock = ROCKS[self.next_rock].to_vec();
+        self.next_rock = (self.next_rock + 1) % ROCKS.len();
+        let mut bottom = self.height() + 3;
+        loop {
+            let jet = self.jets[self.next_jet];
+            self.next_jet = (self.next_jet + 1) % self.jets.len();
+            if let Some(pushed) = jet.push(&rock) {
+                if self.fits(&pushed, bottom) {
+                    rock = pushed;
+                }
+            }
+            if bottom == 0 || !self.fits(&rock, bottom - 1) {
+                break;
+            }
+            bottom -= 1;
+        }
+        for (i, r) in rock.into_iter().enumerate() {
+            match self.rows.get_mut(bottom + i) {
+                Some(row) => *row |= r,
+                None => self.rows.push(r),
+            }
+        }
+    }
+
+    fn profile(&self) -> Vec<u8> {
+        self.rows
+            .iter()
+            .rev()
+            .take(PROFILE_DEPTH)
+            .copied()
+            .collect()
+    }
+}
+
+fn tower_height(jets: &[Jet], rocks: u64) -> u64 {
+    let mut chamber = Chamber::new(jets);
+    let mut seen: BTreeMap<(usize, usize, Vec<u8>), (u64, usize)> = BTreeMap::new();
+    let mut skipped_height = 0;
+    let mut dropped = 0;
+    while dropped < rocks {
+        chamber.drop_rock();
+        dropped += 1;
+        if skipped_height != 0 {
+            continue;
+        }
+        let state = (chamber.next_rock, chamber.next_jet, chamber.profile());
+        if let Some((previous_dropped, previous_height)) =
+            seen.insert(state, (dropped, chamber.height()))
+        {
+            let cycle_length = dropped - previous_dropped;
+            let cycle_height = (chamber.height() - previous_height) as u64;
+            let cycles = (rocks - dropped) / cycle_length;
+            dropped += cycles * cycle_length;
+            skipped_height = cycles * cycle_height;
+        }
+    }
+    chamber.height() as u64 + skipped_height
+}
+
+fn jets<'a, E>(i: &'a str) -> IResult<&'a str, Vec<Jet>, E>
+where
+    E: ParseError<&'a str>,
+{
+    many1(alt((
+        value(Jet::Left, char('<')),
+        value(Jet::Right, char('>')),
+    )))(i)
+}
+
+pub fn parse(data: &str) -> Result<Vec<Jet>> {
+    let jets = parse_all(data, jets)?;
+    Ok(jets)
+}
+
+pub fn solve(jets: &[Jet]) -> Result<(u64, u64)> {
+    Ok((
+        tower_height(jets, FEW_ROCKS),
+        tower_height(jets, MANY_ROCKS),
+    ))
+}
+
+pub fn tower_heights_from_str(data: &str) -> Result<(u64, u64)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn parse_jets() {
+        let j = jets::<()>("<><");
+
+        assert_that!(j)
+            .is_ok()
+            .is_equal_to(("", vec![Jet::Left, Jet::Right, Jet::Left]));
+    }
+
+    #[test]
+    fn rocks_stop_at_walls() {
+        assert_that!(Jet::Right.push(&[0b0000011])).is_none();
+        assert_that!(Jet::Left.push(&[0b0000011]))
+            .is_some()
+            .is_equal_to(vec![0b0000110]);
+    }
+
+    #[test]
+    fn first_rocks() {
+        let (_, jets) = jets::<()>(EXAMPLE).unwrap();
+        let mut chamber = Chamber::new(&jets);
+
+        chamber.drop_rock();
+        chamber.drop_rock();
+
+        assert_that!(chamber.rows).is_equal_to(vec![0b0011110, 0b0001000, 0b0011100, 0b0001000]);
+    }
+
+    #[test]
+    fn example_few_rocks() {
+        let (_, jets) = jets::<()>(EXAMPLE).unwrap();
+
+        assert_that!(tower_height(&jets, 2022)).is_equal_to(3068);
+    }
+
+    #[test]
+    fn example_many_rocks() {
+        let (_, jets) = jets::<()>(EXAMPLE).unwrap();
+
+        assert_that!(tower_height(&jets, 1_000_000_000_000)).is_equal_to(1514285714288);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(tower_heights_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((3068, 1514285714288));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::diagnostics::parse_all;
+use crate::error::Result;
+use nom::{
+    character::complete::{char, i32, line_ending},
+    combinator::map,
+    error::ParseError,
+    multi::separated_list1,
+    sequence::{terminated, tuple},
+    IResult,
+};
+use std::collections::BTreeSet;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+pub type Cube = (i32, i32, i32);
+
+fn neighbours(cube: &Cube) -> [Cube; 6] {
+    let (x, y, z) = *cube;
+    [
+        (x - 1, y, z),
+        (x + 1, y, z),
+        (x, y - 1, z),
+        (x, y + 1, z),
+        (x, y, z - 1),
+        (x, y, z + 1),
+    ]
+}
+
+fn surface_area(droplet: &BTreeSet<Cube>) -> usize {
+    droplet
+        .iter()
+        .flat_map(neighbours)
+        .filter(|n| !droplet.contains(n))
+        .count()
+}
+
+/// Fills the air around the droplet, in a box one unit larger than the droplet itself so that
+/// the air can flow all around it.
+fn outside_air(droplet: &BTreeSet<Cube>) -> BTreeSet<Cube> {
+    let min = droplet.iter().fold((i32::MAX, i32::MAX, i32::MAX), |m, c| {
+        (m.0.min(c.0 - 1), m.1.min(c.1 - 1), m.2.min(c.2 - 1))
+    });
+    let max = droplet.iter().fold((i32::MIN, i32::MIN, i32::MIN), |m, c| {
+        (m.0.max(c.0 + 1), m.1.max(c.1 + 1), m.2.max(c.2 + 1))
+    });
+    let in_bounds = |c: &Cube| {
+        (min.0..=max.0).contains(&c.0)
+            && (min.1..=max.1).contains(&c.1)
+            && (min.2..=max.2).contains(&c.2)
+    };
+
+    let mut air = BTreeSet::from([min]);
+    let mut to_visit = vec![min];
+    while let Some(cube) = to_visit.pop() {
+        for n in neighbours(&cube) {
+            if in_bounds(&n) && !droplet.contains(&n) && air.insert(n) {
+                to_visit.push(n);
+            }
+        }
+    }
+    air
+}
+
+fn exterior_surface_area(droplet: &BTreeSet<Cube>) -> usize {
+    let air = outside_air(droplet);
+    droplet
+        .iter()
+        .flat_map(neighbours)
+        .filter(|n| air.contains(n))
+        .count()
+}
+
+fn cube<'a, E>(i: &'a str) -> IResult<&'a str, Cube, E>
+where
+    E: ParseError<&'a str>,
+{
+    map(
+        tuple((terminated(i32, char(',')), terminated(i32, char(',')), i32)),
+        |(x, y, z)| (x, y, z),
+    )(i)
+}
+
+fn droplet<'a, E>(i: &'a str) -> IResult<&'a str, BTreeSet<Cube>, E>
+where
+    E: ParseError<&'a str>,
+{
+    map(separated_list1(line_ending, cube), |cubes| {
+        cubes.into_iter().collect()
+    })(i)
+}
+
+pub fn parse(data: &str) -> Result<BTreeSet<Cube>> {
+    let droplet = parse_all(data, droplet)?;
+    Ok(droplet)
+}
+
+pub fn solve(droplet: &BTreeSet<Cube>) -> Result<(usize, usize)> {
+    Ok((surface_area(droplet), exterior_surface_area(droplet)))
+}
+
+pub fn droplet_surface_from_str(data: &str) -> Result<(usize, usize)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn parse_cube() {
+        assert_that!(cube::<()>("2,-1,5"))
+            .is_ok()
+            .is_equal_to(("", (2, -1, 5)));
+    }
+
+    #[test]
+    fn two_adjacent_cubes() {
+        let droplet = BTreeSet::from([(1, 1, 1), (2, 1, 1)]);
+
+        assert_that!(surface_area(&droplet)).is_equal_to(10);
+    }
+
+    #[test]
+    fn example_surface_area() {
+        let (_, droplet) = droplet::<()>(EXAMPLE).unwrap();
+
+        assert_that!(surface_area(&droplet)).is_equal_to(64);
+    }
+
+    #[test]
+    fn example_exterior_surface_area() {
+        let (_, droplet) = droplet::<()>(EXAMPLE).unwrap();
+
+        assert_that!(exterior_surface_area(&droplet)).is_equal_to(58);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(droplet_surface_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((64, 58));
+    }
+}
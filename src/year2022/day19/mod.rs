@@ -0,0 +1,320 @@
+use crate::diagnostics::parse_all;
+use crate::error::Result;
+use crate::progress;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{multispace0, multispace1, u32},
+    combinator::map,
+    error::ParseError,
+    multi::many1,
+    sequence::{delimited, preceded, tuple},
+    IResult,
+};
+use rayon::prelude::*;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+const QUALITY_MINUTES: u32 = 24;
+const LONG_MINUTES: u32 = 32;
+const REMAINING_BLUEPRINTS: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Blueprint {
+    id: u32,
+    ore_robot_cost: u32,
+    clay_robot_cost: u32,
+    obsidian_robot_cost: (u32, u32),
+    geode_robot_cost: (u32, u32),
+}
+
+impl Blueprint {
+    fn max_ore_cost(&self) -> u32 {
+        self.ore_robot_cost
+            .max(self.clay_robot_cost)
+            .max(self.obsidian_robot_cost.0)
+            .max(self.geode_robot_cost.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Resources {
+    ore: u32,
+    clay: u32,
+    obsidian: u32,
+    geodes: u32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct State {
+    time_left: u32,
+    stock: Resources,
+    robots: Resources,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Robot {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+impl State {
+    fn new(minutes: u32) -> Self {
+        Self {
+            time_left: minutes,
+            stock: Resources::default(),
+            robots: Resources {
+                ore: 1,
+                ..Resources::default()
+            },
+        }
+    }
+
+    /// Geodes we would get by doing nothing but waiting.
+    fn expected_geodes(&self) -> u32 {
+        self.stock.geodes + self.robots.geodes * self.time_left
+    }
+
+    /// Geodes we would get if we could build a geode robot every remaining minute.
+    fn optimistic_geodes(&self) -> u32 {
+        self.expected_geodes() + self.time_left * self.time_left.saturating_sub(1) / 2
+    }
+
+    /// Minutes needed to gather `cost` of a resource, given the current stock and robots.
+    fn wait_for(cost: u32, stock: u32, robots: u32) -> Option<u32> {
+        if stock >= cost {
+            Some(0)
+        } else {
+            (robots > 0).then(|| (cost - stock).div_ceil(robots))
+        }
+    }
+
+    /// Waits until the robot can be built, then builds it; `None` if it cannot be done in time.
+    fn build(&self, robot: Robot, blueprint: &Blueprint) -> Option<Self> {
+        let (ore, clay, obsidian) = match robot {
+            Robot::Ore => (blueprint.ore_robot_cost, 0, 0),
+            Robot::Clay => (blueprint.clay_robot_cost, 0, 0),
+            Robot::Obsidian => (
+                blueprint.obsidian_robot_cost.0,
+                blueprint.obsidian_robot_cost.1,
+                0,
+            ),
+            Robot::Geode => (
+                blueprint.geode_robot_cost.0,
+                0,
+                blueprint.geode_robot_cost.1,
+            ),
+        };
+        let wait = Self::wait_for(ore, self.stock.ore, self.robots.ore)?
+            .max(Self::wait_for(clay, self.stock.clay, self.robots.clay)?)
+            .max(Self::wait_for(
+                obsidian,
+                self.stock.obsidian,
+                self.robots.obsidian,
+            )?);
+        if wait + 1 >= self.time_left {
+            return None;
+        }
+        let elapsed = wait + 1;
+        let mut next = Self {
+            time_left: self.time_left - elapsed,
+            stock: Resources {
+                ore: self.stock.ore + self.robots.ore * elapsed - ore,
+                clay: self.stock.clay + self.robots.clay * elapsed - clay,
+                obsidian: self.stock.obsidian + self.robots.obsidian * elapsed - obsidian,
+                geodes: self.stock.geodes + self.robots.geodes * elapsed,
+            },
+            robots: self.robots,
+        };
+        match robot {
+            Robot::Ore => next.robots.ore += 1,
+            Robot::Clay => next.robots.clay += 1,
+            Robot::Obsidian => next.robots.obsidian += 1,
+            Robot::Geode => next.robots.geodes += 1,
+        }
+        Some(next)
+    }
+}
+
+fn search(blueprint: &Blueprint, state: State, best: &mut u32) {
+    *best = (*best).max(state.expected_geodes());
+    if state.optimistic_geodes() <= *best {
+        return;
+    }
+    // A robot is useless once we produce each minute all we can spend of its resource.
+    let useful_robots = [
+        (Robot::Geode, true),
+        (
+            Robot::Obsidian,
+            state.robots.obsidian < blueprint.geode_robot_cost.1,
+        ),
+        (
+            Robot::Clay,
+            state.robots.clay < blueprint.obsidian_robot_cost.1,
+        ),
+        (Robot::Ore, state.robots.ore < blueprint.max_ore_cost()),
+    ];
+    for (robot, useful) in useful_robots {
+        if let Some(next) = useful.then(|| state.build(robot, blueprint)).flatten() {
+            search(blueprint, next, best);
+        }
+    }
+}
+
+fn max_geodes(blueprint: &Blueprint, minutes: u32) -> u32 {
+    let mut best = 0;
+    search(blueprint, State::new(minutes), &mut best);
+    best
+}
+
+fn quality_levels(blueprints: &[Blueprint]) -> u32 {
+    // the blueprints are examined on other threads: the progress must be taken from this one
+    let progress = progress::current();
+    blueprints
+        .par_iter()
+        .map(|b| b.id * max_geodes(b, QUALITY_MINUTES))
+        .inspect(|_| progress.inc(1))
+        .sum()
+}
+
+fn remaining_blueprints_geodes(blueprints: &[Blueprint]) -> u32 {
+    let progress = progress::current();
+    blueprints
+        .par_iter()
+        .take(REMAINING_BLUEPRINTS)
+        .map(|b| max_geodes(b, LONG_MINUTES))
+        .inspect(|_| progress.inc(1))
+        .product()
+}
+
+fn blueprint<'a, E>(i: &'a str) -> IResult<&'a str, Blueprint, E>
+where
+    E: ParseError<&'a str>,
+{
+    map(
+        tuple((
+            delimited(tag("Blueprint "), u32, tag(":")),
+            delimited(
+                preceded(multispace1, tag("Each ore robot costs ")),
+                u32,
+                tag(" ore."),
+            ),
+            delimited(
+                preceded(multispace1, tag("Each clay robot costs ")),
+                u32,
+                tag(" ore."),
+            ),
+            tuple((
+                delimited(
+                    preceded(multispace1, tag("Each obsidian robot costs ")),
+                    u32,
+                    tag(" ore"),
+                ),
+                delimited(tag(" and "), u32, tag(" clay.")),
+            )),
+            tuple((
+                delimited(
+                    preceded(multispace1, tag("Each geode robot costs ")),
+                    u32,
+                    tag(" ore"),
+                ),
+                delimited(tag(" and "), u32, tag(" obsidian.")),
+            )),
+        )),
+        |(id, ore_robot_cost, clay_robot_cost, obsidian_robot_cost, geode_robot_cost)| Blueprint {
+            id,
+            ore_robot_cost,
+            clay_robot_cost,
+            obsidian_robot_cost,
+            geode_robot_cost,
+        },
+    )(i)
+}
+
+fn blueprints<'a, E>(i: &'a str) -> IResult<&'a str, Vec<Blueprint>, E>
+where
+    E: ParseError<&'a str>,
+{
+    many1(delimited(multispace0, blueprint, multispace0))(i)
+}
+
+pub fn parse(data: &str) -> Result<Vec<Blueprint>> {
+    let blueprints = parse_all(data, blueprints)?;
+    Ok(blueprints)
+}
+
+pub fn solve(blueprints: &[Blueprint]) -> Result<(u32, u32)> {
+    progress::current()
+        .set_length((blueprints.len() + blueprints.len().min(REMAINING_BLUEPRINTS)) as u64);
+    Ok((
+        quality_levels(blueprints),
+        remaining_blueprints_geodes(blueprints),
+    ))
+}
+
+pub fn geode_collection_from_str(data: &str) -> Result<(u32, u32)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn parse_single_line_blueprint() {
+        let b = blueprint::<()>("Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.");
+
+        assert_that!(b).is_ok().is_equal_to((
+            "",
+            Blueprint {
+                id: 1,
+                ore_robot_cost: 4,
+                clay_robot_cost: 2,
+                obsidian_robot_cost: (3, 14),
+                geode_robot_cost: (2, 7),
+            },
+        ));
+    }
+
+    #[test]
+    fn parse_example() {
+        let (rest, blueprints) = blueprints::<()>(EXAMPLE).unwrap();
+
+        assert_that!(rest).is_equal_to("");
+        assert_that!(blueprints.len()).is_equal_to(2);
+    }
+
+    #[test]
+    fn example_max_geodes() {
+        let (_, blueprints) = blueprints::<()>(EXAMPLE).unwrap();
+
+        assert_that!(max_geodes(&blueprints[0], 24)).is_equal_to(9);
+        assert_that!(max_geodes(&blueprints[1], 24)).is_equal_to(12);
+    }
+
+    #[test]
+    fn example_quality_levels() {
+        let (_, blueprints) = blueprints::<()>(EXAMPLE).unwrap();
+
+        assert_that!(quality_levels(&blueprints)).is_equal_to(33);
+    }
+
+    #[test]
+    fn example_long_run() {
+        let (_, blueprints) = blueprints::<()>(EXAMPLE).unwrap();
+
+        assert_that!(max_geodes(&blueprints[0], 32)).is_equal_to(56);
+        assert_that!(max_geodes(&blueprints[1], 32)).is_equal_to(62);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(geode_collection_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((33, 3472));
+    }
+}
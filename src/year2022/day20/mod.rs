@@ -0,0 +1,159 @@
+use crate::diagnostics::parse_all;
+use crate::error::{PuzzleError, Result};
+use nom::{
+    character::complete::{i64, line_ending},
+    error::ParseError,
+    multi::separated_list1,
+    IResult,
+};
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+const DECRYPTION_KEY: i64 = 811_589_153;
+const DECRYPTION_ROUNDS: usize = 10;
+const GROVE_COORDINATE_OFFSETS: [usize; 3] = [1000, 2000, 3000];
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+enum Error {
+    #[error("No zero in the encrypted file")]
+    MissingZero,
+    #[error("Number {0} overflows once decrypted")]
+    Overflow(i64),
+    #[error("The grove coordinates overflow")]
+    CoordinatesOverflow,
+}
+
+impl PuzzleError for Error {}
+
+/// Mixes the numbers the given number of times, and returns them in their final order.
+///
+/// Numbers are moved by their original index rather than their value, as values are not unique.
+fn mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    if numbers.len() < 2 {
+        return numbers.to_vec();
+    }
+    let mut order = (0..numbers.len()).collect::<Vec<_>>();
+    let cycle = numbers.len() as i64 - 1;
+    for _ in 0..rounds {
+        for (original_index, value) in numbers.iter().enumerate() {
+            let position = order
+                .iter()
+                .position(|&i| i == original_index)
+                .expect("every original index is in the mixing order");
+            order.remove(position);
+            // reduced first, so that a large value cannot overflow
+            let new_position = (position as i64 + value.rem_euclid(cycle)) % cycle;
+            let new_position = new_position as usize;
+            order.insert(new_position, original_index);
+        }
+    }
+    order.into_iter().map(|i| numbers[i]).collect()
+}
+
+fn grove_coordinates(mixed: &[i64]) -> Result<i64, Error> {
+    let zero = mixed
+        .iter()
+        .position(|&v| v == 0)
+        .ok_or(Error::MissingZero)?;
+    GROVE_COORDINATE_OFFSETS
+        .iter()
+        .map(|offset| mixed[(zero + offset) % mixed.len()])
+        .try_fold(0i64, |sum, v| sum.checked_add(v))
+        .ok_or(Error::CoordinatesOverflow)
+}
+
+fn decrypt(numbers: &[i64]) -> Result<i64, Error> {
+    let numbers = numbers
+        .iter()
+        .map(|&v| v.checked_mul(DECRYPTION_KEY).ok_or(Error::Overflow(v)))
+        .collect::<Result<Vec<_>, _>>()?;
+    grove_coordinates(&mix(&numbers, DECRYPTION_ROUNDS))
+}
+
+fn encrypted_file<'a, E>(i: &'a str) -> IResult<&'a str, Vec<i64>, E>
+where
+    E: ParseError<&'a str>,
+{
+    separated_list1(line_ending, i64)(i)
+}
+
+pub fn parse(data: &str) -> Result<Vec<i64>> {
+    let numbers = parse_all(data, encrypted_file)?;
+    Ok(numbers)
+}
+
+pub fn solve(numbers: &[i64]) -> Result<(i64, i64)> {
+    Ok((grove_coordinates(&mix(numbers, 1))?, decrypt(numbers)?))
+}
+
+pub fn grove_positioning_from_str(data: &str) -> Result<(i64, i64)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    const NUMBERS: [i64; 7] = [1, 2, -3, 3, -2, 0, 4];
+
+    #[test]
+    fn parse_encrypted_file() {
+        assert_that!(encrypted_file::<()>("1\n2\n-3\n"))
+            .is_ok()
+            .is_equal_to(("\n", vec![1, 2, -3]));
+    }
+
+    #[test]
+    fn mix_once() {
+        let mixed = mix(&NUMBERS, 1);
+
+        // the example starts the list at 1, but it is circular: only the order matters
+        let start = mixed.iter().position(|&v| v == 1).unwrap();
+        let rotated = [&mixed[start..], &mixed[..start]].concat();
+        assert_that!(rotated).is_equal_to(vec![1, 2, -3, 4, 0, 3, -2]);
+    }
+
+    #[test]
+    fn mix_with_duplicates() {
+        let mixed = mix(&[0, 1, 1], 1);
+
+        assert_that!(mixed.len()).is_equal_to(3);
+        assert_that!(mixed.iter().filter(|&&v| v == 1).count()).is_equal_to(2);
+    }
+
+    #[test]
+    fn example_grove_coordinates() {
+        assert_that!(grove_coordinates(&mix(&NUMBERS, 1)))
+            .is_ok()
+            .is_equal_to(3);
+    }
+
+    #[test]
+    fn example_decrypted_grove_coordinates() {
+        assert_that!(decrypt(&NUMBERS))
+            .is_ok()
+            .is_equal_to(1623178306);
+    }
+
+    #[test]
+    fn overflowing_numbers() {
+        let big = i64::MAX / 2;
+
+        assert_that!(decrypt(&[0, big]))
+            .is_err()
+            .is_equal_to(Error::Overflow(big));
+        assert_that!(grove_coordinates(&[0, 1, 1, 1, big, big, big]))
+            .is_err()
+            .is_equal_to(Error::CoordinatesOverflow);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(grove_positioning_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((3, 1623178306));
+    }
+}
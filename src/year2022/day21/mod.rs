@@ -0,0 +1,345 @@
+use crate::diagnostics::parse_all;
+use crate::error::{PuzzleError, Result};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, char, i64, line_ending, one_of},
+    combinator::map,
+    error::ParseError,
+    multi::separated_list1,
+    sequence::{delimited, separated_pair, tuple},
+    IResult,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+const ROOT: &str = "root";
+const HUMAN: &str = "humn";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Operator {
+    fn apply(self, l: i64, r: i64) -> Result<i64, Error> {
+        match self {
+            Operator::Add => l.checked_add(r).ok_or(Error::Overflow),
+            Operator::Sub => l.checked_sub(r).ok_or(Error::Overflow),
+            Operator::Mul => l.checked_mul(r).ok_or(Error::Overflow),
+            Operator::Div if r == 0 => Err(Error::DivisionByZero),
+            Operator::Div => l.checked_div(r).ok_or(Error::Overflow),
+        }
+    }
+
+    /// Checks that `l op r == target`, which the inverted divisions may miss by truncating.
+    fn check(self, l: i64, r: i64, target: i64) -> Result<(), Error> {
+        if self.apply(l, r)? == target {
+            Ok(())
+        } else {
+            Err(Error::NoWholeSolution)
+        }
+    }
+
+    /// Finds `l` such that `l op r == target`.
+    fn solve_left(self, target: i64, r: i64) -> Result<i64, Error> {
+        let l = match self {
+            Operator::Add => target.checked_sub(r).ok_or(Error::Overflow)?,
+            Operator::Sub => target.checked_add(r).ok_or(Error::Overflow)?,
+            Operator::Mul if r == 0 => return Err(Error::DivisionByZero),
+            Operator::Mul => target.checked_div(r).ok_or(Error::Overflow)?,
+            Operator::Div => target.checked_mul(r).ok_or(Error::Overflow)?,
+        };
+        self.check(l, r, target)?;
+        Ok(l)
+    }
+
+    /// Finds `r` such that `l op r == target`.
+    fn solve_right(self, target: i64, l: i64) -> Result<i64, Error> {
+        let r = match self {
+            Operator::Add => target.checked_sub(l).ok_or(Error::Overflow)?,
+            Operator::Sub => l.checked_sub(target).ok_or(Error::Overflow)?,
+            Operator::Mul if l == 0 => return Err(Error::DivisionByZero),
+            Operator::Mul => target.checked_div(l).ok_or(Error::Overflow)?,
+            Operator::Div if target == 0 => return Err(Error::DivisionByZero),
+            Operator::Div => l.checked_div(target).ok_or(Error::Overflow)?,
+        };
+        self.check(l, r, target)?;
+        Ok(r)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Job {
+    Yell(i64),
+    Compute(String, Operator, String),
+}
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Unknown monkey {0}")]
+    UnknownMonkey(String),
+    #[error("Division by zero")]
+    DivisionByZero,
+    #[error("The human must appear exactly once on one side of root")]
+    HumanNotFound,
+    #[error("Monkey {0} waits for its own number")]
+    Cycle(String),
+    #[error("A number overflows")]
+    Overflow,
+    #[error("No whole number passes the equality test")]
+    NoWholeSolution,
+}
+
+impl PuzzleError for Error {}
+
+pub struct Troop {
+    jobs: BTreeMap<String, Job>,
+}
+
+impl Troop {
+    fn new(jobs: Vec<(String, Job)>) -> Self {
+        Self {
+            jobs: jobs.into_iter().collect(),
+        }
+    }
+
+    fn job(&self, name: &str) -> Result<&Job, Error> {
+        self.jobs
+            .get(name)
+            .ok_or_else(|| Error::UnknownMonkey(name.to_owned()))
+    }
+
+    /// Checks that no monkey waits for its own number, which would make the walks through the
+    /// jobs endless. Unknown monkeys are left to these walks.
+    fn check_cycles(&self) -> Result<(), Error> {
+        let mut checked = BTreeSet::new();
+        for start in self.jobs.keys() {
+            // the monkeys from `start` to the one being checked, which are waiting for it
+            let mut waiting = BTreeSet::new();
+            // each monkey is pushed to be visited, then to be marked as checked once the monkeys
+            // above it in the stack, which it waits for, are
+            let mut stack = vec![(start.as_str(), false)];
+            while let Some((name, visited)) = stack.pop() {
+                if visited {
+                    waiting.remove(name);
+                    checked.insert(name);
+                    continue;
+                }
+                if checked.contains(name) {
+                    continue;
+                }
+                if !waiting.insert(name) {
+                    return Err(Error::Cycle(name.to_owned()));
+                }
+                stack.push((name, true));
+                if let Some(Job::Compute(l, _, r)) = self.jobs.get(name) {
+                    stack.push((r.as_str(), false));
+                    stack.push((l.as_str(), false));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn eval(&self, name: &str) -> Result<i64, Error> {
+        match self.job(name)? {
+            Job::Yell(v) => Ok(*v),
+            Job::Compute(l, op, r) => op.apply(self.eval(l)?, self.eval(r)?),
+        }
+    }
+
+    fn depends_on_human(&self, name: &str) -> Result<bool, Error> {
+        if name == HUMAN {
+            return Ok(true);
+        }
+        match self.job(name)? {
+            Job::Yell(_) => Ok(false),
+            Job::Compute(l, _, r) => Ok(self.depends_on_human(l)? || self.depends_on_human(r)?),
+        }
+    }
+
+    /// Walks down from `name` to the human, inverting each operation on the way.
+    fn solve(&self, name: &str, target: i64) -> Result<i64, Error> {
+        if name == HUMAN {
+            return Ok(target);
+        }
+        match self.job(name)? {
+            Job::Yell(_) => Err(Error::HumanNotFound),
+            Job::Compute(l, op, r) => {
+                if self.depends_on_human(l)? {
+                    self.solve(l, op.solve_left(target, self.eval(r)?)?)
+                } else {
+                    self.solve(r, op.solve_right(target, self.eval(l)?)?)
+                }
+            }
+        }
+    }
+
+    fn human_number(&self) -> Result<i64, Error> {
+        match self.job(ROOT)? {
+            Job::Yell(_) => Err(Error::HumanNotFound),
+            Job::Compute(l, _, r) => match (self.depends_on_human(l)?, self.depends_on_human(r)?) {
+                (true, false) => self.solve(l, self.eval(r)?),
+                (false, true) => self.solve(r, self.eval(l)?),
+                _ => Err(Error::HumanNotFound),
+            },
+        }
+    }
+}
+
+fn operator<'a, E>(i: &'a str) -> IResult<&'a str, Operator, E>
+where
+    E: ParseError<&'a str>,
+{
+    map(
+        delimited(char(' '), one_of("+-*/"), char(' ')),
+        |op| match op {
+            '+' => Operator::Add,
+            '-' => Operator::Sub,
+            '*' => Operator::Mul,
+            '/' => Operator::Div,
+            _ => unreachable!(),
+        },
+    )(i)
+}
+
+fn job<'a, E>(i: &'a str) -> IResult<&'a str, Job, E>
+where
+    E: ParseError<&'a str>,
+{
+    alt((
+        map(i64, Job::Yell),
+        map(
+            tuple((alpha1, operator, alpha1)),
+            |(l, op, r): (&str, _, &str)| Job::Compute(l.to_owned(), op, r.to_owned()),
+        ),
+    ))(i)
+}
+
+fn monkey<'a, E>(i: &'a str) -> IResult<&'a str, (String, Job), E>
+where
+    E: ParseError<&'a str>,
+{
+    map(separated_pair(alpha1, tag(": "), job), |(name, job)| {
+        (name.to_owned(), job)
+    })(i)
+}
+
+fn monkeys<'a, E>(i: &'a str) -> IResult<&'a str, Vec<(String, Job)>, E>
+where
+    E: ParseError<&'a str>,
+{
+    separated_list1(line_ending, monkey)(i)
+}
+
+pub fn parse(data: &str) -> Result<Troop> {
+    let troop = Troop::new(parse_all(data, monkeys)?);
+    troop.check_cycles()?;
+    Ok(troop)
+}
+
+pub fn solve(troop: &Troop) -> Result<(i64, i64)> {
+    Ok((troop.eval(ROOT)?, troop.human_number()?))
+}
+
+pub fn monkey_math_from_str(data: &str) -> Result<(i64, i64)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn parse_monkeys() {
+        let m = monkeys::<()>("root: pppw + sjmn\ndbpl: 5");
+
+        assert_that!(m).is_ok().is_equal_to((
+            "",
+            vec![
+                (
+                    "root".to_owned(),
+                    Job::Compute("pppw".to_owned(), Operator::Add, "sjmn".to_owned()),
+                ),
+                ("dbpl".to_owned(), Job::Yell(5)),
+            ],
+        ));
+    }
+
+    #[test]
+    fn unknown_monkey() {
+        let (_, monkeys) = monkeys::<()>("root: abcd + efgh\nabcd: 5").unwrap();
+
+        assert_that!(Troop::new(monkeys).eval(ROOT)).is_err();
+    }
+
+    #[test]
+    fn overflowing_operations() {
+        assert!(matches!(
+            Operator::Add.apply(i64::MAX, 1),
+            Err(Error::Overflow)
+        ));
+        assert!(matches!(
+            Operator::Mul.solve_left(i64::MIN, -1),
+            Err(Error::Overflow)
+        ));
+    }
+
+    #[test]
+    fn inexact_inversions() {
+        assert!(matches!(
+            Operator::Mul.solve_left(7, 2),
+            Err(Error::NoWholeSolution)
+        ));
+        assert!(matches!(
+            Operator::Div.solve_right(4, 10),
+            Err(Error::NoWholeSolution)
+        ));
+        assert_that!(Operator::Div.solve_right(3, 10))
+            .is_ok()
+            .is_equal_to(3);
+    }
+
+    #[test]
+    fn cyclic_jobs() {
+        let data =
+            "root: aaaa + humn\naaaa: bbbb + cccc\nbbbb: aaaa * dddd\ncccc: 1\ndddd: 2\nhumn: 5";
+
+        assert_that!(parse(data).map(|_| ()).map_err(|e| e.to_string()))
+            .is_err()
+            .is_equal_to("Monkey aaaa waits for its own number".to_owned());
+    }
+
+    #[test]
+    fn example_root() {
+        let (_, monkeys) = monkeys::<()>(EXAMPLE).unwrap();
+
+        assert_that!(Troop::new(monkeys).eval(ROOT))
+            .is_ok()
+            .is_equal_to(152);
+    }
+
+    #[test]
+    fn example_human_number() {
+        let (_, monkeys) = monkeys::<()>(EXAMPLE).unwrap();
+
+        assert_that!(Troop::new(monkeys).human_number())
+            .is_ok()
+            .is_equal_to(301);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(monkey_math_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((152, 301));
+    }
+}
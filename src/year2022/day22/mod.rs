@@ -0,0 +1,458 @@
+use crate::diagnostics::parse_all;
+use crate::error::{PuzzleError, Result};
+use nom::{
+    branch::alt,
+    bytes::complete::take_while1,
+    character::complete::{char, line_ending, u32},
+    combinator::{map, value},
+    error::ParseError,
+    multi::many1,
+    sequence::{separated_pair, terminated},
+    IResult,
+};
+use std::collections::{BTreeMap, VecDeque};
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+const OPEN: u8 = b'.';
+const WALL: u8 = b'#';
+const VOID: u8 = b' ';
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Instruction {
+    Forward(u32),
+    TurnLeft,
+    TurnRight,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Facing {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Facing {
+    fn turn_left(self) -> Self {
+        match self {
+            Facing::Right => Facing::Up,
+            Facing::Down => Facing::Right,
+            Facing::Left => Facing::Down,
+            Facing::Up => Facing::Left,
+        }
+    }
+
+    fn turn_right(self) -> Self {
+        match self {
+            Facing::Right => Facing::Down,
+            Facing::Down => Facing::Left,
+            Facing::Left => Facing::Up,
+            Facing::Up => Facing::Right,
+        }
+    }
+
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Facing::Right => (0, 1),
+            Facing::Down => (1, 0),
+            Facing::Left => (0, -1),
+            Facing::Up => (-1, 0),
+        }
+    }
+
+    fn score(self) -> usize {
+        self as usize
+    }
+}
+
+type Position = (usize, usize);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Board {
+    tiles: Vec<Vec<u8>>,
+}
+
+impl Board {
+    fn new(lines: Vec<&str>) -> Self {
+        let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+        let tiles = lines
+            .into_iter()
+            .map(|l| {
+                let mut row = l.as_bytes().to_vec();
+                row.resize(width, VOID);
+                row
+            })
+            .collect();
+        Self { tiles }
+    }
+
+    fn height(&self) -> usize {
+        self.tiles.len()
+    }
+
+    fn width(&self) -> usize {
+        self.tiles.first().map_or(0, |r| r.len())
+    }
+
+    fn tile(&self, (row, col): Position) -> u8 {
+        self.tiles
+            .get(row)
+            .and_then(|r| r.get(col))
+            .copied()
+            .unwrap_or(VOID)
+    }
+
+    fn start(&self) -> Option<Position> {
+        self.tiles
+            .first()?
+            .iter()
+            .position(|t| *t == OPEN)
+            .map(|col| (0, col))
+    }
+
+    /// The tile next to the position, if it is on the map.
+    fn neighbour(&self, (row, col): Position, facing: Facing) -> Option<Position> {
+        let (dr, dc) = facing.delta();
+        let next = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+        (self.tile(next) != VOID).then_some(next)
+    }
+
+    fn surface_tiles(&self) -> usize {
+        self.tiles.iter().flatten().filter(|t| **t != VOID).count()
+    }
+}
+
+type Vec3 = [i32; 3];
+
+fn neg(v: Vec3) -> Vec3 {
+    [-v[0], -v[1], -v[2]]
+}
+
+fn dot(a: Vec3, b: Vec3) -> i32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(v: Vec3, k: i32) -> Vec3 {
+    [v[0] * k, v[1] * k, v[2] * k]
+}
+
+/// Where a face of the net ends up once folded: its outward normal, and the directions its
+/// columns (right) and rows (down) go along.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Face {
+    block: Position,
+    normal: Vec3,
+    right: Vec3,
+    down: Vec3,
+}
+
+impl Face {
+    fn axis(&self, facing: Facing) -> Vec3 {
+        match facing {
+            Facing::Right => self.right,
+            Facing::Down => self.down,
+            Facing::Left => neg(self.right),
+            Facing::Up => neg(self.down),
+        }
+    }
+
+    fn facing(&self, direction: Vec3) -> Option<Facing> {
+        [Facing::Right, Facing::Down, Facing::Left, Facing::Up]
+            .into_iter()
+            .find(|f| self.axis(*f) == direction)
+    }
+
+    /// The face glued to this one along the edge in the given direction, once folded.
+    fn fold(&self, block: Position, facing: Facing) -> Self {
+        let (normal, right, down) = match facing {
+            Facing::Right => (self.right, neg(self.normal), self.down),
+            Facing::Left => (neg(self.right), self.normal, self.down),
+            Facing::Down => (self.down, self.right, neg(self.normal)),
+            Facing::Up => (neg(self.down), self.right, self.normal),
+        };
+        Self {
+            block,
+            normal,
+            right,
+            down,
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("The map has no open tile to start from")]
+    NoStart,
+    #[error("The map is not the net of a cube")]
+    NotACube,
+}
+
+impl PuzzleError for Error {}
+
+/// The faces of the cube, indexed by their net block and by their normal.
+#[derive(Debug)]
+struct Cube {
+    size: usize,
+    faces: BTreeMap<Position, Face>,
+    by_normal: BTreeMap<Vec3, Position>,
+}
+
+impl Cube {
+    fn fold(board: &Board) -> Result<Self, Error> {
+        let size = ((board.surface_tiles() / 6) as f64).sqrt() as usize;
+        if size == 0 || size * size * 6 != board.surface_tiles() {
+            return Err(Error::NotACube);
+        }
+        let is_face = |(bi, bj): Position| board.tile((bi * size, bj * size)) != VOID;
+        let first = (0..board.width() / size)
+            .map(|bj| (0, bj))
+            .find(|b| is_face(*b))
+            .ok_or(Error::NotACube)?;
+
+        let mut faces = BTreeMap::new();
+        let mut to_visit = VecDeque::from([Face {
+            block: first,
+            normal: [0, 0, 1],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+        }]);
+        while let Some(face) = to_visit.pop_front() {
+            if faces.contains_key(&face.block) {
+                continue;
+            }
+            let (bi, bj) = face.block;
+            for facing in [Facing::Right, Facing::Down, Facing::Left, Facing::Up] {
+                let (di, dj) = facing.delta();
+                let next = bi
+                    .checked_add_signed(di)
+                    .zip(bj.checked_add_signed(dj))
+                    .filter(|(i, j)| i * size < board.height() && j * size < board.width());
+                if let Some(next) = next.filter(|b| is_face(*b)) {
+                    to_visit.push_back(face.fold(next, facing));
+                }
+            }
+            faces.insert(face.block, face);
+        }
+
+        let by_normal = faces
+            .values()
+            .map(|f| (f.normal, f.block))
+            .collect::<BTreeMap<_, _>>();
+        if faces.len() != 6 || by_normal.len() != 6 {
+            return Err(Error::NotACube);
+        }
+        Ok(Self {
+            size,
+            faces,
+            by_normal,
+        })
+    }
+
+    /// Walks over the edge of the current face, onto the adjacent face of the cube.
+    ///
+    /// Tiles are located by the (doubled, to stay on integers) coordinates of their centre,
+    /// with the centre of the cube at the origin.
+    fn wrap(&self, (row, col): Position, facing: Facing) -> (Position, Facing) {
+        let n = self.size as i32;
+        let from = self.faces[&(row / self.size, col / self.size)];
+        let (a, b) = ((row % self.size) as i32, (col % self.size) as i32);
+        let centre = add(
+            add(
+                scale(from.right, 2 * b + 1 - n),
+                scale(from.down, 2 * a + 1 - n),
+            ),
+            scale(from.normal, n),
+        );
+        let direction = from.axis(facing);
+        let centre = add(add(centre, direction), neg(from.normal));
+
+        let to = self.faces[&self.by_normal[&direction]];
+        let a = ((dot(centre, to.down) + n - 1) / 2) as usize;
+        let b = ((dot(centre, to.right) + n - 1) / 2) as usize;
+        let facing = to
+            .facing(neg(from.normal))
+            .expect("the direction of travel lies on the new face");
+        (
+            (to.block.0 * self.size + a, to.block.1 * self.size + b),
+            facing,
+        )
+    }
+}
+
+enum Wrapping {
+    Flat,
+    Cube(Cube),
+}
+
+impl Wrapping {
+    fn wrap(&self, board: &Board, position: Position, facing: Facing) -> (Position, Facing) {
+        match self {
+            Wrapping::Flat => {
+                let mut wrapped = position;
+                while let Some(previous) = board.neighbour(wrapped, facing.turn_left().turn_left())
+                {
+                    wrapped = previous;
+                }
+                (wrapped, facing)
+            }
+            Wrapping::Cube(cube) => cube.wrap(position, facing),
+        }
+    }
+}
+
+fn password(board: &Board, path: &[Instruction], wrapping: &Wrapping) -> Result<usize, Error> {
+    let mut position = board.start().ok_or(Error::NoStart)?;
+    let mut facing = Facing::Right;
+    for instruction in path {
+        match instruction {
+            Instruction::TurnLeft => facing = facing.turn_left(),
+            Instruction::TurnRight => facing = facing.turn_right(),
+            Instruction::Forward(steps) => {
+                for _ in 0..*steps {
+                    let (next, next_facing) = match board.neighbour(position, facing) {
+                        Some(next) => (next, facing),
+                        None => wrapping.wrap(board, position, facing),
+                    };
+                    if board.tile(next) == WALL {
+                        break;
+                    }
+                    (position, facing) = (next, next_facing);
+                }
+            }
+        }
+    }
+    Ok(1000 * (position.0 + 1) + 4 * (position.1 + 1) + facing.score())
+}
+
+fn board<'a, E>(i: &'a str) -> IResult<&'a str, Board, E>
+where
+    E: ParseError<&'a str>,
+{
+    map(
+        many1(terminated(
+            take_while1(|c| c == ' ' || c == '.' || c == '#'),
+            line_ending,
+        )),
+        Board::new,
+    )(i)
+}
+
+fn path<'a, E>(i: &'a str) -> IResult<&'a str, Vec<Instruction>, E>
+where
+    E: ParseError<&'a str>,
+{
+    many1(alt((
+        map(u32, Instruction::Forward),
+        value(Instruction::TurnLeft, char('L')),
+        value(Instruction::TurnRight, char('R')),
+    )))(i)
+}
+
+fn notes<'a, E>(i: &'a str) -> IResult<&'a str, (Board, Vec<Instruction>), E>
+where
+    E: ParseError<&'a str>,
+{
+    separated_pair(board, line_ending, path)(i)
+}
+
+pub fn parse(data: &str) -> Result<(Board, Vec<Instruction>)> {
+    let (board, path) = parse_all(data, notes)?;
+    Ok((board, path))
+}
+
+pub fn solve((board, path): &(Board, Vec<Instruction>)) -> Result<(usize, usize)> {
+    let flat = password(board, path, &Wrapping::Flat)?;
+    let cube = password(board, path, &Wrapping::Cube(Cube::fold(board)?))?;
+    Ok((flat, cube))
+}
+
+pub fn final_passwords_from_str(data: &str) -> Result<(usize, usize)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn parse_path() {
+        assert_that!(path::<()>("10R5L")).is_ok().is_equal_to((
+            "",
+            vec![
+                Instruction::Forward(10),
+                Instruction::TurnRight,
+                Instruction::Forward(5),
+                Instruction::TurnLeft,
+            ],
+        ));
+    }
+
+    #[test]
+    fn parse_notes() {
+        let (_, (board, path)) = notes::<()>(EXAMPLE).unwrap();
+
+        assert_that!(board.height()).is_equal_to(12);
+        assert_that!(board.width()).is_equal_to(16);
+        assert_that!(board.start()).is_some().is_equal_to((0, 8));
+        assert_that!(path.len()).is_equal_to(13);
+    }
+
+    #[test]
+    fn fold_example_net() {
+        let (_, (board, _)) = notes::<()>(EXAMPLE).unwrap();
+
+        let cube = Cube::fold(&board).unwrap();
+
+        assert_that!(cube.size).is_equal_to(4);
+        // the example walks from A (row 6, column 12) to B (row 9, column 15)
+        assert_that!(cube.wrap((5, 11), Facing::Right)).is_equal_to(((8, 14), Facing::Down));
+        // and from C (row 12, column 11) to D (row 8, column 2)
+        assert_that!(cube.wrap((11, 10), Facing::Down)).is_equal_to(((7, 1), Facing::Up));
+    }
+
+    #[test]
+    fn wrapping_twice_comes_back() {
+        let (_, (board, _)) = notes::<()>(EXAMPLE).unwrap();
+        let cube = Cube::fold(&board).unwrap();
+
+        let (there, facing) = cube.wrap((0, 8), Facing::Up);
+        let (back, back_facing) = cube.wrap(there, facing.turn_left().turn_left());
+
+        assert_that!(back).is_equal_to((0, 8));
+        assert_that!(back_facing).is_equal_to(Facing::Down);
+    }
+
+    #[test]
+    fn example_flat_password() {
+        let (_, (board, path)) = notes::<()>(EXAMPLE).unwrap();
+
+        assert_that!(password(&board, &path, &Wrapping::Flat))
+            .is_ok()
+            .is_equal_to(6032);
+    }
+
+    #[test]
+    fn example_cube_password() {
+        let (_, (board, path)) = notes::<()>(EXAMPLE).unwrap();
+        let cube = Cube::fold(&board).unwrap();
+
+        assert_that!(password(&board, &path, &Wrapping::Cube(cube)))
+            .is_ok()
+            .is_equal_to(5031);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(final_passwords_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((6032, 5031));
+    }
+}
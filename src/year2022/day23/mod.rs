@@ -0,0 +1,182 @@
+use crate::diagnostics::parse_all;
+use crate::error::Result;
+use nom::{
+    character::complete::{line_ending, one_of},
+    combinator::map,
+    error::ParseError,
+    multi::{many1, separated_list1},
+    IResult,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+const ROUNDS: usize = 10;
+
+pub type Position = (i32, i32);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Direction {
+    North,
+    South,
+    West,
+    East,
+}
+
+impl Direction {
+    /// The three positions an elf looks at before moving, the one it moves to being first.
+    fn looked_at(self, (x, y): Position) -> [Position; 3] {
+        match self {
+            Direction::North => [(x, y - 1), (x - 1, y - 1), (x + 1, y - 1)],
+            Direction::South => [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)],
+            Direction::West => [(x - 1, y), (x - 1, y - 1), (x - 1, y + 1)],
+            Direction::East => [(x + 1, y), (x + 1, y - 1), (x + 1, y + 1)],
+        }
+    }
+}
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::North,
+    Direction::South,
+    Direction::West,
+    Direction::East,
+];
+
+fn has_neighbours(elves: &HashSet<Position>, (x, y): Position) -> bool {
+    (-1..=1)
+        .flat_map(|dx| (-1..=1).map(move |dy| (dx, dy)))
+        .filter(|d| *d != (0, 0))
+        .any(|(dx, dy)| elves.contains(&(x + dx, y + dy)))
+}
+
+/// Plays a round, and tells whether any elf moved.
+fn round(elves: &mut HashSet<Position>, first_direction: usize) -> bool {
+    let mut proposals: HashMap<Position, Vec<Position>> = HashMap::new();
+    for elf in elves.iter().filter(|e| has_neighbours(elves, **e)) {
+        let proposal = (0..DIRECTIONS.len())
+            .map(|i| DIRECTIONS[(first_direction + i) % DIRECTIONS.len()].looked_at(*elf))
+            .find(|looked_at| looked_at.iter().all(|p| !elves.contains(p)));
+        if let Some([target, ..]) = proposal {
+            proposals.entry(target).or_default().push(*elf);
+        }
+    }
+    let mut moved = false;
+    for (target, candidates) in proposals {
+        if let [elf] = candidates[..] {
+            elves.remove(&elf);
+            elves.insert(target);
+            moved = true;
+        }
+    }
+    moved
+}
+
+/// Empty ground tiles in the smallest rectangle containing every elf, none without elves.
+fn empty_ground(elves: &HashSet<Position>) -> usize {
+    if elves.is_empty() {
+        return 0;
+    }
+    let (min_x, max_x, min_y, max_y) = elves.iter().fold(
+        (i32::MAX, i32::MIN, i32::MAX, i32::MIN),
+        |(min_x, max_x, min_y, max_y), (x, y)| {
+            (min_x.min(*x), max_x.max(*x), min_y.min(*y), max_y.max(*y))
+        },
+    );
+    ((max_x - min_x + 1) * (max_y - min_y + 1)) as usize - elves.len()
+}
+
+fn empty_ground_after_rounds(mut elves: HashSet<Position>, rounds: usize) -> usize {
+    for r in 0..rounds {
+        round(&mut elves, r);
+    }
+    empty_ground(&elves)
+}
+
+fn first_still_round(mut elves: HashSet<Position>) -> usize {
+    (0..)
+        .find(|r| !round(&mut elves, *r))
+        .map(|r| r + 1)
+        .unwrap_or_default()
+}
+
+fn elves<'a, E>(i: &'a str) -> IResult<&'a str, HashSet<Position>, E>
+where
+    E: ParseError<&'a str>,
+{
+    map(separated_list1(line_ending, many1(one_of(".#"))), |rows| {
+        rows.iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, c)| **c == '#')
+                    .map(move |(x, _)| (x as i32, y as i32))
+            })
+            .collect()
+    })(i)
+}
+
+pub fn parse(data: &str) -> Result<HashSet<Position>> {
+    let elves = parse_all(data, elves)?;
+    Ok(elves)
+}
+
+pub fn solve(elves: &HashSet<Position>) -> Result<(usize, usize)> {
+    Ok((
+        empty_ground_after_rounds(elves.clone(), ROUNDS),
+        first_still_round(elves.clone()),
+    ))
+}
+
+pub fn spread_elves_from_str(data: &str) -> Result<(usize, usize)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use spectral::prelude::*;
+
+    const SMALL_EXAMPLE: &str = ".....\n..##.\n..#..\n.....\n..##.\n.....";
+
+    #[test]
+    fn small_example_rounds() {
+        let (_, mut elves) = elves::<()>(SMALL_EXAMPLE).unwrap();
+
+        for r in 0..3 {
+            round(&mut elves, r);
+        }
+
+        assert_that!(elves).is_equal_to(HashSet::from([(2, 0), (4, 1), (0, 2), (4, 3), (2, 5)]));
+    }
+
+    #[test]
+    fn example_empty_ground() {
+        let (_, elves) = elves::<()>(EXAMPLE).unwrap();
+
+        assert_that!(empty_ground_after_rounds(elves, 10)).is_equal_to(110);
+    }
+
+    #[test]
+    fn example_first_still_round() {
+        let (_, elves) = elves::<()>(EXAMPLE).unwrap();
+
+        assert_that!(first_still_round(elves)).is_equal_to(20);
+    }
+
+    #[test]
+    fn no_elves() {
+        assert_that!(spread_elves_from_str("....\n...."))
+            .is_ok()
+            .is_equal_to((0, 1));
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(spread_elves_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((110, 20));
+    }
+}
@@ -0,0 +1,248 @@
+use crate::diagnostics::parse_all;
+use crate::error::{PuzzleError, Result};
+use nom::{
+    character::complete::{line_ending, one_of},
+    error::ParseError,
+    multi::{many1, separated_list1},
+    IResult,
+};
+use std::collections::{HashSet, VecDeque};
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+const GROUND: char = '.';
+
+type Position = (usize, usize);
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("The valley has no entrance or no exit")]
+    NoOpening,
+    #[error("There is no way through the blizzards")]
+    NoPath,
+    #[error("Row {row} of the valley is {width} tiles wide instead of {expected}")]
+    Ragged {
+        row: usize,
+        width: usize,
+        expected: usize,
+    },
+}
+
+impl PuzzleError for Error {}
+
+#[derive(Debug)]
+pub struct Valley {
+    grid: Vec<Vec<char>>,
+    entrance: Position,
+    exit: Position,
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Valley {
+    fn new(grid: Vec<Vec<char>>) -> Result<Self, Error> {
+        let opening = |row: usize| {
+            grid.get(row)
+                .and_then(|r| r.iter().position(|c| *c == GROUND))
+                .map(|col| (row, col))
+        };
+        let entrance = opening(0).ok_or(Error::NoOpening)?;
+        let exit = opening(grid.len().saturating_sub(1)).ok_or(Error::NoOpening)?;
+        if grid.len() < 3 || grid[0].len() < 3 {
+            return Err(Error::NoOpening);
+        }
+        let expected = grid[0].len();
+        if let Some((i, row)) = grid.iter().enumerate().find(|(_, r)| r.len() != expected) {
+            return Err(Error::Ragged {
+                row: i + 1,
+                width: row.len(),
+                expected,
+            });
+        }
+        Ok(Self {
+            grid,
+            entrance,
+            exit,
+        })
+    }
+
+    fn inner_height(&self) -> usize {
+        self.grid.len() - 2
+    }
+
+    fn inner_width(&self) -> usize {
+        self.grid[0].len() - 2
+    }
+
+    /// Blizzards come back to their original positions after this many minutes.
+    fn period(&self) -> usize {
+        let (h, w) = (self.inner_height(), self.inner_width());
+        h * w / gcd(h, w)
+    }
+
+    /// Tells whether a blizzard is at the given position at the given time, by looking where
+    /// each kind of blizzard that could be there would have started.
+    fn has_blizzard(&self, (row, col): Position, time: usize) -> bool {
+        let (h, w) = (self.inner_height(), self.inner_width());
+        let (r, c) = (row - 1, col - 1);
+        let from_left = (c + w - time % w) % w + 1;
+        let from_right = (c + time) % w + 1;
+        let from_top = (r + h - time % h) % h + 1;
+        let from_bottom = (r + time) % h + 1;
+        self.grid[row][from_left] == '>'
+            || self.grid[row][from_right] == '<'
+            || self.grid[from_top][col] == 'v'
+            || self.grid[from_bottom][col] == '^'
+    }
+
+    fn is_free(&self, position: Position, time: usize) -> bool {
+        let (row, col) = position;
+        position == self.entrance
+            || position == self.exit
+            || (row >= 1
+                && row <= self.inner_height()
+                && col >= 1
+                && col <= self.inner_width()
+                && !self.has_blizzard(position, time))
+    }
+
+    fn moves(&self, (row, col): Position) -> Vec<Position> {
+        let mut moves = vec![(row, col), (row + 1, col), (row, col + 1)];
+        if row > 0 {
+            moves.push((row - 1, col));
+        }
+        if col > 0 {
+            moves.push((row, col - 1));
+        }
+        moves
+    }
+
+    /// Time at which the goal is reached at the earliest, when leaving from `from` at `start`.
+    fn crossing(&self, from: Position, to: Position, start: usize) -> Result<usize, Error> {
+        let period = self.period();
+        let mut seen = HashSet::from([(from, start % period)]);
+        let mut to_visit = VecDeque::from([(from, start)]);
+        while let Some((position, time)) = to_visit.pop_front() {
+            if position == to {
+                return Ok(time);
+            }
+            for next in self.moves(position) {
+                if self.is_free(next, time + 1) && seen.insert((next, (time + 1) % period)) {
+                    to_visit.push_back((next, time + 1));
+                }
+            }
+        }
+        Err(Error::NoPath)
+    }
+
+    fn single_trip(&self) -> Result<usize, Error> {
+        self.crossing(self.entrance, self.exit, 0)
+    }
+
+    fn trip_back_for_snacks(&self) -> Result<usize, Error> {
+        let there = self.crossing(self.entrance, self.exit, 0)?;
+        let back = self.crossing(self.exit, self.entrance, there)?;
+        self.crossing(self.entrance, self.exit, back)
+    }
+}
+
+fn valley<'a, E>(i: &'a str) -> IResult<&'a str, Vec<Vec<char>>, E>
+where
+    E: ParseError<&'a str>,
+{
+    separated_list1(line_ending, many1(one_of("#.<>^v")))(i)
+}
+
+pub fn parse(data: &str) -> Result<Valley> {
+    let grid = parse_all(data, valley)?;
+    Ok(Valley::new(grid)?)
+}
+
+pub fn solve(valley: &Valley) -> Result<(usize, usize)> {
+    Ok((valley.single_trip()?, valley.trip_back_for_snacks()?))
+}
+
+pub fn basin_crossings_from_str(data: &str) -> Result<(usize, usize)> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::error::AocError;
+    use spectral::prelude::*;
+
+    #[test]
+    fn openings() {
+        let (_, grid) = valley::<()>(EXAMPLE).unwrap();
+        let valley = Valley::new(grid).unwrap();
+
+        assert_that!(valley.entrance).is_equal_to((0, 1));
+        assert_that!(valley.exit).is_equal_to((5, 6));
+        assert_that!(valley.period()).is_equal_to(12);
+    }
+
+    #[test]
+    fn blizzards_move_and_wrap() {
+        let (_, grid) = valley::<()>("#.###\n#>..#\n#...#\n###.#").unwrap();
+        let valley = Valley::new(grid).unwrap();
+
+        assert_that!(valley.has_blizzard((1, 1), 0)).is_true();
+        assert_that!(valley.has_blizzard((1, 3), 2)).is_true();
+        assert_that!(valley.has_blizzard((1, 1), 3)).is_true();
+        assert_that!(valley.has_blizzard((1, 2), 3)).is_false();
+    }
+
+    #[test]
+    fn example_single_trip() {
+        let (_, grid) = valley::<()>(EXAMPLE).unwrap();
+        let valley = Valley::new(grid).unwrap();
+
+        assert_that!(valley.single_trip()).is_ok().is_equal_to(18);
+    }
+
+    #[test]
+    fn example_trip_back_for_snacks() {
+        let (_, grid) = valley::<()>(EXAMPLE).unwrap();
+        let valley = Valley::new(grid).unwrap();
+
+        assert_that!(valley.trip_back_for_snacks())
+            .is_ok()
+            .is_equal_to(54);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_that!(basin_crossings_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to((18, 54));
+    }
+
+    #[test]
+    fn ragged_valley() {
+        let valley = parse("#.###\n#>..#\n#..#\n###.#");
+
+        assert_that!(valley.map_err(|e| e.to_string()))
+            .is_err()
+            .is_equal_to("Row 3 of the valley is 4 tiles wide instead of 5".to_owned());
+    }
+
+    #[test]
+    fn blocked_valley() {
+        let valley = parse("#.###\n#>>>#\n###.#\n");
+
+        assert_that!(valley).is_ok();
+        assert!(matches!(
+            valley.and_then(|v| solve(&v)),
+            Err(AocError::Logic(_))
+        ));
+    }
+}
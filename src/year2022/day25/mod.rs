@@ -0,0 +1,153 @@
+use crate::diagnostics::BadInput;
+use crate::error::{PuzzleError, Result};
+use thiserror::Error;
+
+/// Example input given in the puzzle statement.
+pub const EXAMPLE: &str = include_str!("example.txt");
+
+const BASE: i64 = 5;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SnafuError {
+    #[error("Invalid SNAFU digit {0:?}")]
+    InvalidDigit(char),
+    #[error("Empty SNAFU number")]
+    Empty,
+    #[error("SNAFU number too large")]
+    Overflow,
+}
+
+impl PuzzleError for SnafuError {}
+
+fn digit_value(digit: char) -> Result<i64, SnafuError> {
+    match digit {
+        '=' => Ok(-2),
+        '-' => Ok(-1),
+        '0' => Ok(0),
+        '1' => Ok(1),
+        '2' => Ok(2),
+        _ => Err(SnafuError::InvalidDigit(digit)),
+    }
+}
+
+/// Converts a SNAFU number (balanced base 5, with `=` for -2 and `-` for -1) to decimal.
+pub fn snafu_to_decimal(snafu: &str) -> Result<i64, SnafuError> {
+    if snafu.is_empty() {
+        return Err(SnafuError::Empty);
+    }
+    snafu.chars().try_fold(0i64, |acc, digit| {
+        let value = digit_value(digit)?;
+        acc.checked_mul(BASE)
+            .and_then(|acc| acc.checked_add(value))
+            .ok_or(SnafuError::Overflow)
+    })
+}
+
+/// Converts a decimal number to SNAFU.
+pub fn decimal_to_snafu(decimal: i64) -> String {
+    if decimal == 0 {
+        return "0".to_owned();
+    }
+    let mut digits = vec![];
+    let mut n = decimal;
+    while n != 0 {
+        // shift the remainder from 0..5 to -2..3 so that it maps onto a SNAFU digit, carrying
+        // one to the next digit when it goes negative
+        let (digit, carry) = match n.rem_euclid(BASE) {
+            r if r > 2 => (r - BASE, 1),
+            r => (r, 0),
+        };
+        digits.push(match digit {
+            -2 => '=',
+            -1 => '-',
+            0 => '0',
+            1 => '1',
+            _ => '2',
+        });
+        n = n.div_euclid(BASE) + carry;
+    }
+    digits.iter().rev().collect()
+}
+
+pub fn parse(data: &str) -> Result<Vec<i64>> {
+    Ok(data
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| snafu_to_decimal(line).map_err(|e| BadInput::at(data, line, e)))
+        .collect::<Result<_, _>>()?)
+}
+
+pub fn solve(fuel_requirements: &[i64]) -> Result<String> {
+    let sum = fuel_requirements
+        .iter()
+        .try_fold(0i64, |sum, &requirement| sum.checked_add(requirement))
+        .ok_or(SnafuError::Overflow)?;
+    Ok(decimal_to_snafu(sum))
+}
+
+pub fn fuel_requirements_from_str(data: &str) -> Result<String> {
+    solve(&parse(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use parameterized::parameterized;
+    use spectral::prelude::*;
+
+    #[parameterized(
+        snafu = { "1", "2", "1=", "1-", "10", "11", "12", "2=", "2-", "20", "1=0", "1-0", "1=11-2", "1-0---0", "1121-1110-1=0" },
+        decimal = { 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 15, 20, 2022, 12345, 314159265 }
+    )]
+    fn conversions(snafu: &str, decimal: i64) {
+        assert_that!(snafu_to_decimal(snafu))
+            .is_ok()
+            .is_equal_to(decimal);
+        assert_that!(decimal_to_snafu(decimal)).is_equal_to(snafu.to_owned());
+    }
+
+    #[test]
+    fn zero() {
+        assert_that!(decimal_to_snafu(0)).is_equal_to("0".to_owned());
+        assert_that!(snafu_to_decimal("0")).is_ok().is_equal_to(0);
+    }
+
+    #[test]
+    fn negative_numbers() {
+        assert_that!(decimal_to_snafu(-3)).is_equal_to("-2".to_owned());
+        assert_that!(snafu_to_decimal("-2")).is_ok().is_equal_to(-3);
+    }
+
+    #[test]
+    fn invalid_digit() {
+        assert_that!(snafu_to_decimal("1=3"))
+            .is_err()
+            .is_equal_to(SnafuError::InvalidDigit('3'));
+    }
+
+    #[test]
+    fn overflows() {
+        assert_that!(snafu_to_decimal(&"2".repeat(28)))
+            .is_err()
+            .is_equal_to(SnafuError::Overflow);
+        assert_that!(solve(&[i64::MAX, 1])).is_err();
+    }
+
+    #[test]
+    fn largest_number() {
+        let snafu = decimal_to_snafu(i64::MAX);
+
+        assert_that!(snafu_to_decimal(&snafu))
+            .is_ok()
+            .is_equal_to(i64::MAX);
+    }
+
+    #[test]
+    fn example_fuel_sum() {
+        assert_that!(fuel_requirements_from_str(EXAMPLE))
+            .is_ok()
+            .is_equal_to("2=-1=0".to_owned());
+    }
+}
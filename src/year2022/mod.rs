@@ -0,0 +1,119 @@
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+use crate::runner::{self, Answers, Day};
+
+pub const YEAR: u16 = 2022;
+
+/// Every day of the calendar, in order.
+pub const DAYS: [Day; 25] = [
+    Day::new(1, day01::EXAMPLE, |data| {
+        runner::timed(data, day01::parse, day01::solve)
+    }),
+    Day::new(2, day02::EXAMPLE, |data| {
+        runner::timed(data, day02::parse, day02::solve)
+    }),
+    Day::new(3, day03::EXAMPLE, |data| {
+        runner::timed(data, day03::parse, day03::solve)
+    }),
+    Day::new(4, day04::EXAMPLE, |data| {
+        runner::timed(data, day04::parse, day04::solve)
+    }),
+    Day::new(5, day05::EXAMPLE, |data| {
+        runner::timed(data, day05::parse, day05::solve)
+    }),
+    Day::new(6, day06::EXAMPLE, |data| {
+        runner::timed(data, day06::parse, day06::solve)
+    }),
+    Day::new(7, day07::EXAMPLE, |data| {
+        runner::timed(data, day07::parse, day07::solve)
+    }),
+    Day::new(8, day08::EXAMPLE, |data| {
+        runner::timed(data, day08::parse, day08::solve)
+    }),
+    Day::new(9, day09::EXAMPLE, |data| {
+        runner::timed(data, day09::parse, day09::solve)
+    }),
+    Day::new(10, day10::EXAMPLE, |data| {
+        runner::timed(data, day10::parse, day10::solve)
+    }),
+    Day::new(11, day11::EXAMPLE, |data| {
+        runner::timed(data, day11::parse, day11::solve)
+    }),
+    Day::new(12, day12::EXAMPLE, |data| {
+        runner::timed(data, day12::parse, day12::solve)
+    }),
+    Day::new(13, day13::EXAMPLE, |data| {
+        runner::timed(data, day13::parse, day13::solve)
+    }),
+    Day::new(14, day14::EXAMPLE, |data| {
+        runner::timed(data, day14::parse, day14::solve)
+    }),
+    Day::new(15, day15::EXAMPLE, |data| {
+        runner::timed(data, day15::parse, day15::solve)
+    })
+    .with_example_solver(|data| {
+        runner::timed(data, day15::parse, |sensors| {
+            day15::solve_with(
+                sensors,
+                day15::EXAMPLE_ROW_TO_CHECK,
+                day15::EXAMPLE_SEARCH_AREA_MAX,
+            )
+        })
+    }),
+    Day::new(16, day16::EXAMPLE, |data| {
+        runner::timed(data, day16::parse, day16::solve)
+    }),
+    Day::new(17, day17::EXAMPLE, |data| {
+        runner::timed(data, day17::parse, day17::solve)
+    }),
+    Day::new(18, day18::EXAMPLE, |data| {
+        runner::timed(data, day18::parse, day18::solve)
+    }),
+    Day::new(19, day19::EXAMPLE, |data| {
+        runner::timed(data, day19::parse, day19::solve)
+    }),
+    Day::new(20, day20::EXAMPLE, |data| {
+        runner::timed(data, day20::parse, day20::solve)
+    }),
+    Day::new(21, day21::EXAMPLE, |data| {
+        runner::timed(data, day21::parse, day21::solve)
+    }),
+    Day::new(22, day22::EXAMPLE, |data| {
+        runner::timed(data, day22::parse, day22::solve)
+    }),
+    Day::new(23, day23::EXAMPLE, |data| {
+        runner::timed(data, day23::parse, day23::solve)
+    }),
+    Day::new(24, day24::EXAMPLE, |data| {
+        runner::timed(data, day24::parse, day24::solve)
+    }),
+    Day::new(25, day25::EXAMPLE, |data| {
+        runner::timed(data, day25::parse, |requirements| {
+            day25::solve(requirements).map(Answers::part1)
+        })
+    }),
+];
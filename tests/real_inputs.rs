@@ -0,0 +1,50 @@
+//! Solves every day on the real puzzle inputs, checking the answers against `answers.toml`.
+//!
+//! The inputs are personal, so this only runs with `AOC_REAL_INPUTS=1`, on the days whose input
+//! is in one of the input directories.
+
+use aoc2022::check::{self, Answers};
+use aoc2022::inputs::InputManager;
+use aoc2022::runner;
+use aoc2022::year2022;
+use std::env;
+use std::path::Path;
+
+const ENABLED_VAR: &str = "AOC_REAL_INPUTS";
+const ANSWERS: &str = "answers.toml";
+
+#[test]
+fn real_inputs() {
+    if env::var(ENABLED_VAR).as_deref() != Ok("1") {
+        eprintln!("skipped: set {ENABLED_VAR}=1 to solve the real inputs");
+        return;
+    }
+    let inputs = InputManager::new(year2022::YEAR, None);
+    let days = year2022::DAYS
+        .into_iter()
+        .filter(|day| inputs.find(day.number).is_some())
+        .collect::<Vec<_>>();
+    if days.is_empty() {
+        eprintln!("skipped: no real input found");
+        return;
+    }
+    let expected = Answers::load(Path::new(ANSWERS)).unwrap();
+
+    let reports = runner::run_all(&days, None, None, |day| inputs.load(day.number)).unwrap();
+
+    let failures = reports
+        .iter()
+        .filter_map(|r| {
+            r.outcome
+                .as_ref()
+                .err()
+                .map(|e| format!("Day {}: {e}", r.day))
+        })
+        .collect::<Vec<_>>();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+    let mismatches = check::check(&reports, &expected)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}